@@ -48,6 +48,9 @@ mod sealed {
         /// Determines the value of `Serializer::is_human_readable` and
         /// `Deserializer::is_human_readable`.
         fn is_human_readable() -> bool;
+
+        /// Determines whether map entries are buffered and written sorted by their encoded keys.
+        fn is_sorted_map_keys() -> bool;
     }
 }
 
@@ -100,6 +103,11 @@ impl sealed::SerializerConfig for DefaultConfig {
     fn is_human_readable() -> bool {
         false
     }
+
+    #[inline(always)]
+    fn is_sorted_map_keys() -> bool {
+        false
+    }
 }
 
 /// Config wrapper, that overrides struct serialization by packing as a map with field names.
@@ -161,6 +169,11 @@ where
     fn is_human_readable() -> bool {
         C::is_human_readable()
     }
+
+    #[inline(always)]
+    fn is_sorted_map_keys() -> bool {
+        C::is_sorted_map_keys()
+    }
 }
 
 /// Config wrapper that overrides struct serlization by packing as a tuple without field
@@ -217,6 +230,11 @@ where
     fn is_human_readable() -> bool {
         C::is_human_readable()
     }
+
+    #[inline(always)]
+    fn is_sorted_map_keys() -> bool {
+        C::is_sorted_map_keys()
+    }
 }
 
 /// Config wrapper that overrides `Serializer::is_human_readable` and
@@ -272,6 +290,11 @@ where
     fn is_human_readable() -> bool {
         true
     }
+
+    #[inline(always)]
+    fn is_sorted_map_keys() -> bool {
+        C::is_sorted_map_keys()
+    }
 }
 
 /// Config wrapper that overrides `Serializer::is_human_readable` and
@@ -327,4 +350,163 @@ where
     fn is_human_readable() -> bool {
         false
     }
+
+    #[inline(always)]
+    fn is_sorted_map_keys() -> bool {
+        C::is_sorted_map_keys()
+    }
+}
+
+/// The layout of `std::time::Duration` and `std::time::SystemTime`, selected with
+/// `Serializer::set_time_layout` and `Deserializer::set_time_layout`.
+///
+/// Both types are written as seconds and nanoseconds, counted since the Unix epoch for
+/// `SystemTime`. A deserializer with a layout set accepts only that layout, and rejects the others
+/// as well as serde's default struct representation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimeLayout {
+    /// The MessagePack timestamp extension (type `-1`), in the most compact of its layouts.
+    TimestampExt,
+    /// An array of the seconds and the nanoseconds, `[secs, nanos]`.
+    SecondsNanosArray,
+    /// An unsigned integer of the total nanoseconds, which covers about 584 years.
+    TotalNanosInt,
+}
+
+/// Config wrapper that overrides enum serialization by writing variants by their index instead of
+/// their name.
+///
+/// A unit variant becomes a bare unsigned integer, and other variants become a single-entry map
+/// keyed by the index. The index is the position of the variant in the enum declaration, which is
+/// also its discriminant unless explicit discriminants are given; those are written by `serde_repr`
+/// without this config.
+///
+/// The deserializer accepts variant indices regardless of this config, rejecting indices the enum
+/// doesn't have with `decode::Error::UnknownVariant`.
+#[derive(Copy, Clone, Debug)]
+pub struct VariantIndexConfig<C>(C);
+
+impl<C> VariantIndexConfig<C> {
+    /// Creates a `VariantIndexConfig` inheriting unchanged configuration options from the given configuration.
+    #[inline]
+    pub fn new(inner: C) -> Self {
+        Self(inner)
+    }
+}
+
+impl<C> sealed::SerializerConfig for VariantIndexConfig<C>
+where
+    C: sealed::SerializerConfig,
+{
+    #[inline]
+    fn write_struct_len<S>(ser: &mut S, len: usize) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+    {
+        C::write_struct_len(ser, len)
+    }
+
+    #[inline]
+    fn write_struct_field<S, T>(ser: &mut S, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+        T: ?Sized + Serialize,
+    {
+        C::write_struct_field(ser, key, value)
+    }
+
+    #[inline]
+    fn write_variant_ident<S>(
+        ser: &mut S,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+    {
+        ser.serialize_u32(variant_index)
+    }
+
+    #[inline(always)]
+    fn is_human_readable() -> bool {
+        C::is_human_readable()
+    }
+
+    #[inline(always)]
+    fn is_sorted_map_keys() -> bool {
+        C::is_sorted_map_keys()
+    }
+}
+
+/// Config wrapper that overrides map serialization by writing the entries sorted by their encoded
+/// keys.
+///
+/// Equal maps are then always written as the same bytes, no matter in which order they are
+/// iterated, which is needed for hashing or signing the output. Keys are compared byte by byte as
+/// encoded, so for example shorter strings come before longer ones. Entries with equal keys keep
+/// their relative order. Nested maps are sorted as well.
+///
+/// Every entry of a map is buffered until the map ends, so this costs an allocation per key and
+/// value and a sort, while without this config entries are streamed straight into the writer.
+/// Struct fields are not affected and keep their declaration order, which is already
+/// deterministic.
+#[derive(Copy, Clone, Debug)]
+pub struct SortedMapKeysConfig<C>(C);
+
+impl<C> SortedMapKeysConfig<C> {
+    /// Creates a `SortedMapKeysConfig` inheriting unchanged configuration options from the given configuration.
+    #[inline]
+    pub fn new(inner: C) -> Self {
+        Self(inner)
+    }
+}
+
+impl<C> sealed::SerializerConfig for SortedMapKeysConfig<C>
+where
+    C: sealed::SerializerConfig,
+{
+    #[inline]
+    fn write_struct_len<S>(ser: &mut S, len: usize) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+    {
+        C::write_struct_len(ser, len)
+    }
+
+    #[inline]
+    fn write_struct_field<S, T>(ser: &mut S, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+        T: ?Sized + Serialize,
+    {
+        C::write_struct_field(ser, key, value)
+    }
+
+    #[inline]
+    fn write_variant_ident<S>(
+        ser: &mut S,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+    {
+        C::write_variant_ident(ser, variant_index, variant)
+    }
+
+    #[inline(always)]
+    fn is_human_readable() -> bool {
+        C::is_human_readable()
+    }
+
+    #[inline(always)]
+    fn is_sorted_map_keys() -> bool {
+        true
+    }
 }
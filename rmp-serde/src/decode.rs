@@ -1,22 +1,25 @@
 //! Generic MessagePack deserialization.
 
+use std::collections::HashSet;
 use std::convert::TryInto;
 use std::error;
 use std::fmt::{self, Display, Formatter};
 use std::io::{self, Cursor, ErrorKind, Read};
+use std::marker::PhantomData;
 use std::num::TryFromIntError;
 use std::str::{self, Utf8Error};
 
 use byteorder::{self, ReadBytesExt};
 
 use serde;
-use serde::de::{self, Deserialize, DeserializeOwned, DeserializeSeed, Unexpected, Visitor};
+use serde::de::{self, Deserialize, DeserializeOwned, DeserializeSeed, IntoDeserializer, Unexpected, Visitor};
 
 use rmp;
-use rmp::decode::{self, RmpRead, DecodeStringError, MarkerReadError, NumValueReadError, ValueReadError};
+use rmp::decode::{self, RmpRead, DecodeError, DecodeStringError, MarkerReadError, NumValueReadError, ValueReadError};
 use rmp::Marker;
 
-use crate::config::{BinaryConfig, DefaultConfig, HumanReadableConfig, SerializerConfig};
+use crate::config::{BinaryConfig, DefaultConfig, HumanReadableConfig, SerializerConfig, TimeLayout};
+use crate::timestamp::{self, NANOS_PER_SEC, SYSTEM_TIME_FIELDS, SYSTEM_TIME_NAME, TIMESTAMP_EXT_TYPE};
 use crate::MSGPACK_EXT_STRUCT_NAME;
 
 /// Enum representing errors that can occur while decoding MessagePack data.
@@ -44,6 +47,25 @@ pub enum Error {
     Utf8Error(Utf8Error),
     /// The depth limit was exceeded.
     DepthLimitExceeded,
+    /// The same key appeared twice in a map.
+    ///
+    /// Returned only if enabled with
+    /// [`Deserializer::set_reject_duplicate_keys`](Deserializer::set_reject_duplicate_keys).
+    DuplicateKey,
+    /// An enum variant was encoded by an index that the enum doesn't have.
+    ///
+    /// See [`Serializer::with_variant_indices`](crate::Serializer::with_variant_indices).
+    UnknownVariant(u64),
+    /// The input continues after the decoded value.
+    ///
+    /// Returned by [`from_slice_exact`] with the number of bytes the value took and the total
+    /// length of the input.
+    TrailingData {
+        /// Length of the decoded value.
+        consumed: u64,
+        /// Length of the whole input.
+        total: u64,
+    },
 }
 
 macro_rules! depth_count(
@@ -73,6 +95,9 @@ impl error::Error for Error {
             Error::Syntax(..) => None,
             Error::Utf8Error(ref err) => Some(err),
             Error::DepthLimitExceeded => None,
+            Error::DuplicateKey => None,
+            Error::UnknownVariant(..) => None,
+            Error::TrailingData { .. } => None,
         }
     }
 }
@@ -103,6 +128,15 @@ impl Display for Error {
             Error::Syntax(ref msg) => fmt.write_str(msg),
             Error::Utf8Error(ref err) => write!(fmt, "string found to be invalid utf8: {}", err),
             Error::DepthLimitExceeded => fmt.write_str("depth limit exceeded"),
+            Error::DuplicateKey => fmt.write_str("duplicate map key"),
+            Error::UnknownVariant(idx) => write!(fmt, "unknown enum variant index {}", idx),
+            Error::TrailingData { consumed, total } => write!(
+                fmt,
+                "{} trailing bytes after the value, which ends at byte {} of {}",
+                total - consumed,
+                consumed,
+                total
+            ),
         }
     }
 }
@@ -154,7 +188,7 @@ impl<'a> From<DecodeStringError<'a>> for Error {
             DecodeStringError::InvalidDataRead(err) => Error::InvalidDataRead(err),
             DecodeStringError::TypeMismatch(marker) => Error::TypeMismatch(marker),
             DecodeStringError::BufferSizeTooSmall(..) => Error::Uncategorized("BufferSizeTooSmall".to_string()),
-            DecodeStringError::InvalidUtf8(..) => Error::Uncategorized("InvalidUtf8".to_string()),
+            DecodeStringError::InvalidUtf8(_, err) => Error::Utf8Error(err),
         }
     }
 }
@@ -178,6 +212,8 @@ pub struct Deserializer<R, C = DefaultConfig> {
     config: C,
     marker: Option<Marker>,
     depth: usize,
+    reject_duplicate_keys: bool,
+    time_layout: Option<TimeLayout>,
 }
 
 impl<R: Read, C> Deserializer<R, C> {
@@ -197,6 +233,25 @@ impl<R: Read, C> Deserializer<R, C> {
             Ok(self.marker.insert(m).to_owned())
         }
     }
+
+    /// Reads the index of an enum variant if the next value is an unsigned integer, as written by
+    /// `Serializer::with_variant_indices`, leaving any other value in place.
+    fn read_variant_index(&mut self, variants: &[&str]) -> Result<Option<u32>, Error> {
+        let idx = match self.peek_or_read_marker()? {
+            Marker::FixPos(val) => u64::from(val),
+            Marker::U8 => u64::from(self.rd.read_data_u8()?),
+            Marker::U16 => u64::from(self.rd.read_data_u16()?),
+            Marker::U32 => u64::from(self.rd.read_data_u32()?),
+            Marker::U64 => self.rd.read_data_u64()?,
+            _ => return Ok(None),
+        };
+        self.marker = None;
+
+        if idx >= variants.len() as u64 {
+            return Err(Error::UnknownVariant(idx));
+        }
+        Ok(Some(idx as u32))
+    }
 }
 
 impl<R: Read> Deserializer<ReadReader<R>, DefaultConfig> {
@@ -209,6 +264,8 @@ impl<R: Read> Deserializer<ReadReader<R>, DefaultConfig> {
             // Cached marker in case of deserializing optional values.
             marker: None,
             depth: 1024,
+            reject_duplicate_keys: false,
+            time_layout: None,
         }
     }
 }
@@ -241,12 +298,14 @@ impl<R: Read, C: SerializerConfig> Deserializer<R, C> {
     /// versions of `rmp-serde`.
     #[inline]
     pub fn with_human_readable(self) -> Deserializer<R, HumanReadableConfig<C>> {
-        let Deserializer { rd, config, marker, depth } = self;
+        let Deserializer { rd, config, marker, depth, reject_duplicate_keys, time_layout } = self;
         Deserializer {
             rd,
             config: HumanReadableConfig::new(config),
             marker,
             depth,
+            reject_duplicate_keys,
+            time_layout,
         }
     }
 
@@ -257,12 +316,14 @@ impl<R: Read, C: SerializerConfig> Deserializer<R, C> {
     /// representation.
     #[inline]
     pub fn with_binary(self) -> Deserializer<R, BinaryConfig<C>> {
-        let Deserializer { rd, config, marker, depth } = self;
+        let Deserializer { rd, config, marker, depth, reject_duplicate_keys, time_layout } = self;
         Deserializer {
             rd,
             config: BinaryConfig::new(config),
             marker,
             depth,
+            reject_duplicate_keys,
+            time_layout,
         }
     }
 }
@@ -287,6 +348,8 @@ where
             config: DefaultConfig,
             marker: None,
             depth: 1024,
+            reject_duplicate_keys: false,
+            time_layout: None,
         }
     }
 
@@ -297,6 +360,37 @@ where
     }
 }
 
+impl<'de> Deserializer<ReadRefReader<'de, [u8]>> {
+    /// Constructs a new `Deserializer` from the remaining bytes of the given [Bytes](decode::Bytes)
+    /// reader, with zero-copy.
+    ///
+    /// Like [Deserializer::from_read_ref], strings and binaries are passed to the visitor with
+    /// `visit_borrowed_str` and `visit_borrowed_bytes`, so `&'de str` and `#[serde(borrow)]` fields
+    /// point into the buffer of `bytes`. This allows mixing the raw `rmp` decode functions with
+    /// serde on the same buffer, picking up where the reader stopped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmp::decode::{self, Bytes};
+    /// use rmp_serde::Deserializer;
+    /// use serde::Deserialize;
+    ///
+    /// // Encoded `1`, `["Bobby", 8]`.
+    /// let buf = [0x01, 0x92, 0xa5, 0x42, 0x6f, 0x62, 0x62, 0x79, 0x08];
+    /// let mut rd = Bytes::new(&buf);
+    /// assert_eq!(1, decode::read_pfix(&mut rd).unwrap());
+    ///
+    /// let mut de = Deserializer::from_bytes(rd);
+    /// let (name, age): (&str, u8) = Deserialize::deserialize(&mut de).unwrap();
+    /// assert_eq!(("Bobby", 8), (name, age));
+    /// ```
+    #[inline]
+    pub fn from_bytes(bytes: decode::Bytes<'de>) -> Self {
+        Deserializer::from_read_ref(bytes.remaining_slice())
+    }
+}
+
 impl<'de, R: ReadSlice<'de>, C: SerializerConfig> Deserializer<R, C> {
     /// Changes the maximum nesting depth that is allowed
     #[inline(always)]
@@ -304,6 +398,111 @@ impl<'de, R: ReadSlice<'de>, C: SerializerConfig> Deserializer<R, C> {
         self.depth = depth;
     }
 
+    /// Makes map decoding fail with `Error::DuplicateKey` if the same key appears twice in a map.
+    ///
+    /// By default the last value wins when decoding into a `BTreeMap` or `HashMap`, and a struct
+    /// fails with a "duplicate field" error only if it is derived. With this option enabled keys
+    /// are compared by their encoded bytes, after normalizing them to the smallest encoding, so
+    /// `5` written as a fixint and as a `u64` are the same key.
+    ///
+    /// Keys are still deserialized from the input, so borrowed keys like `&'de str` work, but a
+    /// copy of each key's encoding is kept until the map ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// use rmp_serde::decode::Error;
+    /// use rmp_serde::Deserializer;
+    /// use serde::Deserialize;
+    ///
+    /// // {"a": 1, "a": 2}
+    /// let buf = [0x82, 0xa1, 0x61, 0x01, 0xa1, 0x61, 0x02];
+    ///
+    /// let mut de = Deserializer::new(&buf[..]);
+    /// de.set_reject_duplicate_keys(true);
+    ///
+    /// match BTreeMap::<String, u8>::deserialize(&mut de) {
+    ///     Err(Error::DuplicateKey) => {}
+    ///     other => panic!("unexpected result: {:?}", other),
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn set_reject_duplicate_keys(&mut self, enabled: bool) {
+        self.reject_duplicate_keys = enabled;
+    }
+
+    /// Makes `std::time::Duration` and `std::time::SystemTime` decode only from the given layout.
+    ///
+    /// Any other encoding of them, including serde's default struct representation, fails with
+    /// `Error::TypeMismatch` carrying the marker found, or with `Error::LengthMismatch` for an
+    /// array that doesn't have two elements. Without a layout they are decoded from serde's
+    /// structs, and `SystemTime` from the timestamp extension as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use rmp_serde::config::TimeLayout;
+    /// use rmp_serde::decode::Error;
+    /// use rmp_serde::Deserializer;
+    /// use serde::Deserialize;
+    ///
+    /// // 1.5 seconds as the total nanoseconds.
+    /// let buf = [0xce, 0x59, 0x68, 0x2f, 0x00];
+    ///
+    /// let mut de = Deserializer::new(&buf[..]);
+    /// de.set_time_layout(TimeLayout::TotalNanosInt);
+    /// assert_eq!(Duration::from_millis(1500), Duration::deserialize(&mut de).unwrap());
+    ///
+    /// let mut de = Deserializer::new(&buf[..]);
+    /// de.set_time_layout(TimeLayout::SecondsNanosArray);
+    /// match Duration::deserialize(&mut de) {
+    ///     Err(Error::TypeMismatch(..)) => {}
+    ///     other => panic!("unexpected result: {:?}", other),
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn set_time_layout(&mut self, layout: TimeLayout) {
+        self.time_layout = Some(layout);
+    }
+
+    /// Turns this deserializer into an iterator over consecutive values of type `T`.
+    ///
+    /// This is useful for reading streams of MessagePack values concatenated back-to-back, like
+    /// log files. The iterator ends when the input ends exactly at a value boundary. If it ends in
+    /// the middle of a value, an error is yielded instead.
+    ///
+    /// After the first error the iterator stops, because the position in the stream is undefined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmp_serde::Deserializer;
+    ///
+    /// // Encoded `"one"`, `"two"`.
+    /// let buf = [0xa3, 0x6f, 0x6e, 0x65, 0xa3, 0x74, 0x77, 0x6f];
+    ///
+    /// let de = Deserializer::new(&buf[..]);
+    /// let values: Vec<String> = de.into_iter().collect::<Result<_, _>>().unwrap();
+    ///
+    /// assert_eq!(vec!["one", "two"], values);
+    /// ```
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    pub fn into_iter<T>(self) -> StreamDeserializer<'de, R, C, T>
+        where T: Deserialize<'de>
+    {
+        StreamDeserializer {
+            de: self,
+            done: false,
+            _lifetime: PhantomData,
+            _output: PhantomData,
+        }
+    }
+
     fn read_str_data<V>(&mut self, len: u32, visitor: V) -> Result<V::Value, Error>
         where V: Visitor<'de>
     {
@@ -335,6 +534,204 @@ impl<'de, R: ReadSlice<'de>, C: SerializerConfig> Deserializer<R, C> {
         }
     }
 
+    /// Reads the timestamp extension, passing it to the visitor as a `SystemTime` sequence of
+    /// seconds and nanoseconds since the Unix epoch.
+    fn read_system_time<V>(&mut self, marker: Marker, visitor: V) -> Result<V::Value, Error>
+        where V: Visitor<'de>
+    {
+        let len = ext_len(&mut self.rd, marker)?;
+        if self.rd.read_data_i8()? != TIMESTAMP_EXT_TYPE {
+            return Err(Error::TypeMismatch(marker));
+        }
+
+        let data = match read_bin_data(&mut self.rd, len)? {
+            Reference::Borrowed(buf) => buf,
+            Reference::Copied(buf) => buf,
+        };
+        let (secs, nanos) = timestamp::read_timestamp(data)
+            .ok_or_else(|| Error::Syntax("invalid timestamp extension data".into()))?;
+        if secs < 0 {
+            return Err(de::Error::invalid_value(Unexpected::Signed(secs), &"timestamp after Unix epoch"));
+        }
+
+        let seq = vec![secs as u64, u64::from(nanos)].into_iter();
+        visitor.visit_seq(de::value::SeqDeserializer::new(seq))
+    }
+
+    /// Reads a `Duration` or `SystemTime` in exactly the given layout, passing it to the visitor
+    /// as a sequence of seconds and nanoseconds.
+    fn read_time<V>(&mut self, layout: TimeLayout, visitor: V) -> Result<V::Value, Error>
+        where V: Visitor<'de>
+    {
+        let marker = self.take_or_read_marker()?;
+        let (secs, nanos) = match layout {
+            TimeLayout::TimestampExt => {
+                return match marker {
+                    Marker::FixExt4 | Marker::FixExt8 | Marker::Ext8 => self.read_system_time(marker, visitor),
+                    marker => Err(Error::TypeMismatch(marker)),
+                };
+            }
+            TimeLayout::SecondsNanosArray => {
+                match marker {
+                    Marker::FixArray(2) => {}
+                    Marker::FixArray(len) => return Err(Error::LengthMismatch(len.into())),
+                    marker => return Err(Error::TypeMismatch(marker)),
+                }
+                let secs = u64::deserialize(&mut *self)?;
+                let nanos = u32::deserialize(&mut *self)?;
+                if nanos >= NANOS_PER_SEC {
+                    return Err(de::Error::invalid_value(Unexpected::Unsigned(nanos.into()), &"nanoseconds below one second"));
+                }
+                (secs, nanos)
+            }
+            TimeLayout::TotalNanosInt => {
+                match marker {
+                    Marker::FixPos(..) | Marker::U8 | Marker::U16 | Marker::U32 | Marker::U64 => {}
+                    marker => return Err(Error::TypeMismatch(marker)),
+                }
+                self.marker = Some(marker);
+                timestamp::from_total_nanos(u64::deserialize(&mut *self)?)
+            }
+        };
+
+        let seq = vec![secs, u64::from(nanos)].into_iter();
+        visitor.visit_seq(de::value::SeqDeserializer::new(seq))
+    }
+
+    /// Skips the next value entirely, without validating strings.
+    fn skip_value(&mut self) -> Result<(), Error> {
+        let marker = self.take_or_read_marker()?;
+
+        // Entering a container costs a level of depth, like in `depth_count!`.
+        let max_depth = self.depth.saturating_sub(1);
+        decode::skip_value_data(&mut self.rd, marker, max_depth).map_err(|err| match err {
+            DecodeError::InvalidMarkerRead(err) => Error::InvalidMarkerRead(err),
+            DecodeError::InvalidDataRead(err) => Error::InvalidDataRead(err),
+            DecodeError::TypeMismatch(marker) => Error::TypeMismatch(marker),
+            DecodeError::DepthLimitExceeded => Error::DepthLimitExceeded,
+            err => Error::Uncategorized(err.to_string()),
+        })
+    }
+
+    /// Copies the next value into `out` with every marker and length in its smallest encoding,
+    /// so that equal values produce equal bytes.
+    fn copy_value_canonical(&mut self, out: &mut Vec<u8>) -> Result<(), Error> {
+        let marker = self.take_or_read_marker()?;
+
+        let len = match marker {
+            Marker::Null |
+            Marker::True |
+            Marker::False => {
+                out.push(marker.to_u8());
+                return Ok(());
+            }
+            Marker::FixPos(val) => return write_canonical(rmp::encode::write_uint(out, val.into())),
+            Marker::FixNeg(val) => return write_canonical(rmp::encode::write_sint(out, val.into())),
+            Marker::U8 => return write_canonical(rmp::encode::write_uint(out, self.rd.read_data_u8()?.into())),
+            Marker::U16 => return write_canonical(rmp::encode::write_uint(out, self.rd.read_data_u16()?.into())),
+            Marker::U32 => return write_canonical(rmp::encode::write_uint(out, self.rd.read_data_u32()?.into())),
+            Marker::U64 => return write_canonical(rmp::encode::write_uint(out, self.rd.read_data_u64()?)),
+            Marker::I8 |
+            Marker::I16 |
+            Marker::I32 |
+            Marker::I64 => {
+                let val = match marker {
+                    Marker::I8 => self.rd.read_data_i8()?.into(),
+                    Marker::I16 => self.rd.read_data_i16()?.into(),
+                    Marker::I32 => self.rd.read_data_i32()?.into(),
+                    _ => self.rd.read_data_i64()?,
+                };
+                return if val < 0 {
+                    write_canonical(rmp::encode::write_sint(out, val))
+                } else {
+                    write_canonical(rmp::encode::write_uint(out, val as u64))
+                };
+            }
+            Marker::F32 | Marker::F64 => {
+                out.push(marker.to_u8());
+                if marker == Marker::F32 { 4 } else { 8 }
+            }
+            Marker::FixStr(len) => {
+                out.push(marker.to_u8());
+                len.into()
+            }
+            Marker::Str8 | Marker::Str16 | Marker::Str32 => {
+                let len = match marker {
+                    Marker::Str8 => read_u8(&mut self.rd)?.into(),
+                    Marker::Str16 => read_u16(&mut self.rd)?.into(),
+                    _ => read_u32(&mut self.rd)?,
+                };
+                write_canonical(rmp::encode::write_str_len(out, len))?;
+                len
+            }
+            Marker::Bin8 | Marker::Bin16 | Marker::Bin32 => {
+                let len = match marker {
+                    Marker::Bin8 => read_u8(&mut self.rd)?.into(),
+                    Marker::Bin16 => read_u16(&mut self.rd)?.into(),
+                    _ => read_u32(&mut self.rd)?,
+                };
+                write_canonical(rmp::encode::write_bin_len(out, len))?;
+                len
+            }
+            Marker::FixArray(_) |
+            Marker::Array16 |
+            Marker::Array32 |
+            Marker::FixMap(_) |
+            Marker::Map16 |
+            Marker::Map32 => {
+                let count = match marker {
+                    Marker::FixArray(len) => u64::from(len),
+                    Marker::Array16 => read_u16(&mut self.rd)?.into(),
+                    Marker::Array32 => read_u32(&mut self.rd)?.into(),
+                    Marker::FixMap(len) => u64::from(len),
+                    Marker::Map16 => read_u16(&mut self.rd)?.into(),
+                    Marker::Map32 => read_u32(&mut self.rd)?.into(),
+                    _ => unreachable!(),
+                };
+                let count = match marker {
+                    Marker::FixArray(_) | Marker::Array16 | Marker::Array32 => {
+                        write_canonical(rmp::encode::write_array_len(out, count as u32))?;
+                        count
+                    }
+                    _ => {
+                        write_canonical(rmp::encode::write_map_len(out, count as u32))?;
+                        count * 2
+                    }
+                };
+
+                return depth_count!(self.depth, {
+                    for _ in 0..count {
+                        self.copy_value_canonical(out)?;
+                    }
+                    Ok(())
+                });
+            }
+            Marker::FixExt1 |
+            Marker::FixExt2 |
+            Marker::FixExt4 |
+            Marker::FixExt8 |
+            Marker::FixExt16 |
+            Marker::Ext8 |
+            Marker::Ext16 |
+            Marker::Ext32 => {
+                let len = ext_len(&mut self.rd, marker)?;
+                let ty = self.rd.read_data_i8()?;
+                write_canonical(rmp::encode::write_ext_meta(out, len, ty))?;
+                len
+            }
+            Marker::Reserved => return Err(Error::TypeMismatch(Marker::Reserved)),
+        };
+
+        let len = u64::from(len);
+        let copied = io::copy(&mut (&mut self.rd).take(len), out)
+            .map_err(Error::InvalidDataRead)?;
+        if copied != len {
+            return Err(Error::InvalidDataRead(ErrorKind::UnexpectedEof.into()));
+        }
+
+        Ok(())
+    }
+
     fn read_128(&mut self) -> Result<[u8; 16], Error> {
         let marker = self.take_or_read_marker()?;
 
@@ -357,6 +754,64 @@ impl<'de, R: ReadSlice<'de>, C: SerializerConfig> Deserializer<R, C> {
     }
 }
 
+/// Iterator that deserializes a stream of consecutive MessagePack values into values of type `T`.
+///
+/// Constructed with [`Deserializer::into_iter`].
+#[derive(Debug)]
+pub struct StreamDeserializer<'de, R, C, T> {
+    de: Deserializer<R, C>,
+    done: bool,
+    _lifetime: PhantomData<&'de ()>,
+    _output: PhantomData<fn() -> T>,
+}
+
+impl<'de, R, C, T> StreamDeserializer<'de, R, C, T> {
+    /// Consumes this iterator, returning the underlying deserializer.
+    #[inline]
+    pub fn into_inner(self) -> Deserializer<R, C> {
+        self.de
+    }
+}
+
+impl<'de, R, C, T> Iterator for StreamDeserializer<'de, R, C, T>
+where
+    R: ReadSlice<'de>,
+    C: SerializerConfig,
+    T: Deserialize<'de>,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // Reading the marker fails with `UnexpectedEof` only when there are no bytes left, which
+        // means that the previous value was the last one.
+        match self.de.peek_or_read_marker() {
+            Ok(..) => {}
+            Err(MarkerReadError(err)) if err.kind() == ErrorKind::UnexpectedEof => {
+                self.done = true;
+                return None;
+            }
+            Err(err) => {
+                self.done = true;
+                return Some(Err(err.into()));
+            }
+        }
+
+        let res = T::deserialize(&mut self.de);
+        self.done = res.is_err();
+        Some(res)
+    }
+}
+
+/// Unwraps the result of encoding into a `Vec`, which can't fail.
+#[inline]
+fn write_canonical<T>(res: Result<T, rmp::encode::ValueWriteError>) -> Result<(), Error> {
+    res.map(|_| ()).map_err(|err| Error::Uncategorized(err.to_string()))
+}
+
 fn read_bin_data<'a, 'de, R: ReadSlice<'de>>(rd: &'a mut R, len: u32) -> Result<Reference<'de,'a, [u8]>, Error> {
     rd.read_slice(len as usize).map_err(Error::InvalidDataRead)
 }
@@ -612,9 +1067,13 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
         }
     }
 
-    fn deserialize_enum<V>(self, _name: &str, _variants: &[&str], visitor: V) -> Result<V::Value, Error>
+    fn deserialize_enum<V>(self, _name: &str, variants: &'static [&'static str], visitor: V) -> Result<V::Value, Error>
         where V: Visitor<'de>
     {
+        if let Some(idx) = self.read_variant_index(variants)? {
+            return visitor.visit_enum(idx.into_deserializer());
+        }
+
         let marker = self.peek_or_read_marker()?;
         match rmp::decode::marker_to_len(&mut self.rd, marker) {
             Ok(len) => match len {
@@ -623,7 +1082,7 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
                 // or as just the variant
                 1 => {
                     self.marker = None;
-                    visitor.visit_enum(VariantAccess::new(self))
+                    visitor.visit_enum(VariantAccess::new(self, variants))
                 }
                 n => Err(Error::LengthMismatch(n as u32)),
             },
@@ -646,6 +1105,27 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
         visitor.visit_newtype_struct(self)
     }
 
+    fn deserialize_struct<V>(self, name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        if let Some(layout) = self.time_layout {
+            if timestamp::time_fields(name) == Some(fields) {
+                return self.read_time(layout, visitor);
+            }
+        }
+
+        if name == SYSTEM_TIME_NAME && fields == SYSTEM_TIME_FIELDS {
+            match self.take_or_read_marker()? {
+                marker @ (Marker::FixExt4 | Marker::FixExt8 | Marker::Ext8) => {
+                    return self.read_system_time(marker, visitor);
+                }
+                marker => self.marker = Some(marker),
+            }
+        }
+
+        self.deserialize_any(visitor)
+    }
+
     fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
         where V: Visitor<'de>
     {
@@ -661,6 +1141,13 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
         }
     }
 
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        self.skip_value()?;
+        visitor.visit_unit()
+    }
+
     #[inline]
     fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
@@ -682,8 +1169,8 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
     forward_to_deserialize_any! {
         bool u8 u16 u32 u64 i8 i16 i32 i64 f32
         f64 char str string bytes byte_buf unit
-        seq map struct identifier tuple
-        tuple_struct ignored_any
+        seq map identifier tuple
+        tuple_struct
     }
 }
 
@@ -726,13 +1213,20 @@ impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::SeqAccess<'de> fo
 struct MapAccess<'a, R, C> {
     de: &'a mut Deserializer<R, C>,
     left: u32,
+    /// Canonical encodings of the keys seen so far, if duplicate keys are rejected.
+    seen: Option<HashSet<Vec<u8>>>,
+    /// The encoding of the current key as it was read, reused across keys.
+    key: Vec<u8>,
 }
 
 impl<'a, R: 'a, C> MapAccess<'a, R, C> {
     fn new(de: &'a mut Deserializer<R, C>, len: u32) -> Self {
+        let seen = if de.reject_duplicate_keys { Some(HashSet::new()) } else { None };
         MapAccess {
             de,
             left: len,
+            seen,
+            key: Vec::new(),
         }
     }
 }
@@ -744,12 +1238,47 @@ impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::MapAccess<'de> fo
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
         where K: DeserializeSeed<'de>
     {
-        if self.left > 0 {
-            self.left -= 1;
-            seed.deserialize(&mut *self.de).map(Some)
-        } else {
-            Ok(None)
+        if self.left == 0 {
+            return Ok(None);
+        }
+        self.left -= 1;
+
+        let seen = match self.seen {
+            Some(ref mut seen) => seen,
+            None => return seed.deserialize(&mut *self.de).map(Some),
+        };
+
+        // Deserialize the key straight from the input, so that it can borrow from it, and compare
+        // the keys by a copy of their encoding.
+        self.key.clear();
+        if let Some(marker) = self.de.marker {
+            self.key.push(marker.to_u8());
+        }
+        let mut de = Deserializer {
+            rd: RecordingReader { rd: &mut self.de.rd, rec: &mut self.key },
+            config: self.de.config,
+            marker: self.de.marker.take(),
+            depth: self.de.depth,
+            reject_duplicate_keys: true,
+            time_layout: self.de.time_layout,
+        };
+        let val = seed.deserialize(&mut de)?;
+
+        let mut key = Vec::with_capacity(self.key.len());
+        let mut de = Deserializer {
+            rd: ReadRefReader::new(&self.key[..]),
+            config: self.de.config,
+            marker: None,
+            depth: self.de.depth,
+            reject_duplicate_keys: true,
+            time_layout: self.de.time_layout,
+        };
+        de.copy_value_canonical(&mut key)?;
+
+        if !seen.insert(key) {
+            return Err(Error::DuplicateKey);
         }
+        Ok(Some(val))
     }
 
     #[inline]
@@ -837,11 +1366,12 @@ impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::VariantAccess<'de
 
 struct VariantAccess<'a, R, C> {
     de: &'a mut Deserializer<R, C>,
+    variants: &'static [&'static str],
 }
 
 impl<'a, R: 'a, C> VariantAccess<'a, R, C> {
-    pub fn new(de: &'a mut Deserializer<R, C>) -> Self {
-        VariantAccess { de }
+    pub fn new(de: &'a mut Deserializer<R, C>, variants: &'static [&'static str]) -> Self {
+        VariantAccess { de, variants }
     }
 }
 
@@ -853,7 +1383,11 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> de::EnumAccess<'de> for Va
     fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Error>
         where V: de::DeserializeSeed<'de>,
     {
-        Ok((seed.deserialize(&mut *self.de)?, self))
+        let variant = match self.de.read_variant_index(self.variants)? {
+            Some(idx) => seed.deserialize(IntoDeserializer::<Error>::into_deserializer(idx))?,
+            None => seed.deserialize(&mut *self.de)?,
+        };
+        Ok((variant, self))
     }
 }
 
@@ -947,6 +1481,43 @@ impl<R: Read> Read for ReadReader<R> {
     }
 }
 
+/// Reader wrapper that appends a copy of everything read through it to `rec`.
+///
+/// The reader is a trait object, so that keys of maps nested in a key are recorded with the same
+/// type instead of one more wrapper per level.
+struct RecordingReader<'r, 'de> {
+    rd: &'r mut dyn ReadSlice<'de>,
+    rec: &'r mut Vec<u8>,
+}
+
+impl<'r, 'de> ReadSlice<'de> for RecordingReader<'r, 'de> {
+    #[inline]
+    fn read_slice<'a>(&'a mut self, len: usize) -> Result<Reference<'de, 'a, [u8]>, io::Error> {
+        let data = self.rd.read_slice(len)?;
+        match data {
+            Reference::Borrowed(buf) => self.rec.extend_from_slice(buf),
+            Reference::Copied(buf) => self.rec.extend_from_slice(buf),
+        }
+        Ok(data)
+    }
+}
+
+impl<'r, 'de> Read for RecordingReader<'r, 'de> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.rd.read(buf)?;
+        self.rec.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.rd.read_exact(buf)?;
+        self.rec.extend_from_slice(buf);
+        Ok(())
+    }
+}
+
 /// Borrowed reader wrapper.
 #[derive(Debug)]
 pub struct ReadRefReader<'a, R: ?Sized> {
@@ -1021,11 +1592,33 @@ where R: Read,
     Deserialize::deserialize(&mut Deserializer::new(rd))
 }
 
+/// Deserialize an instance of type `T` from a reader of MessagePack.
+///
+/// This is the same as [from_read], named like in other serde formats. Both the compact and the
+/// struct-as-map representations are accepted, so there is no separate `_named` variant.
+///
+/// # Errors
+///
+/// This conversion can fail if the structure of the Value does not match the structure expected
+/// by `T`. It can also fail if the structure is correct but `T`'s implementation of `Deserialize`
+/// decides that something is wrong with the data, for example required struct fields are missing.
+#[inline]
+pub fn from_reader<R, T>(rd: R) -> Result<T, Error>
+where R: Read,
+      T: DeserializeOwned
+{
+    from_read(rd)
+}
+
 /// Deserialize a temporary scope-bound instance of type `T` from a slice, with zero-copy if possible.
 ///
 /// Deserialization will be performed in zero-copy manner whenever it is possible, borrowing the
 /// data from the slice itself. For example, strings and byte-arrays won't copied.
 ///
+/// Binary values are passed to the visitor with `visit_borrowed_bytes`, so `&'a [u8]` fields
+/// annotated with `#[serde(with = "serde_bytes")]` and `&'a serde_bytes::Bytes` fields point
+/// directly into `input`.
+///
 /// # Errors
 ///
 /// This conversion can fail if the structure of the Value does not match the structure expected
@@ -1057,6 +1650,51 @@ where
     from_read_ref(input)
 }
 
+/// Deserialize an instance of type `T` from a slice, like [from_slice], but only if the slice
+/// contains exactly one value.
+///
+/// `from_slice` stops after the first value and ignores whatever follows it, which could hide
+/// corrupted or concatenated payloads. Use this function if the input must be consumed entirely,
+/// for example when checking its integrity.
+///
+/// # Errors
+///
+/// Returns `Error::TrailingData` with the length of the value and the length of the input if the
+/// value ends before the input does, in addition to the errors returned by [from_slice].
+///
+/// # Examples
+///
+/// ```
+/// use rmp_serde::decode::Error;
+///
+/// // Encoded `42`, followed by a stray nil.
+/// let buf = [0x2a, 0xc0];
+///
+/// assert_eq!(42, rmp_serde::from_slice::<u8>(&buf).unwrap());
+/// match rmp_serde::from_slice_exact::<u8>(&buf) {
+///     Err(Error::TrailingData { consumed: 1, total: 2 }) => {}
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+/// ```
+pub fn from_slice_exact<'a, T>(input: &'a [u8]) -> Result<T, Error>
+where
+    T: Deserialize<'a>
+{
+    let mut de = Deserializer::from_read_ref(input);
+    let val = Deserialize::deserialize(&mut de)?;
+
+    // A marker that was peeked but not consumed by the value is trailing data as well.
+    let remaining = de.rd.buf.len() + de.marker.map_or(0, |_| 1);
+    if remaining != 0 {
+        return Err(Error::TrailingData {
+            consumed: (input.len() - remaining) as u64,
+            total: input.len() as u64,
+        });
+    }
+
+    Ok(val)
+}
+
 #[inline]
 #[doc(hidden)]
 #[deprecated(note = "use from_slice")]
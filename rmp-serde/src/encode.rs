@@ -1,5 +1,6 @@
 //! Serialize a Rust data structure into MessagePack data.
 
+use std::convert::TryFrom;
 use std::error;
 use std::fmt::{self, Display};
 use std::io::Write;
@@ -15,9 +16,10 @@ use rmp::encode::ValueWriteError;
 use rmp::{encode, Marker};
 
 use crate::config::{
-    BinaryConfig, DefaultConfig, HumanReadableConfig, SerializerConfig, StructMapConfig,
-    StructTupleConfig
+    BinaryConfig, DefaultConfig, HumanReadableConfig, VariantIndexConfig, SerializerConfig,
+    SortedMapKeysConfig, StructMapConfig, StructTupleConfig, TimeLayout
 };
+use crate::timestamp;
 use crate::MSGPACK_EXT_STRUCT_NAME;
 
 /// This type represents all possible errors that can occur when serializing or
@@ -116,6 +118,7 @@ pub struct Serializer<W, C = DefaultConfig> {
     wr: W,
     config: C,
     depth: usize,
+    time_layout: Option<TimeLayout>,
 }
 
 impl<W: Write, C> Serializer<W, C> {
@@ -147,6 +150,35 @@ impl<W: Write, C> Serializer<W, C> {
     pub fn unstable_set_max_depth(&mut self, depth: usize) {
         self.depth = depth;
     }
+
+    /// Sets the layout of `std::time::Duration` and `std::time::SystemTime`, to match the
+    /// convention of the peer reading the output.
+    ///
+    /// Without a layout both are serialized as serde's structs of seconds and nanoseconds. Serde
+    /// identifies these types only by name, so any struct named `Duration` or `SystemTime` with two
+    /// fields is written in this layout, and fails with `Error::InvalidDataModel` unless its fields
+    /// are those of the std type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use rmp_serde::config::TimeLayout;
+    /// use rmp_serde::Serializer;
+    /// use serde::Serialize;
+    ///
+    /// let mut buf = Vec::new();
+    /// let mut se = Serializer::new(&mut buf);
+    /// se.set_time_layout(TimeLayout::SecondsNanosArray);
+    /// Duration::new(1, 500).serialize(&mut se).unwrap();
+    ///
+    /// assert_eq!(vec![0x92, 0x01, 0xcd, 0x01, 0xf4], buf);
+    /// ```
+    #[inline]
+    pub fn set_time_layout(&mut self, layout: TimeLayout) {
+        self.time_layout = Some(layout);
+    }
 }
 
 impl<W: Write> Serializer<W, DefaultConfig> {
@@ -163,6 +195,7 @@ impl<W: Write> Serializer<W, DefaultConfig> {
             wr,
             depth: 1024,
             config: DefaultConfig,
+            time_layout: None,
         }
     }
 }
@@ -170,7 +203,7 @@ impl<W: Write> Serializer<W, DefaultConfig> {
 impl<'a, W: Write + 'a, C> Serializer<W, C> {
     #[inline]
     fn compound(&'a mut self) -> Result<Compound<'a, W, C>, Error> {
-        let c = Compound { se: self };
+        let c = Compound { se: self, time: None };
         Ok(c)
     }
 }
@@ -188,9 +221,18 @@ impl<'a, W: Write + 'a, C: SerializerConfig> Serializer<W, C> {
                 }
                 None => Some(UnknownLengthCompound::from(&*self)),
             },
+            sorted: None,
             se: self,
         })
     }
+
+    /// Serializes the value into a new buffer using the same configuration.
+    #[inline]
+    fn to_buf<T: ?Sized + Serialize>(&self, value: &T) -> Result<Vec<u8>, Error> {
+        let mut se = Serializer { wr: Vec::new(), config: self.config, depth: self.depth, time_layout: self.time_layout };
+        value.serialize(&mut se)?;
+        Ok(se.wr)
+    }
 }
 
 impl<W: Write, C> Serializer<W, C> {
@@ -198,12 +240,21 @@ impl<W: Write, C> Serializer<W, C> {
     ///
     /// This is used, when the default struct serialization as a tuple does not fit your
     /// requirements.
+    ///
+    /// Field names are written as map keys, so the output is larger than the positional tuple
+    /// form, but it tolerates schema drift: fields may be reordered, and on decoding unknown
+    /// fields are skipped while missing ones can be filled with `#[serde(default)]`. It's also
+    /// what MessagePack libraries for many other languages expect by default.
+    ///
+    /// The `Deserializer` accepts both forms for any struct, so it doesn't need to know which
+    /// one the producer used.
     #[inline]
     pub fn with_struct_map(self) -> Serializer<W, StructMapConfig<C>> {
-        let Serializer { wr, depth, config } = self;
+        let Serializer { wr, depth, config, time_layout } = self;
         Serializer {
             wr,
             depth,
+            time_layout,
             config: StructMapConfig::new(config),
         }
     }
@@ -215,10 +266,11 @@ impl<W: Write, C> Serializer<W, C> {
     /// representation.
     #[inline]
     pub fn with_struct_tuple(self) -> Serializer<W, StructTupleConfig<C>> {
-        let Serializer { wr, depth, config } = self;
+        let Serializer { wr, depth, config, time_layout } = self;
         Serializer {
             wr,
             depth,
+            time_layout,
             config: StructTupleConfig::new(config),
         }
     }
@@ -232,10 +284,11 @@ impl<W: Write, C> Serializer<W, C> {
     /// versions of `rmp-serde`.
     #[inline]
     pub fn with_human_readable(self) -> Serializer<W, HumanReadableConfig<C>> {
-        let Serializer { wr, depth, config } = self;
+        let Serializer { wr, depth, config, time_layout } = self;
         Serializer {
             wr,
             depth,
+            time_layout,
             config: HumanReadableConfig::new(config),
         }
     }
@@ -247,13 +300,102 @@ impl<W: Write, C> Serializer<W, C> {
     /// representation.
     #[inline]
     pub fn with_binary(self) -> Serializer<W, BinaryConfig<C>> {
-        let Serializer { wr, depth, config } = self;
+        let Serializer { wr, depth, config, time_layout } = self;
         Serializer {
             wr,
             depth,
+            time_layout,
             config: BinaryConfig::new(config),
         }
     }
+
+    /// Consumes this serializer returning the new one, which will serialize
+    /// `std::time::SystemTime` as the MessagePack timestamp extension (type `-1`), counting
+    /// seconds and nanoseconds since the Unix epoch.
+    ///
+    /// This is useful to interoperate with other MessagePack implementations, which map the
+    /// timestamp extension to their native time types.
+    ///
+    /// This is a shorthand for [`set_time_layout`](Self::set_time_layout) with
+    /// `TimeLayout::TimestampExt`, so `std::time::Duration` is written as the timestamp extension
+    /// too.
+    #[inline]
+    pub fn with_timestamp_ext(mut self) -> Self {
+        self.set_time_layout(TimeLayout::TimestampExt);
+        self
+    }
+
+    /// Consumes this serializer returning the new one, which will serialize enum variants by
+    /// their index instead of their name.
+    ///
+    /// A unit-only enum is then written as a bare integer, which is how many compact binary
+    /// protocols represent enums such as opcodes. The `Deserializer` accepts both forms.
+    ///
+    /// Serde only knows the position of a variant, so an enum with explicit discriminants like
+    /// `A = 0x10` is still written as `0` for its first variant. To write the discriminants of a
+    /// `#[repr(u8)]` enum instead, derive its `Serialize` and `Deserialize` with the `serde_repr`
+    /// crate, which needs no config.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate serde_derive;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// enum Opcode {
+    ///     Ping,
+    ///     Pong,
+    /// }
+    ///
+    /// let mut buf = Vec::new();
+    /// Opcode::Pong.serialize(&mut rmp_serde::Serializer::new(&mut buf).with_variant_indices()).unwrap();
+    /// assert_eq!(vec![0x01], buf);
+    /// ```
+    #[inline]
+    pub fn with_variant_indices(self) -> Serializer<W, VariantIndexConfig<C>> {
+        let Serializer { wr, depth, config, time_layout } = self;
+        Serializer {
+            wr,
+            depth,
+            time_layout,
+            config: VariantIndexConfig::new(config),
+        }
+    }
+
+    /// Consumes this serializer returning the new one, which will write map entries sorted by
+    /// their encoded keys.
+    ///
+    /// This makes the output byte-identical for equal maps regardless of their iteration order,
+    /// for example of a `HashMap`, which is needed for content addressing or signatures.
+    ///
+    /// Note that the entries of every map are buffered and sorted before anything of the map is
+    /// written, instead of being streamed into the writer. See [`SortedMapKeysConfig`] for the
+    /// ordering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use serde::Serialize;
+    ///
+    /// let map: HashMap<&str, u8> = vec![("bb", 2), ("a", 1), ("c", 3)].into_iter().collect();
+    ///
+    /// let mut buf = Vec::new();
+    /// map.serialize(&mut rmp_serde::Serializer::new(&mut buf).with_sorted_map_keys()).unwrap();
+    /// assert_eq!(vec![0x83, 0xa1, 0x61, 0x01, 0xa1, 0x63, 0x03, 0xa2, 0x62, 0x62, 0x02], buf);
+    /// ```
+    #[inline]
+    pub fn with_sorted_map_keys(self) -> Serializer<W, SortedMapKeysConfig<C>> {
+        let Serializer { wr, depth, config, time_layout } = self;
+        Serializer {
+            wr,
+            depth,
+            time_layout,
+            config: SortedMapKeysConfig::new(config),
+        }
+    }
 }
 
 impl<W: Write, C> UnderlyingWrite for Serializer<W, C> {
@@ -279,6 +421,221 @@ impl<W: Write, C> UnderlyingWrite for Serializer<W, C> {
 #[derive(Debug)]
 pub struct Compound<'a, W: 'a, C: 'a> {
     se: &'a mut Serializer<W, C>,
+    /// Collected fields of a `Duration` or `SystemTime` that is going to be written in a
+    /// `TimeLayout`.
+    time: Option<TimeFields>,
+}
+
+#[derive(Debug)]
+struct TimeFields {
+    layout: TimeLayout,
+    secs: Option<u64>,
+    nanos: Option<u32>,
+}
+
+impl TimeFields {
+    fn new(layout: TimeLayout) -> Self {
+        TimeFields { layout, secs: None, nanos: None }
+    }
+
+    fn collect<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        match key {
+            "secs" | "secs_since_epoch" => self.secs = Some(value.serialize(TimeFieldSerializer)?),
+            "nanos" | "nanos_since_epoch" => {
+                let nanos = value.serialize(TimeFieldSerializer)?;
+                let nanos = u32::try_from(nanos)
+                    .map_err(|_| Error::InvalidDataModel("expected u32 nanoseconds, received larger integer"))?;
+                self.nanos = Some(nanos);
+            }
+            _ => return Err(Error::InvalidDataModel("unexpected Duration or SystemTime field")),
+        }
+
+        Ok(())
+    }
+
+    fn write<W: Write>(self, wr: &mut W) -> Result<(), Error> {
+        let (secs, nanos) = match (self.secs, self.nanos) {
+            (Some(secs), Some(nanos)) => (secs, nanos),
+            _ => return Err(Error::InvalidDataModel("expected time with integer seconds and nanoseconds")),
+        };
+
+        match self.layout {
+            TimeLayout::TimestampExt => {
+                if secs > i64::MAX as u64 {
+                    return Err(Error::InvalidDataModel("time out of range of the timestamp extension"));
+                }
+                timestamp::write_timestamp(wr, secs as i64, nanos)?;
+            }
+            TimeLayout::SecondsNanosArray => {
+                encode::write_array_len(wr, 2)?;
+                encode::write_uint(wr, secs)?;
+                encode::write_uint(wr, u64::from(nanos))?;
+            }
+            TimeLayout::TotalNanosInt => {
+                let total = timestamp::to_total_nanos(secs, nanos)
+                    .ok_or(Error::InvalidDataModel("time out of range of 64-bit nanoseconds"))?;
+                encode::write_uint(wr, total)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Captures the integer seconds or nanoseconds of a `Duration` or `SystemTime` field.
+struct TimeFieldSerializer;
+
+impl serde::Serializer for TimeFieldSerializer {
+    type Ok = u64;
+    type Error = Error;
+
+    type SerializeSeq = serde::ser::Impossible<u64, Error>;
+    type SerializeTuple = serde::ser::Impossible<u64, Error>;
+    type SerializeTupleStruct = serde::ser::Impossible<u64, Error>;
+    type SerializeTupleVariant = serde::ser::Impossible<u64, Error>;
+    type SerializeMap = serde::ser::Impossible<u64, Error>;
+    type SerializeStruct = serde::ser::Impossible<u64, Error>;
+    type SerializeStructVariant = serde::ser::Impossible<u64, Error>;
+
+    #[inline]
+    fn serialize_u32(self, val: u32) -> Result<Self::Ok, Self::Error> {
+        Ok(u64::from(val))
+    }
+
+    #[inline]
+    fn serialize_u64(self, val: u64) -> Result<Self::Ok, Self::Error> {
+        Ok(val)
+    }
+
+    #[cold]
+    fn serialize_bool(self, _val: bool) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received bool"))
+    }
+
+    #[cold]
+    fn serialize_i8(self, _val: i8) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received i8"))
+    }
+
+    #[cold]
+    fn serialize_i16(self, _val: i16) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received i16"))
+    }
+
+    #[cold]
+    fn serialize_i32(self, _val: i32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received i32"))
+    }
+
+    #[cold]
+    fn serialize_i64(self, _val: i64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received i64"))
+    }
+
+    #[cold]
+    fn serialize_u8(self, _val: u8) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received u8"))
+    }
+
+    #[cold]
+    fn serialize_u16(self, _val: u16) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received u16"))
+    }
+
+    #[cold]
+    fn serialize_f32(self, _val: f32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received f32"))
+    }
+
+    #[cold]
+    fn serialize_f64(self, _val: f64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received f64"))
+    }
+
+    #[cold]
+    fn serialize_char(self, _val: char) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received char"))
+    }
+
+    #[cold]
+    fn serialize_str(self, _val: &str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received str"))
+    }
+
+    #[cold]
+    fn serialize_bytes(self, _val: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received bytes"))
+    }
+
+    #[cold]
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received unit"))
+    }
+
+    #[cold]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received unit struct"))
+    }
+
+    #[cold]
+    fn serialize_unit_variant(self, _name: &'static str, _idx: u32, _variant: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received unit variant"))
+    }
+
+    #[cold]
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _name: &'static str, _value: &T) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received newtype struct"))
+    }
+
+    #[cold]
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(self, _name: &'static str, _idx: u32, _variant: &'static str, _value: &T) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received newtype variant"))
+    }
+
+    #[cold]
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received none"))
+    }
+
+    #[cold]
+    fn serialize_some<T: ?Sized + Serialize>(self, _value: &T) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received some"))
+    }
+
+    #[cold]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received seq"))
+    }
+
+    #[cold]
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received tuple"))
+    }
+
+    #[cold]
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received tuple struct"))
+    }
+
+    #[cold]
+    fn serialize_tuple_variant(self, _name: &'static str, _idx: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received tuple variant"))
+    }
+
+    #[cold]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received map"))
+    }
+
+    #[cold]
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received struct"))
+    }
+
+    #[cold]
+    fn serialize_struct_variant(self, _name: &'static str, _idx: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Error::InvalidDataModel("expected integer time field, received struct variant"))
+    }
 }
 
 #[derive(Debug)]
@@ -349,12 +706,18 @@ impl<'a, W: Write + 'a, C: SerializerConfig> SerializeStruct for Compound<'a, W,
     fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) ->
         Result<(), Self::Error>
     {
-        C::write_struct_field(&mut *self.se, key, value)
+        match self.time {
+            Some(ref mut fields) => fields.collect(key, value),
+            None => C::write_struct_field(&mut *self.se, key, value),
+        }
     }
 
-    #[inline(always)]
+    #[inline]
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(())
+        match self.time {
+            Some(fields) => fields.write(&mut self.se.wr),
+            None => Ok(()),
+        }
     }
 }
 
@@ -399,7 +762,12 @@ struct UnknownLengthCompound<C> {
 impl<W, C: SerializerConfig> From<&Serializer<W, C>> for UnknownLengthCompound<C> {
     fn from(se: &Serializer<W, C>) -> Self {
         Self {
-            se: Serializer { wr: Vec::with_capacity(128), config: se.config, depth: se.depth },
+            se: Serializer {
+                wr: Vec::with_capacity(128),
+                config: se.config,
+                depth: se.depth,
+                time_layout: se.time_layout,
+            },
             elem_count: 0
         }
     }
@@ -420,10 +788,14 @@ impl<W, C: SerializerConfig> From<&Serializer<W, C>> for UnknownLengthCompound<C
 /// representaion is desired.
 ///
 /// Otherwise, if the length is known, the elements will be encoded directly by the `Serializer`.
+///
+/// With `Serializer::with_sorted_map_keys` map entries are instead collected as encoded key-value
+/// pairs, which are sorted and written on `end()`.
 #[derive(Debug)]
 pub struct MaybeUnknownLengthCompound<'a, W: 'a, C: 'a> {
     se: &'a mut Serializer<W, C>,
     compound: Option<UnknownLengthCompound<C>>,
+    sorted: Option<Vec<(Vec<u8>, Vec<u8>)>>,
 }
 
 impl<'a, W: Write + 'a, C: SerializerConfig> SerializeSeq for MaybeUnknownLengthCompound<'a, W, C> {
@@ -456,14 +828,36 @@ impl<'a, W: Write + 'a, C: SerializerConfig> SerializeMap for MaybeUnknownLength
     type Error = Error;
 
     fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        if let Some(ref mut entries) = self.sorted {
+            entries.push((self.se.to_buf(key)?, Vec::new()));
+            return Ok(());
+        }
         <Self as SerializeSeq>::serialize_element(self, key)
     }
 
     fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        if let Some(ref mut entries) = self.sorted {
+            return match entries.last_mut() {
+                Some(entry) => {
+                    entry.1 = self.se.to_buf(value)?;
+                    Ok(())
+                }
+                None => Err(Error::InvalidDataModel("map value serialized before its key")),
+            };
+        }
         <Self as SerializeSeq>::serialize_element(self, value)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
+        if let Some(mut entries) = self.sorted {
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            encode::write_map_len(&mut self.se.wr, entries.len() as u32)?;
+            for (key, value) in entries {
+                self.se.wr.write_all(&key).map_err(ValueWriteError::InvalidDataWrite)?;
+                self.se.wr.write_all(&value).map_err(ValueWriteError::InvalidDataWrite)?;
+            }
+            return Ok(());
+        }
         if let Some(compound) = self.compound {
             encode::write_map_len(&mut self.se.wr, compound.elem_count / 2)?;
             self.se.wr.write_all(&compound.se.into_inner())
@@ -640,23 +1034,33 @@ where
     }
 
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        if C::is_sorted_map_keys() {
+            return Ok(MaybeUnknownLengthCompound { se: self, compound: None, sorted: Some(Vec::new()) });
+        }
         self.maybe_unknown_len_compound(len, |wr, len| encode::write_map_len(wr, len))
     }
 
-    fn serialize_struct(self, _name: &'static str, len: usize) ->
+    fn serialize_struct(self, name: &'static str, len: usize) ->
         Result<Self::SerializeStruct, Self::Error>
     {
+        if let Some(layout) = self.time_layout {
+            if timestamp::time_fields(name).map(<[_]>::len) == Some(len) {
+                return Ok(Compound { se: self, time: Some(TimeFields::new(layout)) });
+            }
+        }
+
         C::write_struct_len(self, len)?;
         self.compound()
     }
 
-    fn serialize_struct_variant(self, name: &'static str, id: u32, variant: &'static str, len: usize) ->
+    fn serialize_struct_variant(self, _name: &'static str, id: u32, variant: &'static str, len: usize) ->
         Result<Self::SerializeStructVariant, Error>
     {
         // encode as a map from variant idx to a sequence of its attributed data, like: {idx => [v1,...,vN]}
         encode::write_map_len(&mut self.wr, 1)?;
         C::write_variant_ident(self, id, variant)?;
-        self.serialize_struct(name, len)
+        C::write_struct_len(self, len)?;
+        self.compound()
     }
 }
 
@@ -1068,6 +1472,35 @@ where
     val.serialize(&mut se)
 }
 
+/// Serialize the given data structure as MessagePack into the writer.
+/// This function uses compact representation - structures as arrays
+///
+/// This is the counterpart of [from_reader](crate::from_reader), named like in other serde
+/// formats. Pass `&mut W` to keep using the writer afterwards.
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to fail.
+#[inline]
+pub fn to_writer<W, T>(mut wr: W, val: &T) -> Result<(), Error>
+where
+    W: Write,
+    T: Serialize + ?Sized
+{
+    write(&mut wr, val)
+}
+
+/// Serialize the given data structure as MessagePack into the writer.
+/// This function serializes structures as maps
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to fail.
+#[inline]
+pub fn to_writer_named<W, T>(mut wr: W, val: &T) -> Result<(), Error>
+where
+    W: Write,
+    T: Serialize + ?Sized
+{
+    write_named(&mut wr, val)
+}
+
 /// Serialize the given data structure as a MessagePack byte vector.
 /// This method uses compact representation, structs are serialized as arrays
 ///
@@ -68,16 +68,17 @@ use std::str::{self, Utf8Error};
 use serde::de;
 use serde::{Deserialize, Serialize};
 
-pub use crate::decode::{from_read, Deserializer};
+pub use crate::decode::{from_read, from_reader, Deserializer};
 #[allow(deprecated)]
 pub use crate::decode::from_read_ref;
-pub use crate::encode::{to_vec, to_vec_named, Serializer};
+pub use crate::encode::{to_vec, to_vec_named, to_writer, to_writer_named, Serializer};
 
-pub use crate::decode::from_slice;
+pub use crate::decode::{from_slice, from_slice_exact};
 
 pub mod config;
 pub mod decode;
 pub mod encode;
+mod timestamp;
 
 /// Name of Serde newtype struct to Represent Msgpack's Ext
 /// Msgpack Ext: Ext(tag, binary)
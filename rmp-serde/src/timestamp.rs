@@ -0,0 +1,101 @@
+//! MessagePack timestamp extension (type `-1`) support for `std::time::SystemTime`.
+//!
+//! Timestamps are stored as seconds and nanoseconds relative to the Unix epoch
+//! (1970-01-01T00:00:00 UTC), using one of the three layouts defined by the specification:
+//!
+//! - timestamp 32 (`fixext4`): unsigned 32-bit seconds, no nanoseconds.
+//! - timestamp 64 (`fixext8`): 30-bit nanoseconds and unsigned 34-bit seconds packed into 64 bits.
+//! - timestamp 96 (`ext8` with 12 bytes): 32-bit nanoseconds followed by signed 64-bit seconds.
+//!
+//! Also has the helpers for the other layouts of `std::time::Duration` and `SystemTime` selected
+//! by `TimeLayout`.
+
+use std::io::Write;
+
+use rmp::encode::{self, ValueWriteError};
+
+/// Extension type reserved for timestamps by the MessagePack specification.
+pub(crate) const TIMESTAMP_EXT_TYPE: i8 = -1;
+
+/// Name and fields that serde uses for `std::time::SystemTime`.
+pub(crate) const SYSTEM_TIME_NAME: &str = "SystemTime";
+pub(crate) const SYSTEM_TIME_FIELDS: &[&str] = &["secs_since_epoch", "nanos_since_epoch"];
+
+/// Name and fields that serde uses for `std::time::Duration`.
+pub(crate) const DURATION_NAME: &str = "Duration";
+pub(crate) const DURATION_FIELDS: &[&str] = &["secs", "nanos"];
+
+pub(crate) const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Returns the fields of the serde struct of `Duration` or `SystemTime` with the given name.
+pub(crate) fn time_fields(name: &str) -> Option<&'static [&'static str]> {
+    match name {
+        SYSTEM_TIME_NAME => Some(SYSTEM_TIME_FIELDS),
+        DURATION_NAME => Some(DURATION_FIELDS),
+        _ => None,
+    }
+}
+
+/// Converts seconds and nanoseconds into the total nanoseconds, if it fits into 64 bits.
+pub(crate) fn to_total_nanos(secs: u64, nanos: u32) -> Option<u64> {
+    secs.checked_mul(u64::from(NANOS_PER_SEC))?.checked_add(u64::from(nanos))
+}
+
+/// Splits the total nanoseconds into seconds and nanoseconds.
+pub(crate) fn from_total_nanos(total: u64) -> (u64, u32) {
+    let per_sec = u64::from(NANOS_PER_SEC);
+    (total / per_sec, (total % per_sec) as u32)
+}
+
+/// Writes the timestamp using the most compact layout able to represent it.
+pub(crate) fn write_timestamp<W: Write>(wr: &mut W, secs: i64, nanos: u32) -> Result<(), ValueWriteError> {
+    let res = if secs >> 34 == 0 {
+        let data = (u64::from(nanos) << 34) | secs as u64;
+        if data >> 32 == 0 {
+            encode::write_ext_meta(wr, 4, TIMESTAMP_EXT_TYPE)?;
+            wr.write_all(&(data as u32).to_be_bytes())
+        } else {
+            encode::write_ext_meta(wr, 8, TIMESTAMP_EXT_TYPE)?;
+            wr.write_all(&data.to_be_bytes())
+        }
+    } else {
+        encode::write_ext_meta(wr, 12, TIMESTAMP_EXT_TYPE)?;
+        wr.write_all(&nanos.to_be_bytes())
+            .and_then(|()| wr.write_all(&secs.to_be_bytes()))
+    };
+
+    res.map_err(ValueWriteError::InvalidDataWrite)
+}
+
+/// Parses the timestamp extension payload into seconds and nanoseconds since the Unix epoch.
+///
+/// Returns `None` if the payload length doesn't match any layout or nanoseconds are out of range.
+pub(crate) fn read_timestamp(data: &[u8]) -> Option<(i64, u32)> {
+    let (secs, nanos) = match data.len() {
+        4 => {
+            let mut buf = [0; 4];
+            buf.copy_from_slice(data);
+            (i64::from(u32::from_be_bytes(buf)), 0)
+        }
+        8 => {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(data);
+            let data = u64::from_be_bytes(buf);
+            ((data & 0x0000_0003_ffff_ffff) as i64, (data >> 34) as u32)
+        }
+        12 => {
+            let mut nanos = [0; 4];
+            let mut secs = [0; 8];
+            nanos.copy_from_slice(&data[..4]);
+            secs.copy_from_slice(&data[4..]);
+            (i64::from_be_bytes(secs), u32::from_be_bytes(nanos))
+        }
+        _ => return None,
+    };
+
+    if nanos < NANOS_PER_SEC {
+        Some((secs, nanos))
+    } else {
+        None
+    }
+}
@@ -2,10 +2,12 @@ extern crate rmp_serde as rmps;
 
 use std::fmt::{self, Formatter};
 use std::io::Cursor;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use serde::de;
 use serde::Deserialize;
 
+use crate::rmps::config::TimeLayout;
 use crate::rmps::decode::{self, Error};
 use crate::rmps::{Deserializer, Raw, RawRef};
 use rmp::Marker;
@@ -569,3 +571,367 @@ fn fail_depth_limit() {
         other => panic!("unexpected result: {:?}", other),
     }
 }
+
+#[test]
+fn pass_system_time_from_struct() {
+    let buf = [0x92, 0x01, 0x02];
+    let mut de = Deserializer::new(&buf[..]);
+
+    assert_eq!(UNIX_EPOCH + Duration::new(1, 2), SystemTime::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_system_time_from_timestamp32() {
+    let buf = [0xd6, 0xff, 0x00, 0x00, 0x00, 0x01];
+    let mut de = Deserializer::new(&buf[..]);
+
+    assert_eq!(UNIX_EPOCH + Duration::from_secs(1), SystemTime::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_system_time_from_timestamp64() {
+    let buf = [0xd7, 0xff, 0x00, 0x00, 0x07, 0xd0, 0x00, 0x00, 0x00, 0x01];
+    let mut de = Deserializer::new(&buf[..]);
+
+    assert_eq!(UNIX_EPOCH + Duration::new(1, 500), SystemTime::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_system_time_from_timestamp96() {
+    let buf = [
+        0xc7, 0x0c, 0xff,
+        0x00, 0x00, 0x00, 0x07,
+        0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00,
+    ];
+    let mut de = Deserializer::new(&buf[..]);
+
+    assert_eq!(UNIX_EPOCH + Duration::new(1 << 34, 7), SystemTime::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn fail_system_time_from_timestamp96_before_epoch() {
+    let buf = [
+        0xc7, 0x0c, 0xff,
+        0x00, 0x00, 0x00, 0x00,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    ];
+    let mut de = Deserializer::new(&buf[..]);
+
+    match SystemTime::deserialize(&mut de) {
+        Err(Error::Syntax(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_system_time_from_timestamp64_nanos_out_of_range() {
+    // 0x3fffffff nanoseconds is larger than a second.
+    let buf = [0xd7, 0xff, 0xff, 0xff, 0xff, 0xfc, 0x00, 0x00, 0x00, 0x00];
+    let mut de = Deserializer::new(&buf[..]);
+
+    match SystemTime::deserialize(&mut de) {
+        Err(Error::Syntax(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_system_time_from_other_ext_type() {
+    let buf = [0xd6, 0x01, 0x00, 0x00, 0x00, 0x01];
+    let mut de = Deserializer::new(&buf[..]);
+
+    match SystemTime::deserialize(&mut de) {
+        Err(Error::TypeMismatch(Marker::FixExt4)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn pass_duration_from_each_time_layout() {
+    let cases: &[(TimeLayout, &[u8])] = &[
+        (TimeLayout::TimestampExt, &[0xd7, 0xff, 0x00, 0x00, 0x07, 0xd0, 0x00, 0x00, 0x00, 0x01]),
+        (TimeLayout::SecondsNanosArray, &[0x92, 0x01, 0xcd, 0x01, 0xf4]),
+        (TimeLayout::TotalNanosInt, &[0xce, 0x3b, 0x9a, 0xcb, 0xf4]),
+    ];
+    for &(layout, buf) in cases {
+        let mut de = Deserializer::new(buf);
+        de.set_time_layout(layout);
+
+        assert_eq!(Duration::new(1, 500), Duration::deserialize(&mut de).unwrap());
+        assert!(de.get_ref().is_empty());
+    }
+}
+
+#[test]
+fn pass_system_time_from_total_nanos() {
+    let buf = [0xce, 0x3b, 0x9a, 0xcb, 0xf4];
+    let mut de = Deserializer::new(&buf[..]);
+    de.set_time_layout(TimeLayout::TotalNanosInt);
+
+    assert_eq!(UNIX_EPOCH + Duration::new(1, 500), SystemTime::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn fail_duration_from_other_time_layouts() {
+    let cases: &[(TimeLayout, &[u8], Marker)] = &[
+        // A timestamp, when reading an array.
+        (TimeLayout::SecondsNanosArray, &[0xd6, 0xff, 0x00, 0x00, 0x00, 0x01], Marker::FixExt4),
+        // An array, when reading a timestamp.
+        (TimeLayout::TimestampExt, &[0x92, 0x01, 0x02], Marker::FixArray(2)),
+        // Serde's struct as a map, when reading total nanoseconds.
+        (TimeLayout::TotalNanosInt, &[0x82, 0xa4, 0x73, 0x65, 0x63, 0x73, 0x01, 0xa5, 0x6e, 0x61, 0x6e, 0x6f, 0x73, 0x02], Marker::FixMap(2)),
+        // A negative integer, when reading total nanoseconds.
+        (TimeLayout::TotalNanosInt, &[0xff], Marker::FixNeg(-1)),
+    ];
+    for &(layout, buf, marker) in cases {
+        let mut de = Deserializer::new(buf);
+        de.set_time_layout(layout);
+
+        match Duration::deserialize(&mut de) {
+            Err(Error::TypeMismatch(actual)) => assert_eq!(marker, actual),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn fail_duration_from_seconds_nanos_array_of_other_length() {
+    let buf = [0x93, 0x01, 0x02, 0x03];
+    let mut de = Deserializer::new(&buf[..]);
+    de.set_time_layout(TimeLayout::SecondsNanosArray);
+
+    match Duration::deserialize(&mut de) {
+        Err(Error::LengthMismatch(3)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_duration_from_seconds_nanos_array_nanos_out_of_range() {
+    // [1, 1000000000]
+    let buf = [0x92, 0x01, 0xce, 0x3b, 0x9a, 0xca, 0x00];
+    let mut de = Deserializer::new(&buf[..]);
+    de.set_time_layout(TimeLayout::SecondsNanosArray);
+
+    match Duration::deserialize(&mut de) {
+        Err(Error::Syntax(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn pass_stream_of_values() {
+    // Encoded `[1, 2]`, `[3, 4]`, `[5, 6]`.
+    let buf = [0x92, 0x01, 0x02, 0x92, 0x03, 0x04, 0x92, 0x05, 0x06];
+    let de = Deserializer::new(Cursor::new(&buf[..]));
+
+    let values: Vec<(u8, u8)> = de.into_iter().collect::<Result<_, _>>().unwrap();
+    assert_eq!(vec![(1, 2), (3, 4), (5, 6)], values);
+}
+
+#[test]
+fn pass_stream_of_borrowed_values() {
+    // Encoded `"a"`, `"bc"`.
+    let buf = [0xa1, 0x61, 0xa2, 0x62, 0x63];
+    let de = Deserializer::from_read_ref(&buf[..]);
+
+    let values: Vec<&str> = de.into_iter().collect::<Result<_, _>>().unwrap();
+    assert_eq!(vec!["a", "bc"], values);
+}
+
+#[test]
+fn pass_empty_stream() {
+    let buf: [u8; 0] = [];
+    let mut iter = Deserializer::new(&buf[..]).into_iter::<u8>();
+
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn fail_stream_truncated_value() {
+    // Encoded `[1, 2]`, followed by an array with only one of the two elements.
+    let buf = [0x92, 0x01, 0x02, 0x92, 0x03];
+    let mut iter = Deserializer::new(&buf[..]).into_iter::<(u8, u8)>();
+
+    assert_eq!((1, 2), iter.next().unwrap().unwrap());
+    match iter.next() {
+        Some(Err(Error::InvalidMarkerRead(err))) => assert_eq!(std::io::ErrorKind::UnexpectedEof, err.kind()),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn pass_ignored_any_skips_whole_value() {
+    let buf = [
+        // {"a": [1.5f64, bin8 [1, 2]], -1: ext(5, [0, 0, 0, 0]), "s": "str"}
+        0x83,
+        0xa1, 0x61, 0x92, 0xcb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc4, 0x02, 0x01, 0x02,
+        0xff, 0xd6, 0x05, 0x00, 0x00, 0x00, 0x00,
+        0xa1, 0x73, 0xa3, 0x73, 0x74, 0x72,
+        // Next value.
+        0x2a,
+    ];
+    let mut de = Deserializer::new(Cursor::new(&buf[..]));
+
+    de::IgnoredAny::deserialize(&mut de).unwrap();
+    assert_eq!(42, u8::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_ignored_any_skips_invalid_utf8() {
+    let buf = [0xa2, 0xc3, 0x28, 0x2a];
+    let mut de = Deserializer::new(&buf[..]);
+
+    de::IgnoredAny::deserialize(&mut de).unwrap();
+    assert_eq!(42, u8::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn fail_ignored_any_truncated() {
+    let buf = [0xa3, 0x73, 0x74];
+    let mut de = Deserializer::new(&buf[..]);
+
+    match de::IgnoredAny::deserialize(&mut de) {
+        Err(Error::InvalidDataRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_ignored_any_depth_limit() {
+    let buf = [0x91, 0x91, 0x91, 0xc0];
+    let mut de = Deserializer::new(&buf[..]);
+    de.set_max_depth(2);
+
+    match de::IgnoredAny::deserialize(&mut de) {
+        Err(Error::DepthLimitExceeded) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn pass_duplicate_map_keys_last_wins_by_default() {
+    use std::collections::BTreeMap;
+
+    // {"a": 1, "a": 2}
+    let buf = [0x82, 0xa1, 0x61, 0x01, 0xa1, 0x61, 0x02];
+    let mut de = Deserializer::new(&buf[..]);
+
+    let map: BTreeMap<String, u8> = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(Some(&2), map.get("a"));
+    assert_eq!(1, map.len());
+}
+
+#[test]
+fn fail_duplicate_map_keys() {
+    use std::collections::{BTreeMap, HashMap};
+
+    // {"a": 1, "b": 2, "a": 3}
+    let buf = [0x83, 0xa1, 0x61, 0x01, 0xa1, 0x62, 0x02, 0xa1, 0x61, 0x03];
+
+    let mut de = Deserializer::new(&buf[..]);
+    de.set_reject_duplicate_keys(true);
+    match BTreeMap::<String, u8>::deserialize(&mut de) {
+        Err(Error::DuplicateKey) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let mut de = Deserializer::from_read_ref(&buf[..]);
+    de.set_reject_duplicate_keys(true);
+    match HashMap::<String, u8>::deserialize(&mut de) {
+        Err(Error::DuplicateKey) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_duplicate_map_keys_with_different_encodings() {
+    use std::collections::BTreeMap;
+
+    // {5: "x", 5: "y"}, where the second key is written as an u64.
+    let buf = [
+        0x82, 0x05, 0xa1, 0x78,
+        0xcf, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0xa1, 0x79,
+    ];
+    let mut de = Deserializer::new(&buf[..]);
+    de.set_reject_duplicate_keys(true);
+
+    match BTreeMap::<u64, String>::deserialize(&mut de) {
+        Err(Error::DuplicateKey) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn pass_distinct_map_keys_with_reject_duplicate_keys() {
+    use std::collections::BTreeMap;
+
+    // {"a": {"x": 1}, "b": {"x": 2}}, nested maps are checked separately.
+    let buf = [0x82, 0xa1, 0x61, 0x81, 0xa1, 0x78, 0x01, 0xa1, 0x62, 0x81, 0xa1, 0x78, 0x02];
+    let mut de = Deserializer::from_read_ref(&buf[..]);
+    de.set_reject_duplicate_keys(true);
+
+    let map: BTreeMap<String, BTreeMap<String, u8>> = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(Some(&2), map["b"].get("x"));
+    assert_eq!(2, map.len());
+}
+
+#[test]
+fn pass_borrowed_map_keys_with_reject_duplicate_keys() {
+    use std::collections::BTreeMap;
+
+    // {"a": 1, "b": 2}
+    let buf = [0x82, 0xa1, 0x61, 0x01, 0xa1, 0x62, 0x02];
+    let mut de = Deserializer::from_read_ref(&buf[..]);
+    de.set_reject_duplicate_keys(true);
+
+    let map: BTreeMap<&str, u8> = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(vec![("a", 1), ("b", 2)], map.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn fail_duplicate_borrowed_map_keys() {
+    use std::collections::BTreeMap;
+
+    // {"a": 1, "a": 2}
+    let buf = [0x82, 0xa1, 0x61, 0x01, 0xa1, 0x61, 0x02];
+    let mut de = Deserializer::from_read_ref(&buf[..]);
+    de.set_reject_duplicate_keys(true);
+
+    match BTreeMap::<&str, u8>::deserialize(&mut de) {
+        Err(Error::DuplicateKey) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn pass_from_slice_exact() {
+    // ["le", 42]
+    let buf = [0x92, 0xa2, 0x6c, 0x65, 0x2a];
+
+    assert_eq!(("le", 42u8), rmps::from_slice_exact(&buf).unwrap());
+}
+
+#[test]
+fn fail_from_slice_exact_trailing_data() {
+    // ["le", 42] followed by three garbage bytes.
+    let buf = [0x92, 0xa2, 0x6c, 0x65, 0x2a, 0xde, 0xad, 0xbe];
+
+    assert_eq!(("le", 42u8), rmps::from_slice(&buf).unwrap());
+    match rmps::from_slice_exact::<(&str, u8)>(&buf) {
+        Err(Error::TrailingData { consumed: 5, total: 8 }) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_from_slice_exact_truncated() {
+    let buf = [0x92, 0xa2, 0x6c];
+
+    match rmps::from_slice_exact::<(&str, u8)>(&buf) {
+        Err(Error::InvalidDataRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
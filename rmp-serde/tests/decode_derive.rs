@@ -121,6 +121,42 @@ fn pass_unit_variant() {
     assert_eq!(6, de.get_ref().position());
 }
 
+#[test]
+fn pass_unit_variant_from_index() {
+    let buf = [0x00, 0xcc, 0x01];
+    let cur = Cursor::new(&buf[..]);
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[repr(u8)]
+    enum Opcode {
+        Ping,
+        Pong,
+    }
+
+    let mut de = Deserializer::new(cur);
+    assert_eq!(Opcode::Ping, Opcode::deserialize(&mut de).unwrap());
+    assert_eq!(Opcode::Pong, Opcode::deserialize(&mut de).unwrap());
+    assert_eq!(3, de.get_ref().position());
+}
+
+#[test]
+fn fail_unknown_variant_index() {
+    let buf = [0x02];
+    let cur = Cursor::new(&buf[..]);
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Enum {
+        A,
+        B,
+    }
+
+    let mut de = Deserializer::new(cur);
+    match Enum::deserialize(&mut de) {
+        Err(Error::UnknownVariant(2)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
 #[test]
 fn pass_tuple_enum_with_arg() {
     // The encoded byte-array is: {1 => 42}.
@@ -192,7 +228,7 @@ fn fail_enum_overflow() {
     let actual: Result<Enum, Error> = Deserialize::deserialize(&mut de);
 
     match actual.err().unwrap() {
-        Error::Syntax(..) => (),
+        Error::UnknownVariant(1) => (),
         other => panic!("unexpected result: {:?}", other),
     }
 }
@@ -535,3 +571,73 @@ fn pass_from_ref() {
 
     assert_eq!(Dog { name: "Bobby", age: 8 }, rmps::from_read_ref(&buf).unwrap());
 }
+
+#[test]
+fn pass_from_bytes_borrows_from_input() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Image<'a> {
+        name: &'a str,
+        #[serde(borrow)]
+        pixels: &'a serde_bytes::Bytes,
+    }
+
+    // Encoded `7`, `["cat", bin [1, 2, 3]]`.
+    let buf = [0x07, 0x92, 0xa3, 0x63, 0x61, 0x74, 0xc4, 0x03, 0x01, 0x02, 0x03];
+    let mut rd = rmp::decode::Bytes::new(&buf);
+    assert_eq!(7, rmp::decode::read_pfix(&mut rd).unwrap());
+
+    let mut de = Deserializer::from_bytes(rd);
+    let img = Image::deserialize(&mut de).unwrap();
+    assert_eq!("cat", img.name);
+    assert_eq!(&[1, 2, 3], &img.pixels[..]);
+
+    // Both fields point at their data in `buf`, nothing was copied.
+    assert_eq!(buf[3..].as_ptr(), img.name.as_ptr());
+    assert_eq!(buf[8..].as_ptr(), img.pixels.as_ptr());
+}
+
+#[test]
+fn pass_struct_map_with_unknown_fields() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Struct {
+        a: u8,
+        c: u8,
+    }
+
+    // {"a": 1, "b": [{"x": nil}, "skipped"], "c": 3}
+    let buf = [
+        0x83,
+        0xa1, 0x61, 0x01,
+        0xa1, 0x62, 0x92, 0x81, 0xa1, 0x78, 0xc0, 0xa7, 0x73, 0x6b, 0x69, 0x70, 0x70, 0x65, 0x64,
+        0xa1, 0x63, 0x03,
+    ];
+    let mut de = Deserializer::new(&buf[..]);
+
+    assert_eq!(Struct { a: 1, c: 3 }, Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn fail_duplicate_struct_fields() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Struct {
+        a: u8,
+        #[serde(default)]
+        b: u8,
+    }
+
+    // {"a": 1, "b": 2}
+    let buf = [0x82, 0xa1, 0x61, 0x01, 0xa1, 0x62, 0x02];
+    let mut de = Deserializer::from_read_ref(&buf[..]);
+    de.set_reject_duplicate_keys(true);
+    assert_eq!(Struct { a: 1, b: 2 }, Struct::deserialize(&mut de).unwrap());
+
+    // {"a": 1, "a": 2}, with the latter key written as str8.
+    let buf = [0x82, 0xa1, 0x61, 0x01, 0xd9, 0x01, 0x61, 0x02];
+    let mut de = Deserializer::from_read_ref(&buf[..]);
+    de.set_reject_duplicate_keys(true);
+
+    match Struct::deserialize(&mut de) {
+        Err(Error::DuplicateKey) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
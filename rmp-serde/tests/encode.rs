@@ -1,9 +1,11 @@
 extern crate rmp_serde as rmps;
 
 use std::io::Cursor;
+use std::time::{Duration, UNIX_EPOCH};
 
 use serde::Serialize;
 
+use crate::rmps::config::TimeLayout;
 use crate::rmps::encode::{self, Error};
 use crate::rmps::{Raw, RawRef, Serializer};
 
@@ -276,6 +278,65 @@ fn pass_empty_map() {
     assert_eq!(out, buf);
 }
 
+#[test]
+fn pass_map_with_sorted_keys() {
+    use std::collections::HashMap;
+
+    let mut val = HashMap::new();
+    val.insert(300u16, "c");
+    val.insert(1u16, "a");
+    val.insert(2u16, "b");
+
+    let mut buf = vec![];
+    val.serialize(&mut Serializer::new(&mut buf).with_sorted_map_keys()).unwrap();
+
+    let out = vec![
+        0x83, // (size: 3)
+        0x01, 0xa1, 0x61, // 1 => "a"
+        0x02, 0xa1, 0x62, // 2 => "b"
+        0xcd, 0x01, 0x2c, 0xa1, 0x63, // 300 => "c"
+    ];
+    assert_eq!(out, buf);
+}
+
+#[test]
+fn pass_map_with_sorted_keys_is_deterministic() {
+    use std::collections::HashMap;
+
+    let mut expected = None;
+    for n in 0..8 {
+        // Every map has its own hasher state, and so its own iteration order.
+        let val: HashMap<String, u32> = (0..32).map(|i| (format!("key{}", (i + n) % 32), (i + n) % 32)).collect();
+
+        let mut buf = vec![];
+        val.serialize(&mut Serializer::new(&mut buf).with_sorted_map_keys()).unwrap();
+        match expected {
+            None => expected = Some(buf),
+            Some(ref expected) => assert_eq!(*expected, buf),
+        }
+    }
+}
+
+#[test]
+fn pass_nested_map_with_sorted_keys() {
+    use std::collections::BTreeMap;
+
+    let mut inner = BTreeMap::new();
+    inner.insert("bb", 1);
+    inner.insert("c", 2);
+    let mut val = BTreeMap::new();
+    val.insert("k", inner);
+
+    let mut buf = vec![];
+    val.serialize(&mut Serializer::new(&mut buf).with_sorted_map_keys()).unwrap();
+
+    let out = vec![
+        0x81, 0xa1, 0x6b, // {"k" =>
+        0x82, 0xa1, 0x63, 0x02, 0xa2, 0x62, 0x62, 0x01, // {"c" => 2, "bb" => 1}}
+    ];
+    assert_eq!(out, buf);
+}
+
 #[test]
 fn pass_encoding_struct_into_vec() {
     let val = (42u8, "the Answer");
@@ -368,3 +429,126 @@ fn pass_raw_ref_invalid_utf8() {
 fn serializer_one_type_arg() {
     let _s: rmp_serde::Serializer<&mut dyn std::io::Write>;
 }
+
+#[test]
+fn pass_system_time_default() {
+    let mut buf = Vec::new();
+    (UNIX_EPOCH + Duration::new(1, 2)).serialize(&mut Serializer::new(&mut buf)).unwrap();
+
+    // Encoded as serde's struct `[secs_since_epoch, nanos_since_epoch]`.
+    assert_eq!(vec![0x92, 0x01, 0x02], buf);
+}
+
+#[test]
+fn pass_system_time_as_timestamp32() {
+    let mut buf = Vec::new();
+    let mut se = Serializer::new(&mut buf).with_timestamp_ext();
+    (UNIX_EPOCH + Duration::from_secs(1)).serialize(&mut se).unwrap();
+
+    assert_eq!(vec![0xd6, 0xff, 0x00, 0x00, 0x00, 0x01], buf);
+}
+
+#[test]
+fn pass_system_time_as_timestamp64() {
+    let mut buf = Vec::new();
+    let mut se = Serializer::new(&mut buf).with_timestamp_ext();
+    (UNIX_EPOCH + Duration::new(1, 500)).serialize(&mut se).unwrap();
+
+    assert_eq!(vec![0xd7, 0xff, 0x00, 0x00, 0x07, 0xd0, 0x00, 0x00, 0x00, 0x01], buf);
+}
+
+#[test]
+fn pass_system_time_as_timestamp96() {
+    let mut buf = Vec::new();
+    let mut se = Serializer::new(&mut buf).with_timestamp_ext();
+    (UNIX_EPOCH + Duration::new(1 << 34, 7)).serialize(&mut se).unwrap();
+
+    assert_eq!(vec![
+        0xc7, 0x0c, 0xff,
+        0x00, 0x00, 0x00, 0x07,
+        0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00,
+    ], buf);
+}
+
+#[test]
+fn pass_duration_as_seconds_nanos_array() {
+    let mut buf = Vec::new();
+    let mut se = Serializer::new(&mut buf);
+    se.set_time_layout(TimeLayout::SecondsNanosArray);
+    Duration::new(300, 7).serialize(&mut se).unwrap();
+
+    assert_eq!(vec![0x92, 0xcd, 0x01, 0x2c, 0x07], buf);
+}
+
+#[test]
+fn pass_duration_as_total_nanos() {
+    let mut buf = Vec::new();
+    let mut se = Serializer::new(&mut buf);
+    se.set_time_layout(TimeLayout::TotalNanosInt);
+    Duration::new(1, 500).serialize(&mut se).unwrap();
+
+    assert_eq!(vec![0xce, 0x3b, 0x9a, 0xcb, 0xf4], buf);
+}
+
+#[test]
+fn pass_duration_as_timestamp() {
+    let mut buf = Vec::new();
+    let mut se = Serializer::new(&mut buf);
+    se.set_time_layout(TimeLayout::TimestampExt);
+    Duration::from_secs(1).serialize(&mut se).unwrap();
+
+    assert_eq!(vec![0xd6, 0xff, 0x00, 0x00, 0x00, 0x01], buf);
+}
+
+#[test]
+fn pass_system_time_layout_overrides_timestamp_ext() {
+    let mut buf = Vec::new();
+    let mut se = Serializer::new(&mut buf).with_timestamp_ext();
+    se.set_time_layout(TimeLayout::SecondsNanosArray);
+    (UNIX_EPOCH + Duration::new(1, 2)).serialize(&mut se).unwrap();
+
+    assert_eq!(vec![0x92, 0x01, 0x02], buf);
+}
+
+#[test]
+fn pass_duration_with_timestamp_ext() {
+    let mut buf = Vec::new();
+    Duration::new(1, 0).serialize(&mut Serializer::new(&mut buf).with_timestamp_ext()).unwrap();
+
+    assert_eq!(vec![0xd6, 0xff, 0x00, 0x00, 0x00, 0x01], buf);
+}
+
+#[test]
+fn fail_duration_as_total_nanos_out_of_range() {
+    let mut se = Serializer::new(Vec::new());
+    se.set_time_layout(TimeLayout::TotalNanosInt);
+
+    match Duration::from_secs(u64::MAX / 1_000_000_000 + 1).serialize(&mut se) {
+        Err(Error::InvalidDataModel(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_duration_with_non_integer_field() {
+    use serde::ser::SerializeStruct;
+
+    struct Duration;
+
+    impl Serialize for Duration {
+        fn serialize<S: serde::Serializer>(&self, se: S) -> Result<S::Ok, S::Error> {
+            let mut st = se.serialize_struct("Duration", 2)?;
+            st.serialize_field("secs", "1")?;
+            st.serialize_field("nanos", &2u32)?;
+            st.end()
+        }
+    }
+
+    let mut se = Serializer::new(Vec::new());
+    se.set_time_layout(TimeLayout::SecondsNanosArray);
+
+    match Duration.serialize(&mut se) {
+        Err(Error::InvalidDataModel("expected integer time field, received str")) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -3,6 +3,8 @@ extern crate serde_derive;
 
 extern crate rmp_serde as rmps;
 
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use crate::rmps::Serializer;
 use serde::Serialize;
 
@@ -34,6 +36,38 @@ fn pass_unit_variant() {
     assert_eq!(vec![0xa2, 0x56, 0x31, 0xa2, 0x56, 0x32], buf);
 }
 
+#[test]
+fn pass_unit_variant_as_index() {
+    #[derive(Serialize)]
+    enum Enum {
+        V1,
+        V2,
+    }
+
+    let mut buf = Vec::new();
+    Enum::V1.serialize(&mut Serializer::new(&mut buf).with_variant_indices()).unwrap();
+    Enum::V2.serialize(&mut Serializer::new(&mut buf).with_variant_indices()).unwrap();
+
+    // Expect: 0, 1
+    assert_eq!(vec![0x00, 0x01], buf);
+}
+
+#[test]
+fn pass_unit_variant_with_discriminant_as_index() {
+    #[derive(Serialize)]
+    #[allow(dead_code)]
+    enum Enum {
+        V1 = 0x10,
+        V2 = 0x20,
+    }
+
+    let mut buf = Vec::new();
+    Enum::V2.serialize(&mut Serializer::new(&mut buf).with_variant_indices()).unwrap();
+
+    // Expect: 1, the position of the variant rather than its discriminant.
+    assert_eq!(vec![0x01], buf);
+}
+
 #[test]
 fn pass_newtype_struct() {
     #[derive(Serialize)]
@@ -60,6 +94,22 @@ fn pass_newtype_variant() {
     assert_eq!(buf, vec![0x81, 0xa2, 0x56, 0x32, 42]);
 }
 
+#[test]
+fn pass_newtype_variant_as_index() {
+    #[derive(Serialize)]
+    enum Enum {
+        V1(u64),
+        V2(u64),
+    }
+
+    let mut buf = Vec::new();
+    Enum::V1(0).serialize(&mut Serializer::new(&mut buf).with_variant_indices()).unwrap();
+    Enum::V2(42).serialize(&mut Serializer::new(&mut buf).with_variant_indices()).unwrap();
+
+    // Expect: {0 => 0}, {1 => 42}
+    assert_eq!(buf, vec![0x81, 0x00, 0x00, 0x81, 0x01, 42]);
+}
+
 #[test]
 fn pass_untagged_newtype_variant() {
     #[derive(Serialize)]
@@ -360,3 +410,17 @@ fn pass_struct_as_map_using_triple_ext_many_times() {
     assert_eq!(vec![0x82, 0xa4, 0x6e, 0x61, 0x6d, 0x65, 0xa5, 0x42, 0x6f, 0x62, 0x62, 0x79, 0xa3, 0x61, 0x67, 0x65, 0x08],
                se.into_inner());
 }
+
+#[test]
+fn pass_system_time_as_timestamp_in_struct_map() {
+    #[derive(Serialize)]
+    struct Event {
+        at: SystemTime,
+    }
+
+    let mut buf = Vec::new();
+    let mut se = Serializer::new(&mut buf).with_struct_map().with_timestamp_ext();
+    Event { at: UNIX_EPOCH }.serialize(&mut se).unwrap();
+
+    assert_eq!(vec![0x81, 0xa2, 0x61, 0x74, 0xd6, 0xff, 0x00, 0x00, 0x00, 0x00], buf);
+}
@@ -194,6 +194,29 @@ fn round_struct_like_enum_with_struct_tuple() {
     assert_eq!(expected, Deserialize::deserialize(&mut de).unwrap());
 }
 
+#[test]
+fn round_enum_with_variant_indices() {
+    use serde::Serialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Enum {
+        A,
+        B(u32),
+        C { data: u32 },
+    }
+
+    for expected in [Enum::A, Enum::B(42), Enum::C { data: 42 }] {
+        let mut buf = Vec::new();
+        expected
+            .serialize(&mut Serializer::new(&mut buf).with_variant_indices().with_struct_map())
+            .unwrap();
+
+        let mut de = Deserializer::new(&buf[..]);
+
+        assert_eq!(expected, Deserialize::deserialize(&mut de).unwrap());
+    }
+}
+
 #[test]
 fn round_enum_with_newtype_struct() {
     use serde::Serialize;
@@ -285,6 +308,37 @@ fn round_struct_as_map() {
     assert_eq!(dog1, check);
 }
 
+#[test]
+fn round_struct_from_both_forms() {
+    use crate::rmps::decode::from_slice;
+    use crate::rmps::{to_vec, to_vec_named};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+    struct V1 {
+        name: String,
+        age: u16,
+    }
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+    struct V2 {
+        age: u16,
+        name: String,
+        #[serde(default)]
+        tags: Vec<String>,
+    }
+
+    let v1 = V1 { name: "Frankie".into(), age: 42 };
+    let expected = V2 { age: 42, name: "Frankie".into(), tags: vec![] };
+
+    // A struct-as-map producer may reorder, add and remove fields.
+    assert_eq!(expected, from_slice::<V2>(&to_vec_named(&v1).unwrap()).unwrap());
+    let v2 = V2 { age: 42, name: "Frankie".into(), tags: vec!["good".into()] };
+    assert_eq!(v1, from_slice::<V1>(&to_vec_named(&v2).unwrap()).unwrap());
+
+    // The positional form is still accepted for the same type.
+    assert_eq!(v1, from_slice::<V1>(&to_vec(&v1).unwrap()).unwrap());
+    assert!(to_vec(&v1).unwrap().len() < to_vec_named(&v1).unwrap().len());
+}
+
 #[test]
 fn round_struct_as_map_in_vec() {
     // See: issue #205
@@ -616,6 +670,48 @@ fn roundtrip_some_failures() {
     assert_roundtrips(Some(None::<()>));
 }
 
+#[test]
+fn round_system_time_as_timestamp() {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    for &(secs, nanos) in &[(0, 0), (1, 0), (u32::MAX as u64, 0), (1, 1), ((1 << 34) - 1, 999_999_999), (1 << 34, 0), (u64::MAX >> 1, 1)] {
+        let time = match UNIX_EPOCH.checked_add(Duration::new(secs, nanos)) {
+            Some(time) => time,
+            None => continue,
+        };
+
+        let mut buf = Vec::new();
+        time.serialize(&mut Serializer::new(&mut buf).with_timestamp_ext()).unwrap();
+
+        assert_eq!(time, rmp_serde::from_slice::<SystemTime>(&buf).unwrap());
+    }
+}
+
+#[test]
+fn round_time_layouts() {
+    use rmps::config::TimeLayout;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let layouts = [TimeLayout::TimestampExt, TimeLayout::SecondsNanosArray, TimeLayout::TotalNanosInt];
+    for &layout in &layouts {
+        for &(secs, nanos) in &[(0, 0), (1, 0), (1, 999_999_999), (u32::MAX as u64, 1)] {
+            let duration = Duration::new(secs, nanos);
+            let time = UNIX_EPOCH + duration;
+
+            let mut buf = Vec::new();
+            let mut se = Serializer::new(&mut buf);
+            se.set_time_layout(layout);
+            duration.serialize(&mut se).unwrap();
+            time.serialize(&mut se).unwrap();
+
+            let mut de = Deserializer::new(&buf[..]);
+            de.set_time_layout(layout);
+            assert_eq!(duration, Duration::deserialize(&mut de).unwrap());
+            assert_eq!(time, SystemTime::deserialize(&mut de).unwrap());
+        }
+    }
+}
+
 #[cfg(test)]
 #[track_caller]
 fn assert_roundtrips<T: PartialEq + std::fmt::Debug + Serialize + for<'a> Deserialize<'a>>(val: T) {
@@ -698,3 +794,50 @@ fn assert_roundtrips_config<T, CSF, SC, CDF, DC>(
 
     assert_eq!(val, &val2, "Config: {}", desc);
 }
+
+#[test]
+fn round_to_writer_from_reader() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Dog {
+        name: String,
+        age: u8,
+    }
+
+    let val = Dog { name: "Bobby".into(), age: 8 };
+
+    let mut buf = Vec::new();
+    rmps::to_writer(&mut buf, &val).unwrap();
+    assert_eq!(rmps::to_vec(&val).unwrap(), buf);
+    assert_eq!(val, rmps::from_reader(&buf[..]).unwrap());
+    assert_eq!(val, rmps::from_slice::<Dog>(&buf).unwrap());
+
+    let mut buf = Vec::new();
+    rmps::to_writer_named(&mut buf, &val).unwrap();
+    assert_eq!(rmps::to_vec_named(&val).unwrap(), buf);
+    assert_eq!(val, rmps::from_reader(Cursor::new(&buf)).unwrap());
+}
+
+#[test]
+fn round_borrowed_bytes_zero_copy() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Image<'a> {
+        name: &'a str,
+        #[serde(with = "serde_bytes")]
+        pixels: &'a [u8],
+        #[serde(borrow)]
+        thumb: &'a serde_bytes::Bytes,
+    }
+
+    let pixels = vec![0xaa; 70_000];
+    let thumb = vec![0x55; 300];
+    let val = Image { name: "cat", pixels: &pixels, thumb: serde_bytes::Bytes::new(&thumb) };
+    let buf = rmps::to_vec(&val).unwrap();
+
+    let out: Image<'_> = rmps::from_slice(&buf).unwrap();
+    assert_eq!(val, out);
+
+    // Bin data must be borrowed from the input, not copied into a new allocation.
+    let input = buf.as_ptr_range();
+    assert!(input.contains(&out.pixels.as_ptr()));
+    assert!(input.contains(&out.thumb.as_ptr()));
+}
@@ -42,3 +42,71 @@ fn from_string_read_str(b: &mut Bencher) {
         test::black_box(res);
     });
 }
+
+fn f64_array(len: u32) -> Vec<u8> {
+    let mut buf = Vec::new();
+    rmp::encode::write_array_len(&mut buf, len).unwrap();
+    for i in 0..len {
+        rmp::encode::write_f64(&mut buf, f64::from(i) * 0.5).unwrap();
+    }
+    buf
+}
+
+#[bench]
+fn from_f64_array_read_f64_loop(b: &mut Bencher) {
+    let buf = f64_array(1_000_000);
+    let mut out = vec![0.0; 1_000_000];
+
+    b.iter(|| {
+        let mut rd = &buf[..];
+        let len = read_array_len(&mut rd).unwrap();
+        for val in &mut out[..len as usize] {
+            *val = read_f64(&mut rd).unwrap();
+        }
+        test::black_box(&out);
+    });
+}
+
+#[bench]
+fn from_f64_array_read_f64_array_into(b: &mut Bencher) {
+    let buf = f64_array(1_000_000);
+    let mut out = vec![0.0; 1_000_000];
+
+    b.iter(|| {
+        read_f64_array_into(&mut &buf[..], &mut out).unwrap();
+        test::black_box(&out);
+    });
+}
+
+fn bin32(len: usize) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(len + 5);
+    rmp::encode::write_bin_len(&mut buf, len as u32).unwrap();
+    buf.resize(len + 5, 0x2a);
+    buf
+}
+
+#[bench]
+fn from_bin32_16mib_read_zeroed_vec(b: &mut Bencher) {
+    let buf = bin32(16 * 1024 * 1024);
+
+    b.iter(|| {
+        let mut rd = &buf[..];
+        let len = read_bin_len(&mut rd).unwrap();
+        let mut out = vec![0u8; len as usize];
+        rmp::decode::RmpRead::read_exact_buf(&mut rd, &mut out).unwrap();
+        test::black_box(out);
+    });
+    b.bytes = buf.len() as u64;
+}
+
+#[bench]
+fn from_bin32_16mib_read_bin_into_uninit(b: &mut Bencher) {
+    let buf = bin32(16 * 1024 * 1024);
+
+    b.iter(|| {
+        let mut out = Vec::new();
+        read_bin_into_uninit(&mut &buf[..], &mut out).unwrap();
+        test::black_box(out);
+    });
+    b.bytes = buf.len() as u64;
+}
@@ -0,0 +1,118 @@
+//! Async versions of the encoding functions, writing to a tokio [AsyncWrite].
+//!
+//! Each function writes the same bytes as its blocking counterpart in [encode](crate::encode),
+//! so the output can be mixed freely with values encoded into a buffer. The marker and its
+//! payload are written with a single `write_all` call, so there is no need to wrap the writer
+//! into a `BufWriter` just to avoid tiny writes for every value.
+//!
+//! # Examples
+//!
+//! ```
+//! use rmp::async_encode;
+//!
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let mut buf = Vec::new();
+//! async_encode::write_array_len(&mut buf, 2).await.unwrap();
+//! async_encode::write_uint(&mut buf, 300).await.unwrap();
+//! async_encode::write_str(&mut buf, "le message").await.unwrap();
+//!
+//! let mut expected = Vec::new();
+//! rmp::encode::write_array_len(&mut expected, 2).unwrap();
+//! rmp::encode::write_uint(&mut expected, 300).unwrap();
+//! rmp::encode::write_str(&mut expected, "le message").unwrap();
+//! assert_eq!(expected, buf);
+//! # });
+//! ```
+
+use std::convert::TryFrom;
+use std::io::{self, ErrorKind};
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::encode::header::Header;
+use crate::Marker;
+
+#[inline]
+async fn write_header<W: AsyncWrite + Unpin>(wr: &mut W, header: Header) -> io::Result<Marker> {
+    wr.write_all(header.as_bytes()).await?;
+    Ok(header.marker())
+}
+
+/// Returns the length of the data as `u32`, or an `InvalidInput` error if it doesn't fit.
+#[inline]
+fn data_len(len: usize) -> io::Result<u32> {
+    u32::try_from(len).map_err(|_| io::Error::new(ErrorKind::InvalidInput, "data is longer than u32::MAX bytes"))
+}
+
+/// Encodes and attempts to write a nil value, like [write_nil](crate::encode::write_nil).
+pub async fn write_nil<W: AsyncWrite + Unpin>(wr: &mut W) -> io::Result<()> {
+    wr.write_all(&[Marker::Null.to_u8()]).await
+}
+
+/// Encodes and attempts to write a bool value, like [write_bool](crate::encode::write_bool).
+pub async fn write_bool<W: AsyncWrite + Unpin>(wr: &mut W, val: bool) -> io::Result<()> {
+    let marker = if val { Marker::True } else { Marker::False };
+    wr.write_all(&[marker.to_u8()]).await
+}
+
+/// Encodes and attempts to write an `u64` value using the most efficient representation,
+/// returning the marker used, like [write_uint](crate::encode::write_uint).
+pub async fn write_uint<W: AsyncWrite + Unpin>(wr: &mut W, val: u64) -> io::Result<Marker> {
+    write_header(wr, Header::uint(val)).await
+}
+
+/// Encodes and attempts to write an `i64` value using the most efficient representation,
+/// returning the marker used, like [write_sint](crate::encode::write_sint).
+pub async fn write_sint<W: AsyncWrite + Unpin>(wr: &mut W, val: i64) -> io::Result<Marker> {
+    write_header(wr, Header::sint(val)).await
+}
+
+/// Encodes and attempts to write an `f64` value as a 9-byte sequence, like
+/// [write_f64](crate::encode::write_f64).
+pub async fn write_f64<W: AsyncWrite + Unpin>(wr: &mut W, val: f64) -> io::Result<()> {
+    let mut buf = [Marker::F64.to_u8(); 9];
+    buf[1..].copy_from_slice(&val.to_bits().to_be_bytes());
+    wr.write_all(&buf).await
+}
+
+/// Encodes and attempts to write a string, like [write_str](crate::encode::write_str).
+///
+/// # Errors
+///
+/// Returns an `InvalidInput` error without writing anything if the string is longer than
+/// `u32::MAX` bytes.
+pub async fn write_str<W: AsyncWrite + Unpin>(wr: &mut W, data: &str) -> io::Result<()> {
+    write_header(wr, Header::str_len(data_len(data.len())?)).await?;
+    wr.write_all(data.as_bytes()).await
+}
+
+/// Encodes and attempts to write a binary, like [write_bin](crate::encode::write_bin).
+///
+/// # Errors
+///
+/// Returns an `InvalidInput` error without writing anything if the data is longer than
+/// `u32::MAX` bytes.
+pub async fn write_bin<W: AsyncWrite + Unpin>(wr: &mut W, data: &[u8]) -> io::Result<()> {
+    write_header(wr, Header::bin_len(data_len(data.len())?)).await?;
+    wr.write_all(data).await
+}
+
+/// Encodes and attempts to write an array length, returning the marker used, like
+/// [write_array_len](crate::encode::write_array_len).
+pub async fn write_array_len<W: AsyncWrite + Unpin>(wr: &mut W, len: u32) -> io::Result<Marker> {
+    write_header(wr, Header::array_len(len)).await
+}
+
+/// Encodes and attempts to write a map length, returning the marker used, like
+/// [write_map_len](crate::encode::write_map_len).
+pub async fn write_map_len<W: AsyncWrite + Unpin>(wr: &mut W, len: u32) -> io::Result<Marker> {
+    write_header(wr, Header::map_len(len)).await
+}
+
+/// Encodes and attempts to write the header of an extension of `len` bytes with the given type,
+/// returning the marker used, like [write_ext_meta](crate::encode::write_ext_meta).
+///
+/// The `len` bytes of data must be written right after.
+pub async fn write_ext_meta<W: AsyncWrite + Unpin>(wr: &mut W, len: u32, ty: i8) -> io::Result<Marker> {
+    write_header(wr, Header::ext_meta(len, ty)).await
+}
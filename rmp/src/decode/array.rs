@@ -0,0 +1,144 @@
+use core::convert::TryFrom;
+
+use super::{read_array_len, read_int, DecodeError, RmpRead};
+use crate::Marker;
+
+/// Number of `f64` elements decoded per buffered read.
+const F64_CHUNK_LEN: usize = 128;
+/// Size of an encoded `f64` element: the marker followed by 8 bytes of data.
+const F64_ELEM_SIZE: usize = 9;
+
+#[inline]
+fn read_array_len_exact<R: RmpRead>(rd: &mut R, expected: usize) -> Result<(), DecodeError<R::Error>> {
+    // No MessagePack array is that long, so nothing is read.
+    let expected = u32::try_from(expected).map_err(|_| DecodeError::OutOfRange)?;
+    read_fixed_array(rd, expected)
+}
+
+/// Attempts to read the length of an array that must have exactly `expected_len` elements.
+///
+/// This is the guard for records encoded as a fixed-length array of positional fields, which the
+/// caller decodes one by one afterwards.
+///
+/// # Errors
+///
+/// Returns `DecodeError::ArrayLengthMismatch` with both lengths if the array has a different
+/// length, and `DecodeError::TypeMismatch` if the value is not an array. Only the array
+/// header is consumed in either case.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{self, read_fixed_array, DecodeError};
+///
+/// // A point record: [x, y].
+/// let buf = [0x92, 0x01, 0xff];
+/// let mut rd = &buf[..];
+/// read_fixed_array(&mut rd, 2).unwrap();
+/// let point: (i32, i32) = (decode::read_int(&mut rd).unwrap(), decode::read_int(&mut rd).unwrap());
+/// assert_eq!((1, -1), point);
+///
+/// match read_fixed_array(&mut &buf[..], 3) {
+///     Err(DecodeError::ArrayLengthMismatch { expected: 3, actual: 2 }) => {}
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+/// ```
+pub fn read_fixed_array<R: RmpRead>(rd: &mut R, expected_len: u32) -> Result<(), DecodeError<R::Error>> {
+    let actual = read_array_len(rd)?;
+    if actual != expected_len {
+        return Err(DecodeError::ArrayLengthMismatch { expected: expected_len, actual });
+    }
+    Ok(())
+}
+
+/// Attempts to read an array of `f64` values into the given slice.
+///
+/// The array length must be equal to the slice length. Since every element must be encoded as
+/// `f64` with the same marker, the payload is read in large chunks and decoded in a tight loop,
+/// which is much faster than calling [read_f64](super::read_f64) for each element.
+///
+/// # Errors
+///
+/// Returns `DecodeError::ArrayLengthMismatch` if the array length differs from the slice
+/// length, and `DecodeError::TypeMismatch` if any element is not an `f64`.
+/// `DecodeError::OutOfRange` is returned without reading anything if the slice is longer
+/// than `u32::MAX`, the longest possible array.
+///
+/// The marker of the first element of every chunk is checked before the rest of the chunk is
+/// read, so an array of other elements is reported as a `TypeMismatch` after reading only its
+/// first marker, even if the input ends right after it. A mismatch later in a chunk is found only
+/// once the whole chunk is read.
+///
+/// On error the reader position and the slice content are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::read_f64_array_into;
+/// use rmp::encode::{write_array_len, write_f64};
+///
+/// let mut buf = Vec::new();
+/// write_array_len(&mut buf, 3).unwrap();
+/// for v in &[1.0, 2.5, -3.0] {
+///     write_f64(&mut buf, *v).unwrap();
+/// }
+///
+/// let mut out = [0.0; 3];
+/// read_f64_array_into(&mut &buf[..], &mut out).unwrap();
+/// assert_eq!([1.0, 2.5, -3.0], out);
+/// ```
+pub fn read_f64_array_into<R: RmpRead>(rd: &mut R, out: &mut [f64]) -> Result<(), DecodeError<R::Error>> {
+    read_array_len_exact(rd, out.len())?;
+
+    let mut buf = [0u8; F64_CHUNK_LEN * F64_ELEM_SIZE];
+    for chunk in out.chunks_mut(F64_CHUNK_LEN) {
+        let buf = &mut buf[..chunk.len() * F64_ELEM_SIZE];
+        buf[0] = rd.read_u8().map_err(DecodeError::InvalidMarkerRead)?;
+        if buf[0] != Marker::F64.to_u8() {
+            return Err(DecodeError::TypeMismatch(Marker::from_u8(buf[0])));
+        }
+        rd.read_exact_buf(&mut buf[1..]).map_err(DecodeError::InvalidDataRead)?;
+
+        for (val, elem) in chunk.iter_mut().zip(buf.chunks_exact(F64_ELEM_SIZE)) {
+            if elem[0] != Marker::F64.to_u8() {
+                return Err(DecodeError::TypeMismatch(Marker::from_u8(elem[0])));
+            }
+            let mut data = [0u8; 8];
+            data.copy_from_slice(&elem[1..]);
+            *val = f64::from_bits(u64::from_be_bytes(data));
+        }
+    }
+
+    Ok(())
+}
+
+macro_rules! read_int_array_into {
+    ($name:ident, $tp:ident) => {
+        #[doc = concat!("Attempts to read an array of integers into the given `", stringify!($tp), "` slice.")]
+        ///
+        /// The array length must be equal to the slice length. Unlike floats, each element may use
+        /// any integer marker as long as the value fits, like with [read_int](super::read_int), so
+        /// the elements are decoded one by one after the length check.
+        ///
+        /// # Errors
+        ///
+        /// Returns `DecodeError::ArrayLengthMismatch` if the array length differs from the
+        /// slice length, `DecodeError::TypeMismatch` if any element is not an integer and
+        /// `DecodeError::OutOfRange` if it doesn't fit, or without reading anything if the
+        /// slice is longer than `u32::MAX`.
+        ///
+        /// On error the reader position and the slice content are unspecified.
+        pub fn $name<R: RmpRead>(rd: &mut R, out: &mut [$tp]) -> Result<(), DecodeError<R::Error>> {
+            read_array_len_exact(rd, out.len())?;
+
+            for val in out.iter_mut() {
+                *val = read_int(rd)?;
+            }
+
+            Ok(())
+        }
+    };
+}
+
+read_int_array_into!(read_i64_array_into, i64);
+read_int_array_into!(read_u32_array_into, u32);
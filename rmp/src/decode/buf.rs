@@ -0,0 +1,155 @@
+//! Integration with the [bytes](::bytes) crate.
+
+use ::bytes::{Buf, Bytes};
+
+use super::bytes::BytesReadError;
+#[cfg(not(feature = "std"))]
+use super::RmpRead;
+use super::{read_bin_len, read_str_len, DecodeStringError, ValueReadError};
+
+/// A reader adapter over any [Buf], that advances it as the data is consumed.
+///
+/// This allows decoding directly from `bytes::Bytes`, `bytes::BytesMut` and chained buffers
+/// without copying them into a contiguous slice first.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{self, BufSource};
+///
+/// let buf = bytes::Bytes::from_static(&[0xcd, 0x01, 0x2c, 0xc3]);
+/// let mut rd = BufSource::new(buf);
+///
+/// assert_eq!(300, decode::read_u16(&mut rd).unwrap());
+/// assert_eq!(&[0xc3], &rd.into_inner()[..]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BufSource<B> {
+    inner: B,
+}
+
+impl<B> BufSource<B> {
+    /// Wrap the specified buffer.
+    #[inline]
+    pub fn new(inner: B) -> Self {
+        BufSource { inner }
+    }
+
+    /// Get a reference to the underlying buffer.
+    #[inline]
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying buffer.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Unwrap this adapter, returning the remaining buffer.
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<B: Buf> std::io::Read for BufSource<B> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(self.inner.remaining());
+        self.inner.copy_to_slice(&mut buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Fallback implementation for `#[no_std]`
+///
+/// With `feature = "std"` this is covered by the blanket implementation for `std::io::Read`.
+#[cfg(not(feature = "std"))]
+impl<B: Buf> RmpRead for BufSource<B> {
+    type Error = BytesReadError;
+
+    #[inline]
+    fn read_exact_buf(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
+        let remaining = self.inner.remaining();
+        if buf.len() <= remaining {
+            self.inner.copy_to_slice(buf);
+            Ok(())
+        } else {
+            Err(BytesReadError::InsufficientBytes {
+                expected: buf.len(),
+                actual: remaining,
+                position: 0
+            })
+        }
+    }
+}
+
+/// Splits the `len` bytes of data after a `nread`-byte header off the front of `buf`.
+fn split_data(buf: &mut Bytes, nread: usize, len: usize) -> Result<Bytes, BytesReadError> {
+    let remaining = buf.len() - nread;
+    if len > remaining {
+        return Err(BytesReadError::InsufficientBytes {
+            expected: len,
+            actual: remaining,
+            position: nread as u64,
+        });
+    }
+
+    buf.advance(nread);
+    Ok(buf.split_to(len))
+}
+
+/// Attempts to read a binary value from the front of `buf`, returning its data without copying.
+///
+/// The returned `Bytes` shares the underlying memory with `buf`, which is advanced past the value.
+/// On error `buf` is left untouched.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::read_bin_bytes;
+///
+/// let mut buf = bytes::Bytes::from_static(&[0xc4, 0x02, 0xca, 0xfe, 0xc0]);
+///
+/// assert_eq!(&[0xca, 0xfe], &read_bin_bytes(&mut buf).unwrap()[..]);
+/// assert_eq!(&[0xc0], &buf[..]);
+/// ```
+pub fn read_bin_bytes(buf: &mut Bytes) -> Result<Bytes, ValueReadError<BytesReadError>> {
+    let mut rd = super::Bytes::new(buf);
+    let len = read_bin_len(&mut rd)? as usize;
+    let nread = rd.position() as usize;
+
+    split_data(buf, nread, len).map_err(ValueReadError::InvalidDataRead)
+}
+
+/// Attempts to read a string value from the front of `buf`, returning its data without copying.
+///
+/// The returned `Bytes` shares the underlying memory with `buf` and is guaranteed to be valid
+/// utf-8. `buf` is advanced past the value. On error `buf` is left untouched.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::read_str_bytes;
+///
+/// let mut buf = bytes::Bytes::from_static(&[0xa2, 0x6f, 0x6b, 0xc0]);
+///
+/// assert_eq!(b"ok", &read_str_bytes(&mut buf).unwrap()[..]);
+/// assert_eq!(&[0xc0], &buf[..]);
+/// ```
+pub fn read_str_bytes(buf: &mut Bytes) -> Result<Bytes, DecodeStringError<'_, BytesReadError>> {
+    let mut rd = super::Bytes::new(buf);
+    let len = read_str_len(&mut rd)? as usize;
+    let nread = rd.position() as usize;
+
+    if let Some(data) = buf[nread..].get(..len) {
+        if let Err(err) = core::str::from_utf8(data) {
+            return Err(DecodeStringError::InvalidUtf8(&buf[nread..nread + len], err));
+        }
+    }
+
+    split_data(buf, nread, len).map_err(DecodeStringError::InvalidDataRead)
+}
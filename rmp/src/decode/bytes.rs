@@ -1,8 +1,9 @@
 //! Implementation of the [Bytes] type
 
+use alloc::vec::Vec;
 use core::fmt::{Display, Formatter};
 use crate::decode::RmpReadErr;
-use super::RmpRead;
+use super::{read_str_len, DecodeStringError, RmpRead};
 
 /// Indicates that an error occurred reading from [Bytes]
 #[derive(Debug)]
@@ -60,6 +61,36 @@ impl<'a> Bytes<'a> {
     pub fn new(bytes: &'a [u8]) -> Self {
         Bytes { bytes, current_position: 0 }
     }
+    /// Wrap an existing bytes slice, starting at the given offset.
+    ///
+    /// Unlike slicing the input first, [Self::position] reports absolute offsets into `bytes`,
+    /// which is useful for error reporting when decoding from the middle of a larger buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is greater than the length of `bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmp::decode::{read_pfix, Bytes};
+    ///
+    /// let buf = [0x01, 0x02, 0x03];
+    /// let mut rd = Bytes::with_position(&buf, 1);
+    ///
+    /// assert_eq!(2, read_pfix(&mut rd).unwrap());
+    /// assert_eq!(2, rd.position());
+    /// assert_eq!(1, rd.remaining());
+    /// ```
+    #[inline]
+    pub fn with_position(bytes: &'a [u8], start: usize) -> Self {
+        Bytes { bytes: &bytes[start..], current_position: start as u64 }
+    }
+    /// Return the number of bytes remaining in the buffer.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
     /// Get a reference to the remaining bytes in the buffer.
     #[inline]
     pub fn remaining_slice(&self) -> &'a [u8] {
@@ -73,6 +104,47 @@ impl<'a> Bytes<'a> {
     pub fn position(&self) -> u64 {
         self.current_position
     }
+    /// Read a string value, borrowing its data from the underlying buffer.
+    ///
+    /// The length is read first, then the exact subslice is validated as utf-8 once and returned
+    /// with the lifetime of the buffer. The position is advanced past the value only on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecodeStringError::InvalidDataRead` with `BytesReadError::InsufficientBytes` if the
+    /// buffer is shorter than the declared length, and `DecodeStringError::InvalidUtf8` if the data
+    /// is not valid utf-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmp::decode::Bytes;
+    ///
+    /// let buf = [0xaa, 0x6c, 0x65, 0x20, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0xc0];
+    /// let mut rd = Bytes::new(&buf);
+    ///
+    /// assert_eq!("le message", rd.read_str_slice().unwrap());
+    /// assert_eq!(11, rd.position());
+    /// ```
+    pub fn read_str_slice(&mut self) -> Result<&'a str, DecodeStringError<'a, BytesReadError>> {
+        let mut rd = *self;
+        let len = read_str_len(&mut rd)? as usize;
+
+        if len > rd.bytes.len() {
+            return Err(DecodeStringError::InvalidDataRead(BytesReadError::InsufficientBytes {
+                expected: len,
+                actual: rd.bytes.len(),
+                position: rd.current_position,
+            }));
+        }
+
+        let (data, rest) = rd.bytes.split_at(len);
+        let s = core::str::from_utf8(data).map_err(|err| DecodeStringError::InvalidUtf8(data, err))?;
+
+        self.bytes = rest;
+        self.current_position = rd.current_position + len as u64;
+        Ok(s)
+    }
 }
 impl<'a> From<&'a [u8]> for Bytes<'a> {
     #[inline]
@@ -116,6 +188,23 @@ impl RmpRead for Bytes<'_> {
             })
         }
     }
+
+    #[inline]
+    fn read_exact_into_vec(&mut self, buf: &mut Vec<u8>, len: usize) -> Result<(), Self::Error> {
+        if len <= self.bytes.len() {
+            let (src, newly_remaining) = self.bytes.split_at(len);
+            self.bytes = newly_remaining;
+            self.current_position += len as u64;
+            buf.extend_from_slice(src);
+            Ok(())
+        } else {
+            Err(BytesReadError::InsufficientBytes {
+                expected: len,
+                actual: self.bytes.len(),
+                position: self.current_position
+            })
+        }
+    }
 }
 
 #[cfg(not(feature = "std"))]
@@ -150,4 +239,19 @@ impl<'a> RmpRead for &'a [u8] {
             })
         }
     }
+
+    fn read_exact_into_vec(&mut self, buf: &mut Vec<u8>, len: usize) -> Result<(), Self::Error> {
+        if len <= self.len() {
+            let (src, newly_remaining) = self.split_at(len);
+            *self = newly_remaining;
+            buf.extend_from_slice(src);
+            Ok(())
+        } else {
+            Err(BytesReadError::InsufficientBytes {
+                expected: len,
+                actual: self.len(),
+                position: 0
+            })
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,185 @@
+//! Strict decoding functions that reject non-canonical encodings.
+//!
+//! MessagePack allows the same value to be encoded in several ways, for example `5` can be
+//! written as a positive fixint or as a `u64` with seven wasted bytes. The functions in this
+//! module accept only the minimal-width encoding, which is the one produced by the `write_*`
+//! functions of this crate, so that every value has exactly one accepted byte representation.
+//! This matters for formats whose encoded bytes are hashed or signed.
+//!
+//! Non-negative integers must use the unsigned family, so `I8` holding `1` is rejected as well.
+
+use num_traits::cast::FromPrimitive;
+
+use super::{read_marker, DecodeError, RmpRead};
+use crate::Marker;
+
+/// Attempts to read an integer, like [read_int](super::read_int), but only if it is encoded with
+/// the smallest marker that fits its value.
+///
+/// # Errors
+///
+/// Returns `DecodeError::NonCanonical` with the actual marker if a smaller encoding exists,
+/// in addition to the errors returned by [read_int](super::read_int).
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{read_int_canonical, DecodeError};
+/// use rmp::Marker;
+///
+/// assert_eq!(300u16, read_int_canonical(&mut &[0xcd, 0x01, 0x2c][..]).unwrap());
+///
+/// match read_int_canonical::<u16, _>(&mut &[0xcd, 0x00, 0x05][..]) {
+///     Err(DecodeError::NonCanonical(Marker::U16)) => {}
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+/// ```
+pub fn read_int_canonical<T: FromPrimitive, R: RmpRead>(rd: &mut R) -> Result<T, DecodeError<R::Error>> {
+    let marker = read_marker(rd)?;
+    let val = match marker {
+        Marker::FixPos(val) => T::from_u8(val),
+        Marker::FixNeg(val) => T::from_i8(val),
+        Marker::U8 => {
+            let val = rd.read_data_u8()?;
+            check(marker, val >= 0x80)?;
+            T::from_u8(val)
+        }
+        Marker::U16 => {
+            let val = rd.read_data_u16()?;
+            check(marker, val > u16::from(u8::MAX))?;
+            T::from_u16(val)
+        }
+        Marker::U32 => {
+            let val = rd.read_data_u32()?;
+            check(marker, val > u32::from(u16::MAX))?;
+            T::from_u32(val)
+        }
+        Marker::U64 => {
+            let val = rd.read_data_u64()?;
+            check(marker, val > u64::from(u32::MAX))?;
+            T::from_u64(val)
+        }
+        Marker::I8 => {
+            let val = rd.read_data_i8()?;
+            check(marker, val < -32)?;
+            T::from_i8(val)
+        }
+        Marker::I16 => {
+            let val = rd.read_data_i16()?;
+            check(marker, val < i16::from(i8::MIN))?;
+            T::from_i16(val)
+        }
+        Marker::I32 => {
+            let val = rd.read_data_i32()?;
+            check(marker, val < i32::from(i16::MIN))?;
+            T::from_i32(val)
+        }
+        Marker::I64 => {
+            let val = rd.read_data_i64()?;
+            check(marker, val < i64::from(i32::MIN))?;
+            T::from_i64(val)
+        }
+        marker => return Err(DecodeError::TypeMismatch(marker)),
+    };
+
+    val.ok_or(DecodeError::OutOfRange)
+}
+
+/// Attempts to read a string length, like [read_str_len](super::read_str_len), but only if it is
+/// encoded with the smallest marker that fits.
+///
+/// # Errors
+///
+/// Returns `DecodeError::NonCanonical` with the actual marker if a smaller encoding exists
+/// and `DecodeError::TypeMismatch` if the value is not a string.
+pub fn read_str_len_canonical<R: RmpRead>(rd: &mut R) -> Result<u32, DecodeError<R::Error>> {
+    let marker = read_marker(rd)?;
+    let len = match marker {
+        Marker::FixStr(len) => return Ok(u32::from(len)),
+        Marker::Str8 => u32::from(rd.read_data_u8()?),
+        Marker::Str16 => u32::from(rd.read_data_u16()?),
+        Marker::Str32 => rd.read_data_u32()?,
+        marker => return Err(DecodeError::TypeMismatch(marker)),
+    };
+
+    check_len(marker, len)
+}
+
+/// Attempts to read a binary length, like [read_bin_len](super::read_bin_len), but only if it is
+/// encoded with the smallest marker that fits.
+///
+/// # Errors
+///
+/// Returns `DecodeError::NonCanonical` with the actual marker if a smaller encoding exists
+/// and `DecodeError::TypeMismatch` if the value is not a binary.
+pub fn read_bin_len_canonical<R: RmpRead>(rd: &mut R) -> Result<u32, DecodeError<R::Error>> {
+    let marker = read_marker(rd)?;
+    let len = match marker {
+        Marker::Bin8 => return Ok(u32::from(rd.read_data_u8()?)),
+        Marker::Bin16 => u32::from(rd.read_data_u16()?),
+        Marker::Bin32 => rd.read_data_u32()?,
+        marker => return Err(DecodeError::TypeMismatch(marker)),
+    };
+
+    check_len(marker, len)
+}
+
+/// Attempts to read an array length, like [read_array_len](super::read_array_len), but only if it
+/// is encoded with the smallest marker that fits.
+///
+/// # Errors
+///
+/// Returns `DecodeError::NonCanonical` with the actual marker if a smaller encoding exists
+/// and `DecodeError::TypeMismatch` if the value is not an array.
+pub fn read_array_len_canonical<R: RmpRead>(rd: &mut R) -> Result<u32, DecodeError<R::Error>> {
+    let marker = read_marker(rd)?;
+    let len = match marker {
+        Marker::FixArray(len) => return Ok(u32::from(len)),
+        Marker::Array16 => u32::from(rd.read_data_u16()?),
+        Marker::Array32 => rd.read_data_u32()?,
+        marker => return Err(DecodeError::TypeMismatch(marker)),
+    };
+
+    check_len(marker, len)
+}
+
+/// Attempts to read a map length, like [read_map_len](super::read_map_len), but only if it is
+/// encoded with the smallest marker that fits.
+///
+/// # Errors
+///
+/// Returns `DecodeError::NonCanonical` with the actual marker if a smaller encoding exists
+/// and `DecodeError::TypeMismatch` if the value is not a map.
+pub fn read_map_len_canonical<R: RmpRead>(rd: &mut R) -> Result<u32, DecodeError<R::Error>> {
+    let marker = read_marker(rd)?;
+    let len = match marker {
+        Marker::FixMap(len) => return Ok(u32::from(len)),
+        Marker::Map16 => u32::from(rd.read_data_u16()?),
+        Marker::Map32 => rd.read_data_u32()?,
+        marker => return Err(DecodeError::TypeMismatch(marker)),
+    };
+
+    check_len(marker, len)
+}
+
+#[inline]
+fn check<E: super::RmpReadErr>(marker: Marker, canonical: bool) -> Result<(), DecodeError<E>> {
+    if canonical {
+        Ok(())
+    } else {
+        Err(DecodeError::NonCanonical(marker))
+    }
+}
+
+/// Checks that a length read after an 8, 16 or 32-bit length marker doesn't fit a smaller one.
+#[inline]
+fn check_len<E: super::RmpReadErr>(marker: Marker, len: u32) -> Result<u32, DecodeError<E>> {
+    let min = match marker {
+        Marker::Array16 | Marker::Map16 => 0x10,
+        Marker::Str8 => 0x20,
+        Marker::Str16 | Marker::Bin16 => 0x100,
+        _ => 0x1_0000,
+    };
+    check(marker, len >= min)?;
+    Ok(len)
+}
@@ -5,6 +5,9 @@ use crate::Marker;
 ///
 /// The first byte should be the marker and the others should represent the data itself.
 ///
+/// The data is decoded with `f32::from_bits` without any normalization, so NaN payloads, signaling
+/// NaNs and negative zero written by [write_f32](crate::encode::write_f32) are preserved bit-exactly.
+///
 /// # Errors
 ///
 /// This function will return `ValueReadError` on any I/O error while reading either the marker or
@@ -28,6 +31,9 @@ pub fn read_f32<R: RmpRead>(rd: &mut R) -> Result<f32, ValueReadError<R::Error>>
 ///
 /// The first byte should be the marker and the others should represent the data itself.
 ///
+/// The data is decoded with `f64::from_bits` without any normalization, so NaN payloads, signaling
+/// NaNs and negative zero written by [write_f64](crate::encode::write_f64) are preserved bit-exactly.
+///
 /// # Errors
 ///
 /// This function will return `ValueReadError` on any I/O error while reading either the marker or
@@ -1,5 +1,5 @@
 use crate::Marker;
-use super::{read_marker, RmpRead, ValueReadError};
+use super::{read_marker, DecodeError, RmpRead, ValueReadError};
 
 /// Attempts to read exactly 3 bytes from the given reader and interpret them as a fixext1 type
 /// with data attached.
@@ -166,3 +166,38 @@ pub fn read_ext_meta<R: RmpRead>(rd: &mut R) -> Result<ExtMeta, ValueReadError<R
 
     Ok(meta)
 }
+
+/// Attempts to read the extension meta information like [read_ext_meta], failing if the declared
+/// size is greater than `max`.
+///
+/// Use this instead of [read_ext_meta] for untrusted input, when the size is going to be used to
+/// allocate or read the extension body.
+///
+/// # Errors
+///
+/// Returns `DecodeError::LengthLimitExceeded` if `size > max`. The marker, size and type are
+/// consumed by then, but none of the body.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{read_ext_meta_max, DecodeError, ExtMeta};
+///
+/// // An ext32 with type 1 claiming 4 GiB of data.
+/// let buf = [0xc9, 0xff, 0xff, 0xff, 0xff, 0x01];
+/// match read_ext_meta_max(&mut &buf[..], 1024) {
+///     Err(DecodeError::LengthLimitExceeded { len: 4294967295, max: 1024 }) => {}
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+///
+/// let buf = [0xd6, 0x01, 0x00, 0x00, 0x00, 0x02];
+/// assert_eq!(ExtMeta { typeid: 1, size: 4 }, read_ext_meta_max(&mut &buf[..], 1024).unwrap());
+/// ```
+pub fn read_ext_meta_max<R: RmpRead>(rd: &mut R, max: u32) -> Result<ExtMeta, DecodeError<R::Error>> {
+    let meta = read_ext_meta(rd)?;
+    if meta.size > max {
+        return Err(DecodeError::LengthLimitExceeded { len: meta.size, max });
+    }
+
+    Ok(meta)
+}
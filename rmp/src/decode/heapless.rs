@@ -0,0 +1,182 @@
+//! Decoding into fixed-capacity [heapless] containers, for targets without an allocator.
+
+#[cfg(feature = "std")]
+use std::error;
+use core::fmt::{self, Display, Formatter};
+use core::str::Utf8Error;
+
+use super::{read_array_len, read_bin_len, read_str_len, DecodeError, RmpRead, RmpReadErr, ValueReadError};
+use crate::Marker;
+
+/// An error which can occur when decoding into a fixed-capacity container.
+#[derive(Debug)]
+pub enum HeaplessReadError<E: RmpReadErr> {
+    /// Failed to read the marker.
+    InvalidMarkerRead(E),
+    /// Failed to read the data.
+    InvalidDataRead(E),
+    /// The type decoded isn't match with the expected one.
+    TypeMismatch(Marker),
+    /// The declared length is larger than the capacity of the container.
+    ///
+    /// Returned before reading any of the body.
+    CapacityExceeded(u32),
+    /// The string data is not valid utf-8.
+    InvalidUtf8(Utf8Error),
+}
+
+#[cfg(feature = "std")]
+impl<E: RmpReadErr> error::Error for HeaplessReadError<E> {
+    #[cold]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            HeaplessReadError::InvalidMarkerRead(ref err) |
+            HeaplessReadError::InvalidDataRead(ref err) => Some(err),
+            HeaplessReadError::TypeMismatch(..) |
+            HeaplessReadError::CapacityExceeded(..) => None,
+            HeaplessReadError::InvalidUtf8(ref err) => Some(err),
+        }
+    }
+}
+
+impl<E: RmpReadErr> Display for HeaplessReadError<E> {
+    #[cold]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match *self {
+            HeaplessReadError::InvalidMarkerRead(ref err) => write!(f, "failed to read MessagePack marker: {}", err),
+            HeaplessReadError::InvalidDataRead(ref err) => write!(f, "failed to read MessagePack data: {}", err),
+            HeaplessReadError::TypeMismatch(ref marker) => write!(f, "type mismatch, received {:?}", marker),
+            HeaplessReadError::CapacityExceeded(len) => write!(f, "length {} exceeds the container capacity", len),
+            HeaplessReadError::InvalidUtf8(ref err) => write!(f, "invalid utf-8: {}", err),
+        }
+    }
+}
+
+impl<E: RmpReadErr> From<ValueReadError<E>> for HeaplessReadError<E> {
+    #[cold]
+    fn from(err: ValueReadError<E>) -> HeaplessReadError<E> {
+        match err {
+            ValueReadError::InvalidMarkerRead(err) => HeaplessReadError::InvalidMarkerRead(err),
+            ValueReadError::InvalidDataRead(err) => HeaplessReadError::InvalidDataRead(err),
+            ValueReadError::TypeMismatch(marker) => HeaplessReadError::TypeMismatch(marker),
+        }
+    }
+}
+
+impl<E: RmpReadErr> From<HeaplessReadError<E>> for DecodeError<E> {
+    #[cold]
+    fn from(err: HeaplessReadError<E>) -> DecodeError<E> {
+        match err {
+            HeaplessReadError::InvalidMarkerRead(err) => DecodeError::InvalidMarkerRead(err),
+            HeaplessReadError::InvalidDataRead(err) => DecodeError::InvalidDataRead(err),
+            HeaplessReadError::TypeMismatch(marker) => DecodeError::TypeMismatch(marker),
+            HeaplessReadError::CapacityExceeded(len) => DecodeError::CapacityExceeded(len),
+            HeaplessReadError::InvalidUtf8(err) => DecodeError::InvalidUtf8(err),
+        }
+    }
+}
+
+/// Checks the declared length against the capacity `N`.
+#[inline]
+fn check_capacity<E: RmpReadErr, const N: usize>(len: u32) -> Result<usize, HeaplessReadError<E>> {
+    let ulen = len as usize;
+    if ulen > N {
+        return Err(HeaplessReadError::CapacityExceeded(len));
+    }
+    Ok(ulen)
+}
+
+/// Attempts to read a binary value into a [heapless::Vec] of capacity `N`.
+///
+/// # Errors
+///
+/// Returns `HeaplessReadError::CapacityExceeded` with the declared length if it is larger than `N`,
+/// without reading the data.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{read_bin_heapless, HeaplessReadError};
+///
+/// let buf = [0xc4, 0x03, 0x01, 0x02, 0x03];
+///
+/// let bin = read_bin_heapless::<_, 4>(&mut &buf[..]).unwrap();
+/// assert_eq!([1, 2, 3], bin[..]);
+///
+/// match read_bin_heapless::<_, 2>(&mut &buf[..]) {
+///     Err(HeaplessReadError::CapacityExceeded(3)) => {}
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+/// ```
+pub fn read_bin_heapless<R: RmpRead, const N: usize>(rd: &mut R) -> Result<::heapless::Vec<u8, N>, HeaplessReadError<R::Error>> {
+    let len = check_capacity::<R::Error, N>(read_bin_len(rd)?)?;
+    read_data_heapless(rd, len)
+}
+
+/// Attempts to read a string value into a [heapless::String] of capacity `N`.
+///
+/// # Errors
+///
+/// Returns `HeaplessReadError::CapacityExceeded` with the declared length if it is larger than `N`,
+/// without reading the data, and `HeaplessReadError::InvalidUtf8` if the data is not utf-8.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::read_str_heapless;
+///
+/// let buf = [0xaa, 0x6c, 0x65, 0x20, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65];
+///
+/// assert_eq!("le message", read_str_heapless::<_, 16>(&mut &buf[..]).unwrap());
+/// ```
+pub fn read_str_heapless<R: RmpRead, const N: usize>(rd: &mut R) -> Result<::heapless::String<N>, HeaplessReadError<R::Error>> {
+    let len = check_capacity::<R::Error, N>(read_str_len(rd)?)?;
+    let buf = read_data_heapless(rd, len)?;
+    ::heapless::String::from_utf8(buf).map_err(HeaplessReadError::InvalidUtf8)
+}
+
+/// Attempts to read an array into a [heapless::Vec] of capacity `N`, decoding each element with `f`.
+///
+/// # Errors
+///
+/// Returns `HeaplessReadError::CapacityExceeded` with the declared length if it is larger than `N`,
+/// without reading any elements. Errors returned by `f` are propagated as-is.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{read_array_heapless, read_pfix, HeaplessReadError};
+///
+/// let buf = [0x93, 0x01, 0x02, 0x03];
+///
+/// let arr: heapless::Vec<u8, 4> = read_array_heapless(&mut &buf[..], |rd| {
+///     read_pfix(rd).map_err(HeaplessReadError::from)
+/// }).unwrap();
+/// assert_eq!([1, 2, 3], arr[..]);
+/// ```
+pub fn read_array_heapless<R, T, F, E, const N: usize>(rd: &mut R, mut f: F) -> Result<::heapless::Vec<T, N>, E>
+where
+    R: RmpRead,
+    F: FnMut(&mut R) -> Result<T, E>,
+    E: From<HeaplessReadError<R::Error>>,
+{
+    let len = read_array_len(rd).map_err(HeaplessReadError::from)?;
+    let len = check_capacity::<R::Error, N>(len)?;
+
+    let mut vec = ::heapless::Vec::new();
+    for _ in 0..len {
+        // Can't fail, the capacity is checked above.
+        let _ = vec.push(f(rd)?);
+    }
+    Ok(vec)
+}
+
+fn read_data_heapless<R: RmpRead, const N: usize>(rd: &mut R, len: usize) -> Result<::heapless::Vec<u8, N>, HeaplessReadError<R::Error>> {
+    debug_assert!(len <= N);
+
+    let mut buf = ::heapless::Vec::new();
+    // Can't fail, the capacity is checked by the caller.
+    let _ = buf.resize_default(len);
+    rd.read_exact_buf(&mut buf).map_err(HeaplessReadError::InvalidDataRead)?;
+    Ok(buf)
+}
@@ -0,0 +1,167 @@
+//! Implementation of the [LimitRead] type
+
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(not(feature = "std"))]
+use super::{RmpRead, RmpReadErr};
+
+/// A reader adapter that fails once more than a total number of bytes is read through it.
+///
+/// Unlike checking declared lengths, this caps everything a decode can consume, including
+/// markers and the headers of deeply nested containers, so untrusted input can't keep a decoder
+/// reading forever. Every decode function works through it unchanged.
+///
+/// Reading exactly up to the limit succeeds. An attempt to read past it fails with an
+/// `InvalidData` I/O error wrapping [LimitExceeded], after reading the bytes still allowed. The
+/// adapter should not be used after that, because the value being decoded is cut off. Without
+/// the `std` feature the error is `LimitReadError::LimitExceeded` instead, and nothing is read by
+/// the failed call.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{self, LimitRead};
+///
+/// // [[[[]]]]
+/// let buf = [0x91, 0x91, 0x91, 0x90];
+/// let mut rd = LimitRead::new(&buf[..], 3);
+///
+/// for _ in 0..3 {
+///     assert_eq!(1, decode::read_array_len(&mut rd).unwrap());
+/// }
+/// assert_eq!(0, rd.remaining());
+///
+/// assert!(decode::read_array_len(&mut rd).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LimitRead<R> {
+    inner: R,
+    limit: u64,
+    remaining: u64,
+}
+
+impl<R> LimitRead<R> {
+    /// Wrap the specified reader, allowing at most `limit` bytes to be read through it.
+    #[inline]
+    pub fn new(inner: R, limit: u64) -> Self {
+        LimitRead { inner, limit, remaining: limit }
+    }
+
+    /// Return the total number of bytes this adapter allows to be read.
+    #[inline]
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Return the number of bytes that can still be read before the limit is hit.
+    #[inline]
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Get a reference to the underlying reader.
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader are not counted against the limit.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwrap this adapter, returning the underlying reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// The error reported by a [LimitRead] when reading past its limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitExceeded {
+    limit: u64,
+}
+
+impl LimitExceeded {
+    /// Return the limit of the reader that reported this error.
+    #[inline]
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+}
+
+impl Display for LimitExceeded {
+    #[cold]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "read limit of {} bytes exceeded", self.limit)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LimitExceeded {}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> std::io::Read for LimitRead<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.remaining == 0 {
+            let err = LimitExceeded { limit: self.limit };
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
+        }
+
+        let len = core::cmp::min(buf.len() as u64, self.remaining) as usize;
+        let n = self.inner.read(&mut buf[..len])?;
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+/// An error which can occur when reading through a [LimitRead] without the `std` feature.
+#[cfg(not(feature = "std"))]
+#[derive(Debug)]
+pub enum LimitReadError<E: RmpReadErr> {
+    /// The underlying reader failed.
+    InvalidRead(E),
+    /// The read would go past the limit.
+    LimitExceeded(LimitExceeded),
+}
+
+#[cfg(not(feature = "std"))]
+impl<E: RmpReadErr> Display for LimitReadError<E> {
+    #[cold]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match *self {
+            LimitReadError::InvalidRead(ref err) => Display::fmt(err, f),
+            LimitReadError::LimitExceeded(ref err) => Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl<E: RmpReadErr> RmpReadErr for LimitReadError<E> {}
+
+/// Fallback implementation for `#[no_std]`
+///
+/// With `feature = "std"` this is covered by the blanket implementation for `std::io::Read`.
+#[cfg(not(feature = "std"))]
+impl<R: RmpRead> RmpRead for LimitRead<R> {
+    type Error = LimitReadError<R::Error>;
+
+    #[inline]
+    fn read_exact_buf(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
+        if buf.len() as u64 > self.remaining {
+            return Err(LimitReadError::LimitExceeded(LimitExceeded { limit: self.limit }));
+        }
+
+        self.inner.read_exact_buf(buf).map_err(LimitReadError::InvalidRead)?;
+        self.remaining -= buf.len() as u64;
+        Ok(())
+    }
+}
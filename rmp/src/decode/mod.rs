@@ -9,17 +9,43 @@
 //! non-blocking socket and it returns EWOULDBLOCK) be sure that you buffer the data externally
 //! to avoid data loss (using `BufRead` readers with manual consuming or some other way).
 
+mod array;
+#[cfg(feature = "bytes")]
+mod buf;
+mod canonical;
 mod dec;
 mod ext;
+#[cfg(feature = "heapless")]
+mod heapless;
+mod limit;
+#[cfg(feature = "std")]
+mod peek;
 mod sint;
+mod skip;
 mod str;
 mod uint;
 
+pub use self::array::{read_f64_array_into, read_fixed_array, read_i64_array_into, read_u32_array_into};
+#[cfg(feature = "bytes")]
+pub use self::buf::{read_bin_bytes, read_str_bytes, BufSource};
+pub use self::canonical::{
+    read_array_len_canonical, read_bin_len_canonical, read_int_canonical, read_map_len_canonical,
+    read_str_len_canonical,
+};
 pub use self::dec::{read_f32, read_f64};
 pub use self::ext::{
-    read_ext_meta, read_fixext1, read_fixext16, read_fixext2, read_fixext4, read_fixext8, ExtMeta,
+    read_ext_meta, read_ext_meta_max, read_fixext1, read_fixext16, read_fixext2, read_fixext4,
+    read_fixext8, ExtMeta,
 };
+#[cfg(feature = "heapless")]
+pub use self::heapless::{read_array_heapless, read_bin_heapless, read_str_heapless, HeaplessReadError};
+#[cfg(not(feature = "std"))]
+pub use self::limit::LimitReadError;
+pub use self::limit::{LimitExceeded, LimitRead};
+#[cfg(feature = "std")]
+pub use self::peek::PeekReader;
 pub use self::sint::{read_i16, read_i32, read_i64, read_i8, read_nfix};
+pub use self::skip::{skip_value, skip_value_data};
 #[allow(deprecated)]
 // While we re-export deprecated items, we don't want to trigger warnings while compiling this crate
 pub use self::str::{read_str, read_str_from_slice, read_str_len, read_str_ref, DecodeStringError};
@@ -27,8 +53,11 @@ pub use self::uint::{read_pfix, read_u16, read_u32, read_u64, read_u8};
 
 #[cfg(feature = "std")]
 use std::error;
+use core::cmp::min;
 use core::fmt::{self, Display, Debug, Formatter};
 
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
 use num_traits::cast::FromPrimitive;
 
 use crate::Marker;
@@ -72,6 +101,10 @@ mod sealed{
     #[cfg(not(feature = "std"))]
     impl<'a> Sealed for &'a [u8] {}
     impl Sealed for super::Bytes<'_> {}
+    #[cfg(all(feature = "bytes", not(feature = "std")))]
+    impl<B: ::bytes::Buf> Sealed for super::BufSource<B> {}
+    #[cfg(not(feature = "std"))]
+    impl<R: super::RmpRead> Sealed for super::LimitRead<R> {}
 }
 
 
@@ -100,6 +133,44 @@ pub trait RmpRead: sealed::Sealed {
     /// See also [std::io::Read::read_exact]
     fn read_exact_buf(&mut self, buf: &mut [u8]) -> Result<(), Self::Error>;
 
+    /// Append exactly `len` bytes to the given vector.
+    ///
+    /// Unlike [Self::read_exact_buf], the caller doesn't need to zero-initialize the destination.
+    /// Slices and [Bytes] copy into the spare capacity of `buf` directly, while this default
+    /// implementation zero-fills each chunk before reading into it. On error `buf` is truncated
+    /// back to its original length.
+    #[inline]
+    #[doc(hidden)]
+    fn read_exact_into_vec(&mut self, buf: &mut Vec<u8>, len: usize) -> Result<(), Self::Error> {
+        // Fill in bounded chunks, so that a bogus length doesn't allocate more than the input has.
+        let start = buf.len();
+        while buf.len() - start < len {
+            let pos = buf.len();
+            buf.resize(pos + min(len - (pos - start), READ_CHUNK_SIZE), 0);
+            if let Err(err) = self.read_exact_buf(&mut buf[pos..]) {
+                buf.truncate(start);
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Skip exactly `len` bytes.
+    ///
+    /// If there are not enough bytes, this will return an error.
+    #[inline]
+    #[doc(hidden)]
+    fn skip_exact(&mut self, len: u64) -> Result<(), Self::Error> {
+        let mut buf = [0; 256];
+        let mut left = len;
+        while left > 0 {
+            let chunk = min(left, buf.len() as u64) as usize;
+            self.read_exact_buf(&mut buf[..chunk])?;
+            left -= chunk as u64;
+        }
+        Ok(())
+    }
+
     // Internal helper functions to map I/O error into the `InvalidDataRead` error.
 
     /// Read a single (unsigned) byte from this stream.
@@ -161,6 +232,10 @@ wrap_data_funcs_for_compatibility!(
     f32, f64
 );
 
+/// The largest capacity reserved ahead of reading data of a declared length.
+// See https://github.com/3Hren/msgpack-rust/issues/151
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
 #[cfg(feature = "std")]
 impl<T: std::io::Read> RmpRead for T {
     type Error = std::io::Error;
@@ -169,6 +244,40 @@ impl<T: std::io::Read> RmpRead for T {
     fn read_exact_buf(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
         std::io::Read::read_exact(self, buf)
     }
+
+    fn read_exact_into_vec(&mut self, buf: &mut Vec<u8>, len: usize) -> Result<(), Self::Error> {
+        let start = buf.len();
+        while buf.len() - start < len {
+            // Grow geometrically, but never reserve more than what was already read (or 64 KiB).
+            let read = buf.len() - start;
+            let chunk = min(len - read, read.max(READ_CHUNK_SIZE));
+            buf.reserve_exact(chunk);
+
+            // `read_to_end` reads into the spare capacity, but zero-initializes it first unless the
+            // reader implements `read_buf`, which only slices and a few others in std do.
+            let mut rd = std::io::Read::take(&mut *self, chunk as u64);
+            match std::io::Read::read_to_end(&mut rd, buf) {
+                Ok(n) if n == chunk => {}
+                Ok(..) => {
+                    buf.truncate(start);
+                    return Err(std::io::ErrorKind::UnexpectedEof.into());
+                }
+                Err(err) => {
+                    buf.truncate(start);
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn skip_exact(&mut self, len: u64) -> Result<(), Self::Error> {
+        let skipped = std::io::copy(&mut std::io::Read::take(&mut *self, len), &mut std::io::sink())?;
+        if skipped != len {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(())
+    }
 }
 
 // An error returned from the `write_marker` and `write_fixval` functions.
@@ -270,6 +379,39 @@ pub fn read_nil<R: RmpRead>(rd: &mut R) -> Result<(), ValueReadError<R::Error>>
     }
 }
 
+/// Attempts to read an optional value, decoding nil as `None`.
+///
+/// The marker is read first. If it's nil, it's consumed and `Ok(None)` is returned. Otherwise the
+/// already-read marker is handed to `f` together with the reader, like [marker_to_len] does, so
+/// `f` continues from the byte after the marker.
+///
+/// # Errors
+///
+/// This function will return `ValueReadError` on any I/O error while reading the marker. Errors
+/// returned by `f` are propagated as-is.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{marker_to_len, read_option};
+///
+/// let buf = [0xc0, 0x82];
+/// let mut rd = &buf[..];
+///
+/// assert_eq!(None, read_option(&mut rd, marker_to_len).unwrap());
+/// assert_eq!(Some(2), read_option(&mut rd, marker_to_len).unwrap());
+/// ```
+pub fn read_option<R, T, F>(rd: &mut R, f: F) -> Result<Option<T>, ValueReadError<R::Error>>
+where
+    R: RmpRead,
+    F: FnOnce(&mut R, Marker) -> Result<T, ValueReadError<R::Error>>,
+{
+    match read_marker(rd)? {
+        Marker::Null => Ok(None),
+        marker => f(rd, marker).map(Some),
+    }
+}
+
 /// Attempts to read a single byte from the given reader and to decode it as a boolean value.
 ///
 /// According to the MessagePack specification, an encoded boolean value is represented as a single
@@ -354,6 +496,181 @@ impl<E: RmpReadErr> From<ValueReadError<E>> for NumValueReadError<E> {
     }
 }
 
+/// A single error type covering every way decoding can fail, generic only over the reader error.
+///
+/// The `read_*` functions return the narrowest error for what they do, such as [ValueReadError] or
+/// [NumValueReadError]. A library built on rmp that calls several of them can convert each one with
+/// `?` into `DecodeError<R::Error>` instead of defining its own wrapper, since `From` is implemented
+/// for all error types of this module. This works the same on `std` and `no_std`, only `E` differs:
+/// `std::io::Error` for [std::io::Read] and [BytesReadError](bytes::BytesReadError) for [Bytes] on
+/// `no_std`.
+///
+/// To expose one error type regardless of the reader, convert the reader error with
+/// [DecodeError::map_read_err].
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{self, DecodeError, RmpRead};
+///
+/// fn read_point<R: RmpRead>(rd: &mut R) -> Result<(i32, i32), DecodeError<R::Error>> {
+///     let len = decode::read_array_len(rd)?;
+///     if len != 2 {
+///         return Err(DecodeError::ArrayLengthMismatch { expected: 2, actual: len });
+///     }
+///     Ok((decode::read_int(rd)?, decode::read_int(rd)?))
+/// }
+///
+/// assert_eq!((1, -1), read_point(&mut &[0x92, 0x01, 0xff][..]).unwrap());
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DecodeError<E: RmpReadErr> {
+    /// Failed to read the marker.
+    InvalidMarkerRead(E),
+    /// Failed to read the data.
+    InvalidDataRead(E),
+    /// The type decoded isn't match with the expected one.
+    TypeMismatch(Marker),
+    /// Out of range integral type conversion attempted.
+    OutOfRange,
+    /// The array length isn't equal to the expected one.
+    ArrayLengthMismatch { expected: u32, actual: u32 },
+    /// The value is encoded with a wider marker than needed, see [read_int_canonical].
+    NonCanonical(Marker),
+    /// The given buffer is not large enough to hold the declared length.
+    BufferSizeTooSmall(u32),
+    /// The declared length is larger than the capacity of the container.
+    CapacityExceeded(u32),
+    /// The declared length is greater than the allowed maximum.
+    LengthLimitExceeded { len: u32, max: u32 },
+    /// The string data is not valid utf-8.
+    InvalidUtf8(core::str::Utf8Error),
+    /// Arrays and maps are nested deeper than allowed, see [skip_value].
+    DepthLimitExceeded,
+    /// The same key appeared twice in a map, see [read_map_strict].
+    DuplicateKey,
+}
+
+impl<E: RmpReadErr> DecodeError<E> {
+    /// Converts the reader error with `f`, keeping all other variants as is.
+    ///
+    /// This bridges errors from different readers, for example `DecodeError<BytesReadError>` from
+    /// a zero-copy path and `DecodeError<std::io::Error>` from a stream.
+    pub fn map_read_err<F, U>(self, f: F) -> DecodeError<U>
+    where
+        F: FnOnce(E) -> U,
+        U: RmpReadErr,
+    {
+        match self {
+            DecodeError::InvalidMarkerRead(err) => DecodeError::InvalidMarkerRead(f(err)),
+            DecodeError::InvalidDataRead(err) => DecodeError::InvalidDataRead(f(err)),
+            DecodeError::TypeMismatch(marker) => DecodeError::TypeMismatch(marker),
+            DecodeError::OutOfRange => DecodeError::OutOfRange,
+            DecodeError::ArrayLengthMismatch { expected, actual } => DecodeError::ArrayLengthMismatch { expected, actual },
+            DecodeError::NonCanonical(marker) => DecodeError::NonCanonical(marker),
+            DecodeError::BufferSizeTooSmall(len) => DecodeError::BufferSizeTooSmall(len),
+            DecodeError::CapacityExceeded(len) => DecodeError::CapacityExceeded(len),
+            DecodeError::LengthLimitExceeded { len, max } => DecodeError::LengthLimitExceeded { len, max },
+            DecodeError::InvalidUtf8(err) => DecodeError::InvalidUtf8(err),
+            DecodeError::DepthLimitExceeded => DecodeError::DepthLimitExceeded,
+            DecodeError::DuplicateKey => DecodeError::DuplicateKey,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: RmpReadErr> error::Error for DecodeError<E> {
+    #[cold]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            DecodeError::InvalidMarkerRead(ref err) |
+            DecodeError::InvalidDataRead(ref err) => Some(err),
+            DecodeError::InvalidUtf8(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl<E: RmpReadErr> Display for DecodeError<E> {
+    #[cold]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match *self {
+            DecodeError::InvalidMarkerRead(ref err) => write!(f, "failed to read MessagePack marker: {}", err),
+            DecodeError::InvalidDataRead(ref err) => write!(f, "failed to read MessagePack data: {}", err),
+            DecodeError::TypeMismatch(ref marker) => write!(f, "type mismatch, received {:?}", marker),
+            DecodeError::OutOfRange => f.write_str("out of range integral type conversion attempted"),
+            DecodeError::ArrayLengthMismatch { expected, actual } => {
+                write!(f, "expected an array of length {}, received {}", expected, actual)
+            }
+            DecodeError::NonCanonical(ref marker) => write!(f, "non-canonical {:?} encoding", marker),
+            DecodeError::BufferSizeTooSmall(len) => write!(f, "buffer is too small for length {}", len),
+            DecodeError::CapacityExceeded(len) => write!(f, "length {} exceeds the container capacity", len),
+            DecodeError::LengthLimitExceeded { len, max } => {
+                write!(f, "length {} exceeds the maximum of {}", len, max)
+            }
+            DecodeError::InvalidUtf8(ref err) => write!(f, "invalid utf-8: {}", err),
+            DecodeError::DepthLimitExceeded => f.write_str("depth limit exceeded"),
+            DecodeError::DuplicateKey => f.write_str("duplicate map key"),
+        }
+    }
+}
+
+impl<E: RmpReadErr> From<MarkerReadError<E>> for DecodeError<E> {
+    #[cold]
+    fn from(err: MarkerReadError<E>) -> DecodeError<E> {
+        DecodeError::InvalidMarkerRead(err.0)
+    }
+}
+
+impl<E: RmpReadErr> From<ValueReadError<E>> for DecodeError<E> {
+    #[cold]
+    fn from(err: ValueReadError<E>) -> DecodeError<E> {
+        match err {
+            ValueReadError::InvalidMarkerRead(err) => DecodeError::InvalidMarkerRead(err),
+            ValueReadError::InvalidDataRead(err) => DecodeError::InvalidDataRead(err),
+            ValueReadError::TypeMismatch(marker) => DecodeError::TypeMismatch(marker),
+        }
+    }
+}
+
+impl<E: RmpReadErr> From<NumValueReadError<E>> for DecodeError<E> {
+    #[cold]
+    fn from(err: NumValueReadError<E>) -> DecodeError<E> {
+        match err {
+            NumValueReadError::InvalidMarkerRead(err) => DecodeError::InvalidMarkerRead(err),
+            NumValueReadError::InvalidDataRead(err) => DecodeError::InvalidDataRead(err),
+            NumValueReadError::TypeMismatch(marker) => DecodeError::TypeMismatch(marker),
+            NumValueReadError::OutOfRange => DecodeError::OutOfRange,
+        }
+    }
+}
+
+impl<'a, E: RmpReadErr> From<DecodeStringError<'a, E>> for DecodeError<E> {
+    #[cold]
+    fn from(err: DecodeStringError<'a, E>) -> DecodeError<E> {
+        match err {
+            DecodeStringError::InvalidMarkerRead(err) => DecodeError::InvalidMarkerRead(err),
+            DecodeStringError::InvalidDataRead(err) => DecodeError::InvalidDataRead(err),
+            DecodeStringError::TypeMismatch(marker) => DecodeError::TypeMismatch(marker),
+            DecodeStringError::BufferSizeTooSmall(len) => DecodeError::BufferSizeTooSmall(len),
+            DecodeStringError::InvalidUtf8(_, err) => DecodeError::InvalidUtf8(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<DecodeError<std::io::Error>> for std::io::Error {
+    #[cold]
+    fn from(err: DecodeError<std::io::Error>) -> std::io::Error {
+        match err {
+            DecodeError::InvalidMarkerRead(err) |
+            DecodeError::InvalidDataRead(err) => err,
+            err => std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()),
+        }
+    }
+}
+
 /// Attempts to read up to 9 bytes from the given reader and to decode them as integral `T` value.
 ///
 /// This function will try to read up to 9 bytes from the reader (1 for marker and up to 8 for data)
@@ -362,13 +679,19 @@ impl<E: RmpReadErr> From<ValueReadError<E>> for NumValueReadError<E> {
 /// Unlike `read_*`, this function weakens type restrictions, allowing you to safely decode packed
 /// values even if you aren't sure about the actual integral type.
 ///
+/// Any integer marker is accepted for any `T`, and the conversion preserves the numeric value:
+/// an unsigned marker holding `5` or a signed one holding `5` both give `5`, an `U64` holding
+/// `i64::MAX` fits into `i64`, but an `U64` with the top bit set doesn't, and neither does a
+/// negative value, like a `FixNeg`, into an unsigned type. Use [read_int_lenient] to reinterpret
+/// the bits in these cases instead.
+///
 /// # Errors
 ///
 /// This function will return `NumValueReadError` on any I/O error while reading either the marker
 /// or the data.
 ///
-/// It also returns `NumValueReadError::OutOfRange` if the actual type is not an integer or it does
-/// not fit in the given numeric range.
+/// It also returns `NumValueReadError::TypeMismatch` if the actual type is not an integer and
+/// `NumValueReadError::OutOfRange` if its value does not fit in the given numeric range.
 ///
 /// # Examples
 ///
@@ -402,6 +725,83 @@ pub fn read_int<T: FromPrimitive, R: RmpRead>(rd: &mut R) -> Result<T, NumValueR
     val.ok_or(NumValueReadError::OutOfRange)
 }
 
+/// Attempts to read an integer like [read_int], but reinterprets its bits if the value doesn't
+/// fit the sign of `T`.
+///
+/// This is meant for peers that don't track signedness, for example ones that encode every
+/// integer as unsigned, so that an `i32` of `-1` arrives as an `U32` holding `0xffffffff`.
+///
+/// If the decoded value fits into `T`, it's returned as is, exactly like `read_int` does.
+/// Otherwise the payload is reinterpreted with the opposite sign at the width of its marker:
+/// `U8`, `U16`, `U32` and `U64` as `i8`, `i16`, `i32` and `i64`, and `FixNeg`, `I8`, `I16`,
+/// `I32` and `I64` as `u8`, `u16`, `u32` and `u64`. The reinterpreted value must fit into `T` as
+/// well. So the result is right when `T` has the width the peer used, but decoding `0xffffffff`
+/// into an `i64` still gives `4294967295`, because it fits.
+///
+/// # Errors
+///
+/// Returns the same errors as [read_int]. `NumValueReadError::OutOfRange` is returned only if
+/// neither the value nor its reinterpretation fit into `T`.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{read_int, read_int_lenient, NumValueReadError};
+///
+/// // U64 holding u64::MAX, written by a peer that encodes `-1i64` as unsigned.
+/// let buf = [0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+///
+/// assert_eq!(-1i64, read_int_lenient(&mut &buf[..]).unwrap());
+/// match read_int::<i64, _>(&mut &buf[..]) {
+///     Err(NumValueReadError::OutOfRange) => {}
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+///
+/// // FixNeg holding -1.
+/// assert_eq!(255u32, read_int_lenient(&mut &[0xff][..]).unwrap());
+/// ```
+pub fn read_int_lenient<T: FromPrimitive, R: RmpRead>(rd: &mut R) -> Result<T, NumValueReadError<R::Error>> {
+    let val = match read_marker(rd)? {
+        Marker::FixPos(val) => T::from_u8(val),
+        Marker::FixNeg(val) => T::from_i8(val).or_else(|| T::from_u8(val as u8)),
+        Marker::U8 => {
+            let val = rd.read_data_u8()?;
+            T::from_u8(val).or_else(|| T::from_i8(val as i8))
+        }
+        Marker::U16 => {
+            let val = rd.read_data_u16()?;
+            T::from_u16(val).or_else(|| T::from_i16(val as i16))
+        }
+        Marker::U32 => {
+            let val = rd.read_data_u32()?;
+            T::from_u32(val).or_else(|| T::from_i32(val as i32))
+        }
+        Marker::U64 => {
+            let val = rd.read_data_u64()?;
+            T::from_u64(val).or_else(|| T::from_i64(val as i64))
+        }
+        Marker::I8 => {
+            let val = rd.read_data_i8()?;
+            T::from_i8(val).or_else(|| T::from_u8(val as u8))
+        }
+        Marker::I16 => {
+            let val = rd.read_data_i16()?;
+            T::from_i16(val).or_else(|| T::from_u16(val as u16))
+        }
+        Marker::I32 => {
+            let val = rd.read_data_i32()?;
+            T::from_i32(val).or_else(|| T::from_u32(val as u32))
+        }
+        Marker::I64 => {
+            let val = rd.read_data_i64()?;
+            T::from_i64(val).or_else(|| T::from_u64(val as u64))
+        }
+        marker => return Err(NumValueReadError::TypeMismatch(marker)),
+    };
+
+    val.ok_or(NumValueReadError::OutOfRange)
+}
+
 /// Attempts to read up to 5 bytes from the given reader and to decode them as a big-endian u32
 /// array size.
 ///
@@ -442,6 +842,51 @@ pub fn read_map_len<R: RmpRead>(rd: &mut R) -> Result<u32, ValueReadError<R::Err
     marker_to_len(rd, marker)
 }
 
+/// Attempts to read a map into a `BTreeMap`, decoding each key with `read_key` and each value
+/// with `read_value`, and failing if a key appears twice.
+///
+/// Keys are compared after decoding, so `5` written as a fixint and as a `u64` are the same key.
+///
+/// # Errors
+///
+/// Returns `DecodeError::DuplicateKey` as soon as a key repeats, leaving the rest of the map
+/// unread, in addition to the errors of [read_map_len] and those returned by the closures.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{read_int, read_map_strict, DecodeError};
+///
+/// // {1: 2, 1: 3}
+/// let buf = [0x82, 0x01, 0x02, 0x01, 0x03];
+///
+/// let res = read_map_strict(&mut &buf[..], |rd| Ok(read_int::<u8, _>(rd)?), |rd| Ok(read_int::<u8, _>(rd)?));
+/// match res {
+///     Err(DecodeError::DuplicateKey) => {}
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+/// ```
+pub fn read_map_strict<R, K, V, FK, FV>(rd: &mut R, mut read_key: FK, mut read_value: FV) -> Result<BTreeMap<K, V>, DecodeError<R::Error>>
+where
+    R: RmpRead,
+    K: Ord,
+    FK: FnMut(&mut R) -> Result<K, DecodeError<R::Error>>,
+    FV: FnMut(&mut R) -> Result<V, DecodeError<R::Error>>,
+{
+    let len = read_map_len(rd)?;
+    let mut map = BTreeMap::new();
+    for _ in 0..len {
+        let key = read_key(rd)?;
+        if map.contains_key(&key) {
+            return Err(DecodeError::DuplicateKey);
+        }
+        let val = read_value(rd)?;
+        map.insert(key, val);
+    }
+
+    Ok(map)
+}
+
 pub fn marker_to_len<R: RmpRead>(rd: &mut R, marker: Marker) -> Result<u32, ValueReadError<R::Error>> {
     match marker {
         Marker::FixMap(size) => Ok(size as u32),
@@ -451,6 +896,63 @@ pub fn marker_to_len<R: RmpRead>(rd: &mut R, marker: Marker) -> Result<u32, Valu
     }
 }
 
+/// Attempts to read an array length, like [read_array_len], returning it together with the marker
+/// it was encoded with.
+///
+/// The marker tells whether the length was written as a fixarray, `Array16` or `Array32`, which
+/// is needed to check or reproduce the exact encoding.
+///
+/// # Errors
+///
+/// Returns the same errors as [read_array_len].
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::read_array_len_marker;
+/// use rmp::Marker;
+///
+/// assert_eq!((2, Marker::FixArray(2)), read_array_len_marker(&mut &[0x92][..]).unwrap());
+/// assert_eq!((2, Marker::Array16), read_array_len_marker(&mut &[0xdc, 0x00, 0x02][..]).unwrap());
+/// ```
+pub fn read_array_len_marker<R: RmpRead>(rd: &mut R) -> Result<(u32, Marker), ValueReadError<R::Error>> {
+    let marker = read_marker(rd)?;
+    let len = match marker {
+        Marker::FixArray(size) => size as u32,
+        Marker::Array16 => rd.read_data_u16()? as u32,
+        Marker::Array32 => rd.read_data_u32()?,
+        marker => return Err(ValueReadError::TypeMismatch(marker)),
+    };
+
+    Ok((len, marker))
+}
+
+/// Attempts to read a map length, like [read_map_len], returning it together with the marker it
+/// was encoded with.
+///
+/// The marker tells whether the length was written as a fixmap, `Map16` or `Map32`, which is
+/// needed to check or reproduce the exact encoding.
+///
+/// # Errors
+///
+/// Returns the same errors as [read_map_len].
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::read_map_len_marker;
+/// use rmp::Marker;
+///
+/// assert_eq!((1, Marker::FixMap(1)), read_map_len_marker(&mut &[0x81][..]).unwrap());
+/// assert_eq!((1, Marker::Map32), read_map_len_marker(&mut &[0xdf, 0x00, 0x00, 0x00, 0x01][..]).unwrap());
+/// ```
+pub fn read_map_len_marker<R: RmpRead>(rd: &mut R) -> Result<(u32, Marker), ValueReadError<R::Error>> {
+    let marker = read_marker(rd)?;
+    let len = marker_to_len(rd, marker)?;
+
+    Ok((len, marker))
+}
+
 /// Attempts to read up to 5 bytes from the given reader and to decode them as Binary array length.
 ///
 /// # Note
@@ -466,3 +968,115 @@ pub fn read_bin_len<R: RmpRead>(rd: &mut R) -> Result<u32, ValueReadError<R::Err
         marker => Err(ValueReadError::TypeMismatch(marker)),
     }
 }
+
+/// The header of a value that is followed by a length, as read by [read_len_prefix].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LenPrefix {
+    /// An array of the enclosed number of elements.
+    Array(u32),
+    /// A map of the enclosed number of key-value pairs.
+    Map(u32),
+    /// A string of the enclosed number of bytes.
+    Str(u32),
+    /// A binary of the enclosed number of bytes.
+    Bin(u32),
+    /// An extension of the enclosed type and number of data bytes. The type is already consumed.
+    Ext(i8, u32),
+}
+
+/// Attempts to read the marker and the length of an array, map, string, binary or extension in one
+/// call.
+///
+/// This replaces reading the marker and then handing it to a function like [marker_to_len], which
+/// matches it again, so generic decoders that skip or validate values need a single match. For
+/// an extension its type is read as well, so the data follows.
+///
+/// # Errors
+///
+/// Returns `ValueReadError::TypeMismatch` if the marker belongs to a value without a length, such
+/// as an integer or nil. Only the marker is consumed then.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{read_len_prefix, LenPrefix};
+///
+/// // ["hi", ext(5, [0xaa])]
+/// let buf = [0x92, 0xa2, 0x68, 0x69, 0xd4, 0x05, 0xaa];
+/// let mut rd = &buf[..];
+///
+/// assert_eq!(LenPrefix::Array(2), read_len_prefix(&mut rd).unwrap());
+/// assert_eq!(LenPrefix::Str(2), read_len_prefix(&mut rd).unwrap());
+/// rd = &rd[2..];
+/// assert_eq!(LenPrefix::Ext(5, 1), read_len_prefix(&mut rd).unwrap());
+/// assert_eq!([0xaa], rd);
+/// ```
+pub fn read_len_prefix<R: RmpRead>(rd: &mut R) -> Result<LenPrefix, ValueReadError<R::Error>> {
+    let prefix = match read_marker(rd)? {
+        Marker::FixArray(len) => LenPrefix::Array(len as u32),
+        Marker::Array16 => LenPrefix::Array(rd.read_data_u16()? as u32),
+        Marker::Array32 => LenPrefix::Array(rd.read_data_u32()?),
+        Marker::FixMap(len) => LenPrefix::Map(len as u32),
+        Marker::Map16 => LenPrefix::Map(rd.read_data_u16()? as u32),
+        Marker::Map32 => LenPrefix::Map(rd.read_data_u32()?),
+        Marker::FixStr(len) => LenPrefix::Str(len as u32),
+        Marker::Str8 => LenPrefix::Str(rd.read_data_u8()? as u32),
+        Marker::Str16 => LenPrefix::Str(rd.read_data_u16()? as u32),
+        Marker::Str32 => LenPrefix::Str(rd.read_data_u32()?),
+        Marker::Bin8 => LenPrefix::Bin(rd.read_data_u8()? as u32),
+        Marker::Bin16 => LenPrefix::Bin(rd.read_data_u16()? as u32),
+        Marker::Bin32 => LenPrefix::Bin(rd.read_data_u32()?),
+        Marker::FixExt1 => LenPrefix::Ext(rd.read_data_i8()?, 1),
+        Marker::FixExt2 => LenPrefix::Ext(rd.read_data_i8()?, 2),
+        Marker::FixExt4 => LenPrefix::Ext(rd.read_data_i8()?, 4),
+        Marker::FixExt8 => LenPrefix::Ext(rd.read_data_i8()?, 8),
+        Marker::FixExt16 => LenPrefix::Ext(rd.read_data_i8()?, 16),
+        Marker::Ext8 => {
+            let len = rd.read_data_u8()? as u32;
+            LenPrefix::Ext(rd.read_data_i8()?, len)
+        }
+        Marker::Ext16 => {
+            let len = rd.read_data_u16()? as u32;
+            LenPrefix::Ext(rd.read_data_i8()?, len)
+        }
+        Marker::Ext32 => {
+            let len = rd.read_data_u32()?;
+            LenPrefix::Ext(rd.read_data_i8()?, len)
+        }
+        marker => return Err(ValueReadError::TypeMismatch(marker)),
+    };
+
+    Ok(prefix)
+}
+
+/// Attempts to read a binary value, appending its data to `buf` and returning the length.
+///
+/// Slices and [Bytes] copy the data straight into the spare capacity of `buf`, without
+/// zero-initializing it first like `vec![0u8; len]` followed by [RmpRead::read_exact_buf] would,
+/// which matters for large blobs. Other [std::io::Read] implementations go through
+/// `read_to_end`, which zero-initializes the spare capacity before reading into it unless the
+/// reader implements the unstable `read_buf`, so for them this only saves the separate buffer.
+/// The capacity reserved ahead of the data actually read is at most as much as was already read,
+/// or 64 KiB, so a bogus length can't exhaust memory.
+///
+/// # Errors
+///
+/// Returns the errors of [read_bin_len], and `ValueReadError::InvalidDataRead` if the data is
+/// shorter than declared. On error `buf` is left as it was.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::read_bin_into_uninit;
+///
+/// let buf = [0xc4, 0x03, 0x01, 0x02, 0x03];
+/// let mut out = Vec::with_capacity(16);
+///
+/// assert_eq!(3, read_bin_into_uninit(&mut &buf[..], &mut out).unwrap());
+/// assert_eq!([1, 2, 3], out[..]);
+/// ```
+pub fn read_bin_into_uninit<R: RmpRead>(rd: &mut R, buf: &mut Vec<u8>) -> Result<u32, ValueReadError<R::Error>> {
+    let len = read_bin_len(rd)?;
+    rd.read_exact_into_vec(buf, len as usize).map_err(ValueReadError::InvalidDataRead)?;
+    Ok(len)
+}
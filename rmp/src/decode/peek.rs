@@ -0,0 +1,189 @@
+//! Implementation of the [PeekReader] type
+
+use std::cmp::min;
+use std::io::{self, BufRead, ErrorKind, Read};
+
+use crate::Marker;
+
+/// The longest fixed-size header: a marker followed by 8 bytes of data.
+const MAX_HEADER_LEN: usize = 9;
+
+/// Returns the length of the fixed-size part of a value starting with the given marker.
+///
+/// That's the marker itself and the bytes following it that don't depend on a length: the data of
+/// integers and floats, the length of strings, binaries, arrays and maps, and the length and the
+/// type of extensions.
+fn header_len(marker: Marker) -> usize {
+    let extra = match marker {
+        Marker::U8 | Marker::I8 | Marker::Str8 | Marker::Bin8 => 1,
+        Marker::U16 | Marker::I16 | Marker::Str16 | Marker::Bin16 | Marker::Array16 | Marker::Map16 => 2,
+        Marker::U32 | Marker::I32 | Marker::F32 | Marker::Str32 | Marker::Bin32 | Marker::Array32 | Marker::Map32 => 4,
+        Marker::U64 | Marker::I64 | Marker::F64 => 8,
+        Marker::FixExt1 | Marker::FixExt2 | Marker::FixExt4 | Marker::FixExt8 | Marker::FixExt16 => 1,
+        Marker::Ext8 => 2,
+        Marker::Ext16 => 3,
+        Marker::Ext32 => 5,
+        _ => 0,
+    };
+
+    1 + extra
+}
+
+/// A buffered reader adapter that can look at the whole header of the next value without
+/// consuming it.
+///
+/// [PeekReader::peek_header] returns the marker together with the bytes that follow it up to the
+/// variable-length part of the value, at most 9 bytes. Validators and resynchronization logic can
+/// inspect it and then [consume](BufRead::consume) as much as they decide to, or read on with any
+/// decode function, since this is a [Read] and a [BufRead] itself.
+///
+/// The header is returned straight from the buffer of the underlying reader if it's there in one
+/// piece. Otherwise, near the end of that buffer, its bytes are moved into a small buffer of this
+/// adapter, so peeking works with any [BufRead] no matter its capacity.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::BufRead;
+///
+/// use rmp::decode::{self, PeekReader};
+///
+/// // 300, "hi"
+/// let buf = [0xcd, 0x01, 0x2c, 0xa2, 0x68, 0x69];
+/// let mut rd = PeekReader::new(&buf[..]);
+///
+/// assert_eq!([0xcd, 0x01, 0x2c], rd.peek_header().unwrap());
+/// assert_eq!(300, decode::read_int::<u16, _>(&mut rd).unwrap());
+///
+/// // Skip the string header and look at the data.
+/// let len = rd.peek_header().unwrap().len();
+/// rd.consume(len);
+/// assert_eq!(b"hi", rd.fill_buf().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct PeekReader<R> {
+    inner: R,
+    buf: [u8; MAX_HEADER_LEN],
+    pos: usize,
+    end: usize,
+}
+
+impl<R: BufRead> PeekReader<R> {
+    /// Wrap the specified reader.
+    #[inline]
+    pub fn new(inner: R) -> Self {
+        PeekReader { inner, buf: [0; MAX_HEADER_LEN], pos: 0, end: 0 }
+    }
+
+    /// Returns the header of the next value without consuming it.
+    ///
+    /// The header is the marker followed by the data of an integer or a float, or by the length
+    /// of a string, binary, array or map, or by the length and the type of an extension. An empty
+    /// slice means that the input is exhausted at a value boundary.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `UnexpectedEof` I/O error if the input ends in the middle of the header, and
+    /// the I/O errors of the underlying reader other than `Interrupted`, which is retried. The
+    /// bytes of the header read so far stay buffered.
+    pub fn peek_header(&mut self) -> io::Result<&[u8]> {
+        let len = loop {
+            match self.fill_buf() {
+                Ok([]) => return Ok(&[]),
+                Ok(buf) => {
+                    let len = header_len(Marker::from_u8(buf[0]));
+                    if buf.len() < len {
+                        self.buffer_header(len)?;
+                    }
+                    break len;
+                }
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        };
+
+        let buf = self.fill_buf()?;
+        Ok(&buf[..len])
+    }
+
+    /// Moves bytes from the underlying reader into the own buffer until it holds `len` bytes.
+    fn buffer_header(&mut self, len: usize) -> io::Result<()> {
+        self.buf.copy_within(self.pos..self.end, 0);
+        self.end -= self.pos;
+        self.pos = 0;
+
+        while self.end < len {
+            let src = match self.inner.fill_buf() {
+                Ok([]) => return Err(io::Error::new(ErrorKind::UnexpectedEof, "input ended in the middle of a header")),
+                Ok(src) => src,
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+
+            let n = min(src.len(), len - self.end);
+            self.buf[self.end..self.end + n].copy_from_slice(&src[..n]);
+            self.end += n;
+            self.inner.consume(n);
+        }
+
+        Ok(())
+    }
+}
+
+impl<R> PeekReader<R> {
+    /// Get a reference to the underlying reader.
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying reader.
+    ///
+    /// Reading from it directly skips the bytes that this adapter has buffered.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwrap this adapter, returning the underlying reader.
+    ///
+    /// The bytes that were peeked into the own buffer of this adapter but not consumed are lost.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead> Read for PeekReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.end {
+            return self.inner.read(buf);
+        }
+
+        let n = min(buf.len(), self.end - self.pos);
+        buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for PeekReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos < self.end {
+            Ok(&self.buf[self.pos..self.end])
+        } else {
+            self.inner.fill_buf()
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let buffered = self.end - self.pos;
+        if amt < buffered {
+            self.pos += amt;
+        } else {
+            self.pos = 0;
+            self.end = 0;
+            self.inner.consume(amt - buffered);
+        }
+    }
+}
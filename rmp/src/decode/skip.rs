@@ -0,0 +1,114 @@
+//! Skipping whole values without decoding them.
+
+use alloc::vec::Vec;
+
+use super::{read_marker, DecodeError, RmpRead};
+use crate::Marker;
+
+/// Attempts to skip the next value, including everything nested in it, without decoding or
+/// validating it.
+///
+/// See [skip_value_data] for the details.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::decode::{read_int, skip_value};
+///
+/// // ["skipped", {1: [2, 3]}], 42
+/// let buf = [0x92, 0xa7, 0x73, 0x6b, 0x69, 0x70, 0x70, 0x65, 0x64, 0x81, 0x01, 0x92, 0x02, 0x03, 0x2a];
+/// let mut rd = &buf[..];
+///
+/// skip_value(&mut rd, usize::MAX).unwrap();
+/// assert_eq!(42, read_int::<u8, _>(&mut rd).unwrap());
+/// ```
+pub fn skip_value<R: RmpRead>(rd: &mut R, max_depth: usize) -> Result<(), DecodeError<R::Error>> {
+    let marker = read_marker(rd)?;
+    skip_value_data(rd, marker, max_depth)
+}
+
+/// Attempts to skip the rest of a value whose marker was already read, including everything
+/// nested in it.
+///
+/// Nested arrays and maps are tracked with a counter per level rather than by recursion, so
+/// deeply nested input can't overflow the stack. Strings are not checked to be valid UTF-8.
+///
+/// # Errors
+///
+/// Returns `DecodeError::DepthLimitExceeded` if arrays and maps are nested deeper than `max_depth`,
+/// so `0` allows only scalars, and `DecodeError::TypeMismatch` for the reserved `0xc1` marker,
+/// whose length is unknown. The reader is left in the middle of the value then.
+pub fn skip_value_data<R: RmpRead>(rd: &mut R, marker: Marker, max_depth: usize) -> Result<(), DecodeError<R::Error>> {
+    // The number of values still to skip in each array or map being skipped, innermost last.
+    let mut levels: Vec<u64> = Vec::new();
+    let mut marker = marker;
+
+    loop {
+        let count = match marker {
+            Marker::FixArray(len) => Some(u64::from(len)),
+            Marker::Array16 => Some(u64::from(rd.read_data_u16()?)),
+            Marker::Array32 => Some(u64::from(rd.read_data_u32()?)),
+            Marker::FixMap(len) => Some(u64::from(len) * 2),
+            Marker::Map16 => Some(u64::from(rd.read_data_u16()?) * 2),
+            Marker::Map32 => Some(u64::from(rd.read_data_u32()?) * 2),
+            marker => {
+                let len = data_len(rd, marker)?;
+                rd.skip_exact(len).map_err(DecodeError::InvalidDataRead)?;
+                None
+            }
+        };
+
+        if let Some(count) = count {
+            if levels.len() >= max_depth {
+                return Err(DecodeError::DepthLimitExceeded);
+            }
+            levels.push(count);
+        }
+
+        loop {
+            match levels.last_mut() {
+                None => return Ok(()),
+                Some(0) => {
+                    levels.pop();
+                }
+                Some(count) => {
+                    *count -= 1;
+                    break;
+                }
+            }
+        }
+
+        marker = read_marker(rd)?;
+    }
+}
+
+/// Reads the length of the data that follows a scalar, string, binary or extension marker.
+fn data_len<R: RmpRead>(rd: &mut R, marker: Marker) -> Result<u64, DecodeError<R::Error>> {
+    let len = match marker {
+        Marker::Null |
+        Marker::True |
+        Marker::False |
+        Marker::FixPos(..) |
+        Marker::FixNeg(..) => 0,
+        Marker::U8 | Marker::I8 => 1,
+        Marker::U16 | Marker::I16 => 2,
+        Marker::U32 | Marker::I32 | Marker::F32 => 4,
+        Marker::U64 | Marker::I64 | Marker::F64 => 8,
+        Marker::FixStr(len) => u64::from(len),
+        Marker::Str8 | Marker::Bin8 => u64::from(rd.read_data_u8()?),
+        Marker::Str16 | Marker::Bin16 => u64::from(rd.read_data_u16()?),
+        Marker::Str32 | Marker::Bin32 => u64::from(rd.read_data_u32()?),
+        // The type byte precedes the data.
+        Marker::FixExt1 => 2,
+        Marker::FixExt2 => 3,
+        Marker::FixExt4 => 5,
+        Marker::FixExt8 => 9,
+        Marker::FixExt16 => 17,
+        Marker::Ext8 => u64::from(rd.read_data_u8()?) + 1,
+        Marker::Ext16 => u64::from(rd.read_data_u16()?) + 1,
+        Marker::Ext32 => u64::from(rd.read_data_u32()?) + 1,
+        marker => return Err(DecodeError::TypeMismatch(marker)),
+    };
+
+    Ok(len)
+}
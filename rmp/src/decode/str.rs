@@ -14,9 +14,68 @@ pub enum DecodeStringError<'a, E: RmpReadErr = super::Error> {
     TypeMismatch(Marker),
     /// The given buffer is not large enough to accumulate the specified amount of bytes.
     BufferSizeTooSmall(u32),
+    /// The string data is not valid UTF-8.
+    ///
+    /// The data is borrowed from the buffer it was read into, or from the input itself when
+    /// decoding from [Bytes](super::Bytes), so the error path never allocates. The
+    /// [Utf8Error] locates the problem within that data, see [DecodeStringError::utf8_error].
     InvalidUtf8(&'a [u8], Utf8Error),
 }
 
+impl<'a, E: RmpReadErr> DecodeStringError<'a, E> {
+    /// Returns the data that failed UTF-8 validation, or `None` for any other error.
+    ///
+    /// This allows to recover near-UTF-8 strings, for example with `String::from_utf8_lossy`,
+    /// without copying them first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmp::decode::Bytes;
+    ///
+    /// let buf = [0xa2, 0xc3, 0x28];
+    /// let mut rd = Bytes::new(&buf);
+    ///
+    /// let err = rd.read_str_slice().unwrap_err();
+    /// assert_eq!(Some(&buf[1..]), err.as_bytes());
+    /// ```
+    #[inline]
+    pub fn as_bytes(&self) -> Option<&'a [u8]> {
+        match *self {
+            DecodeStringError::InvalidUtf8(data, _) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Returns the UTF-8 validation error, or `None` for any other error.
+    ///
+    /// Its [valid_up_to](Utf8Error::valid_up_to) is an offset into [Self::as_bytes], and its
+    /// [error_len](Utf8Error::error_len) tells an invalid sequence, which is `Some` length, from
+    /// data that is cut off in the middle of a character, which is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmp::decode::Bytes;
+    ///
+    /// // "ab" followed by the first 2 bytes of a 3-byte character.
+    /// let buf = [0xa4, 0x61, 0x62, 0xe2, 0x82];
+    /// let mut rd = Bytes::new(&buf);
+    ///
+    /// let err = rd.read_str_slice().unwrap_err();
+    /// let utf8_err = err.utf8_error().unwrap();
+    /// assert_eq!(2, utf8_err.valid_up_to());
+    /// assert_eq!(None, utf8_err.error_len());
+    /// ```
+    #[inline]
+    pub fn utf8_error(&self) -> Option<Utf8Error> {
+        match *self {
+            DecodeStringError::InvalidUtf8(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl<'a, E: RmpReadErr> error::Error for DecodeStringError<'a, E> {
     #[cold]
@@ -34,7 +93,10 @@ impl<'a, E: RmpReadErr> error::Error for DecodeStringError<'a, E> {
 impl<'a, E: RmpReadErr> Display for DecodeStringError<'a, E> {
     #[cold]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        f.write_str("error while decoding string")
+        match *self {
+            DecodeStringError::InvalidUtf8(_, ref err) => write!(f, "error while decoding string: {}", err),
+            _ => f.write_str("error while decoding string"),
+        }
     }
 }
 
@@ -184,7 +246,7 @@ pub fn read_str_from_slice<T: ?Sized + AsRef<[u8]>>(
         let (head, tail) = buf.split_at(nread + ulen);
         match from_utf8(&head[nread..]) {
             Ok(val) => Ok((val, tail)),
-            Err(err) => Err(DecodeStringError::InvalidUtf8(buf, err)),
+            Err(err) => Err(DecodeStringError::InvalidUtf8(&head[nread..], err)),
         }
     } else {
         Err(DecodeStringError::BufferSizeTooSmall(len))
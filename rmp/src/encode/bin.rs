@@ -1,31 +1,41 @@
+use super::header::Header;
 use super::{RmpWrite};
-use crate::encode::{write_marker, ValueWriteError};
+use crate::encode::ValueWriteError;
 use crate::Marker;
 
 /// Encodes and attempts to write the most efficient binary array length implementation to the given
 /// write, returning the marker used.
 ///
 /// This function is useful when you want to get full control for writing the data itself, for
-/// example, when using non-blocking socket.
+/// example, when using non-blocking socket, or to stream a large payload from a file or another
+/// reader without assembling it in memory first.
+///
+/// Only the marker and the length are written. The caller must then write exactly `len` bytes of
+/// data to the same writer, otherwise the stream is corrupted: every value after it will be
+/// decoded from the wrong offset.
 ///
 /// # Errors
 ///
 /// This function will return `ValueWriteError` on any I/O error occurred while writing either the
 /// marker or the data.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::encode::write_bin_len;
+///
+/// let chunks: [&[u8]; 2] = [&[0xca, 0xfe], &[0xba, 0xbe]];
+///
+/// let mut buf = Vec::new();
+/// write_bin_len(&mut buf, 4).unwrap();
+/// for chunk in &chunks {
+///     buf.extend_from_slice(chunk);
+/// }
+///
+/// assert_eq!([0xc4, 0x04, 0xca, 0xfe, 0xba, 0xbe], buf[..]);
+/// ```
 pub fn write_bin_len<W: RmpWrite>(wr: &mut W, len: u32) -> Result<Marker, ValueWriteError<W::Error>> {
-    if len < 256 {
-        write_marker(&mut *wr, Marker::Bin8)?;
-        wr.write_data_u8(len as u8)?;
-        Ok(Marker::Bin8)
-    } else if len <= u16::MAX as u32 {
-        write_marker(&mut *wr, Marker::Bin16)?;
-        wr.write_data_u16(len as u16)?;
-        Ok(Marker::Bin16)
-    } else {
-        write_marker(&mut *wr, Marker::Bin32)?;
-        wr.write_data_u32(len)?;
-        Ok(Marker::Bin32)
-    }
+    Header::bin_len(len).write(wr)
 }
 
 /// Encodes and attempts to write the most efficient binary implementation to the given `Write`.
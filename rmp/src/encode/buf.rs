@@ -0,0 +1,87 @@
+//! Integration with the [bytes](::bytes) crate.
+
+use ::bytes::BufMut;
+
+#[cfg(not(feature = "std"))]
+use super::buffer::FixedBufCapacityOverflow;
+#[cfg(not(feature = "std"))]
+use super::RmpWrite;
+
+/// A writer adapter over any [BufMut], such as `bytes::BytesMut`.
+///
+/// This allows encoding straight into a network buffer. A growable buffer like `BytesMut` never
+/// runs out of capacity, while writing past the end of a fixed-capacity one is an error.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::encode::{self, BufMutSink};
+///
+/// let mut wr = BufMutSink::new(bytes::BytesMut::new());
+/// encode::write_u16(&mut wr, 300).unwrap();
+///
+/// assert_eq!(&[0xcd, 0x01, 0x2c], &wr.into_inner()[..]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BufMutSink<B> {
+    inner: B,
+}
+
+impl<B> BufMutSink<B> {
+    /// Wrap the specified buffer.
+    #[inline]
+    pub fn new(inner: B) -> Self {
+        BufMutSink { inner }
+    }
+
+    /// Get a reference to the underlying buffer.
+    #[inline]
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying buffer.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Unwrap this adapter, returning the underlying buffer.
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<B: BufMut> std::io::Write for BufMutSink<B> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(self.inner.remaining_mut());
+        self.inner.put_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Fallback implementation for `#[no_std]`
+///
+/// With `feature = "std"` this is covered by the blanket implementation for `std::io::Write`.
+#[cfg(not(feature = "std"))]
+impl<B: BufMut> RmpWrite for BufMutSink<B> {
+    type Error = FixedBufCapacityOverflow;
+
+    #[inline]
+    fn write_bytes(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        if buf.len() <= self.inner.remaining_mut() {
+            self.inner.put_slice(buf);
+            Ok(())
+        } else {
+            Err(FixedBufCapacityOverflow { _priv: () })
+        }
+    }
+}
@@ -17,7 +17,7 @@ use alloc::vec::Vec;
 #[cfg(not(feature = "std"))]
 #[doc(hidden)]
 pub struct FixedBufCapacityOverflow {
-    _priv: ()
+    pub(super) _priv: ()
 }
 
 /// An error returned from writing to `&mut [u8]`
@@ -0,0 +1,93 @@
+//! Implementation of the [CountWrite] type
+
+#[cfg(not(feature = "std"))]
+use super::RmpWrite;
+
+/// A writer adapter that counts the number of bytes written through it.
+///
+/// All writes are forwarded to the wrapped writer unchanged, so every `write_*` function in this
+/// module works through it. After encoding, [Self::count] reports the exact size of the emitted
+/// data, which is useful for protocols that need precise framing without buffering the message.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::encode::{self, CountWrite};
+///
+/// let mut wr = CountWrite::new(Vec::new());
+///
+/// encode::write_str(&mut wr, "le message").unwrap();
+/// encode::write_uint(&mut wr, 300).unwrap();
+///
+/// assert_eq!(14, wr.count());
+/// assert_eq!(14, wr.into_inner().len());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CountWrite<W> {
+    inner: W,
+    count: u64,
+}
+
+impl<W> CountWrite<W> {
+    /// Wrap the specified writer, starting with a count of zero.
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        CountWrite { inner, count: 0 }
+    }
+
+    /// Return the total number of bytes successfully written so far.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Get a reference to the underlying writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer are not counted.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwrap this adapter, returning the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> std::io::Write for CountWrite<W> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.count += n as u64;
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Fallback implementation for `#[no_std]`
+///
+/// With `feature = "std"` this is covered by the blanket implementation for `std::io::Write`.
+#[cfg(not(feature = "std"))]
+impl<W: RmpWrite> RmpWrite for CountWrite<W> {
+    type Error = W::Error;
+
+    #[inline]
+    fn write_bytes(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.inner.write_bytes(buf)?;
+        self.count += buf.len() as u64;
+        Ok(())
+    }
+}
@@ -6,6 +6,9 @@ use crate::Marker;
 ///
 /// The first byte becomes the `f32` marker and the others will represent the data itself.
 ///
+/// The data is the exact IEEE 754 bit pattern of `val`, as returned by `f32::to_bits`, so NaN
+/// payloads, signaling NaNs and negative zero are preserved.
+///
 /// # Errors
 ///
 /// This function will return `ValueWriteError` on any I/O error occurred while writing either the
@@ -20,6 +23,9 @@ pub fn write_f32<W: RmpWrite>(wr: &mut W, val: f32) -> Result<(), ValueWriteErro
 ///
 /// The first byte becomes the `f64` marker and the others will represent the data itself.
 ///
+/// The data is the exact IEEE 754 bit pattern of `val`, as returned by `f64::to_bits`, so NaN
+/// payloads, signaling NaNs and negative zero are preserved.
+///
 /// # Errors
 ///
 /// This function will return `ValueWriteError` on any I/O error occurred while writing either the
@@ -0,0 +1,148 @@
+//! Marker selection shared by the blocking and the async encoders.
+
+use super::{write_marker, RmpWrite, ValueWriteError};
+use crate::Marker;
+
+/// A marker followed by its length or integer payload, in the most compact representation.
+///
+/// Only the encoding is computed here, so that every encoder writes exactly the same bytes no
+/// matter how it does the I/O.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Header {
+    marker: Marker,
+    buf: [u8; 9],
+    len: u8,
+}
+
+impl Header {
+    #[inline]
+    fn new(marker: Marker, data: &[u8]) -> Self {
+        let mut buf = [0u8; 9];
+        buf[0] = marker.to_u8();
+        buf[1..=data.len()].copy_from_slice(data);
+        Header { marker, buf, len: data.len() as u8 + 1 }
+    }
+
+    /// The header of an unsigned integer.
+    #[inline]
+    pub fn uint(val: u64) -> Self {
+        if val < 128 {
+            Header::new(Marker::FixPos(val as u8), &[])
+        } else if val < 256 {
+            Header::new(Marker::U8, &[val as u8])
+        } else if val < 65536 {
+            Header::new(Marker::U16, &(val as u16).to_be_bytes())
+        } else if val < 4294967296 {
+            Header::new(Marker::U32, &(val as u32).to_be_bytes())
+        } else {
+            Header::new(Marker::U64, &val.to_be_bytes())
+        }
+    }
+
+    /// The header of a signed integer, using the unsigned encoding for non-negative values.
+    #[inline]
+    pub fn sint(val: i64) -> Self {
+        match val {
+            val if (-32..0).contains(&val) => Header::new(Marker::FixNeg(val as i8), &[]),
+            val if (-128..-32).contains(&val) => Header::new(Marker::I8, &(val as i8).to_be_bytes()),
+            val if (-32768..-128).contains(&val) => Header::new(Marker::I16, &(val as i16).to_be_bytes()),
+            val if (-2147483648..-32768).contains(&val) => Header::new(Marker::I32, &(val as i32).to_be_bytes()),
+            val if val < -2147483648 => Header::new(Marker::I64, &val.to_be_bytes()),
+            val => Header::uint(val as u64),
+        }
+    }
+
+    /// The header of a string of `len` bytes.
+    #[inline]
+    pub fn str_len(len: u32) -> Self {
+        if len < 32 {
+            Header::new(Marker::FixStr(len as u8), &[])
+        } else if len < 256 {
+            Header::new(Marker::Str8, &[len as u8])
+        } else if len <= u16::MAX as u32 {
+            Header::new(Marker::Str16, &(len as u16).to_be_bytes())
+        } else {
+            Header::new(Marker::Str32, &len.to_be_bytes())
+        }
+    }
+
+    /// The header of a binary of `len` bytes.
+    #[inline]
+    pub fn bin_len(len: u32) -> Self {
+        if len < 256 {
+            Header::new(Marker::Bin8, &[len as u8])
+        } else if len <= u16::MAX as u32 {
+            Header::new(Marker::Bin16, &(len as u16).to_be_bytes())
+        } else {
+            Header::new(Marker::Bin32, &len.to_be_bytes())
+        }
+    }
+
+    /// The header of an array of `len` elements.
+    #[inline]
+    pub fn array_len(len: u32) -> Self {
+        if len < 16 {
+            Header::new(Marker::FixArray(len as u8), &[])
+        } else if len <= u16::MAX as u32 {
+            Header::new(Marker::Array16, &(len as u16).to_be_bytes())
+        } else {
+            Header::new(Marker::Array32, &len.to_be_bytes())
+        }
+    }
+
+    /// The header of a map of `len` key-value pairs.
+    #[inline]
+    pub fn map_len(len: u32) -> Self {
+        if len < 16 {
+            Header::new(Marker::FixMap(len as u8), &[])
+        } else if len <= u16::MAX as u32 {
+            Header::new(Marker::Map16, &(len as u16).to_be_bytes())
+        } else {
+            Header::new(Marker::Map32, &len.to_be_bytes())
+        }
+    }
+
+    /// The header of an extension of `len` bytes, including its type.
+    #[inline]
+    pub fn ext_meta(len: u32, ty: i8) -> Self {
+        let ty = ty as u8;
+        match len {
+            1 => Header::new(Marker::FixExt1, &[ty]),
+            2 => Header::new(Marker::FixExt2, &[ty]),
+            4 => Header::new(Marker::FixExt4, &[ty]),
+            8 => Header::new(Marker::FixExt8, &[ty]),
+            16 => Header::new(Marker::FixExt16, &[ty]),
+            len if len < 256 => Header::new(Marker::Ext8, &[len as u8, ty]),
+            len if len < 65536 => {
+                let [a, b] = (len as u16).to_be_bytes();
+                Header::new(Marker::Ext16, &[a, b, ty])
+            }
+            len => {
+                let [a, b, c, d] = len.to_be_bytes();
+                Header::new(Marker::Ext32, &[a, b, c, d, ty])
+            }
+        }
+    }
+
+    #[inline]
+    pub fn marker(&self) -> Marker {
+        self.marker
+    }
+
+    /// The encoded marker followed by the payload.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len as usize]
+    }
+
+    /// Writes the marker and then the payload, reporting errors of each write separately.
+    #[inline]
+    pub fn write<W: RmpWrite>(&self, wr: &mut W) -> Result<Marker, ValueWriteError<W::Error>> {
+        write_marker(wr, self.marker())?;
+        let data = &self.as_bytes()[1..];
+        if !data.is_empty() {
+            wr.write_bytes(data).map_err(ValueWriteError::InvalidDataWrite)?;
+        }
+        Ok(self.marker())
+    }
+}
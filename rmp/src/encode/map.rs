@@ -1 +1,86 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
 
+use super::{write_map_len, RmpWrite, ValueWriteError};
+
+/// Encodes and attempts to write a map, taking its length from the given iterator of key-value
+/// pairs.
+///
+/// The map length is written first, followed by every pair encoded with the provided `fk` and
+/// `fv` closures for keys and values respectively. Because the length comes from the iterator
+/// itself, it can't get out of sync with the number of encoded pairs.
+///
+/// # Errors
+///
+/// This function will return the error produced by `fk` or `fv`, or `ValueWriteError` converted
+/// into `E` on any I/O error occurred while writing the map length.
+///
+/// # Panics
+///
+/// Panics if the iterator reports more than `u32::MAX` pairs, which can't be represented in
+/// MessagePack.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use rmp::encode;
+///
+/// let mut map = BTreeMap::new();
+/// map.insert("a", 1u8);
+/// map.insert("b", 2u8);
+///
+/// let mut buf = Vec::new();
+///
+/// encode::write_map_iter(&mut buf, &map,
+///     |wr, k| encode::write_str(wr, k),
+///     |wr, &v| encode::write_uint(wr, v.into()).map(|_| ()),
+/// ).unwrap();
+///
+/// assert_eq!([0x82, 0xa1, 0x61, 0x01, 0xa1, 0x62, 0x02], buf[..]);
+/// ```
+pub fn write_map_iter<W, I, K, V, FK, FV, E>(wr: &mut W, iter: I, mut fk: FK, mut fv: FV) -> Result<(), E>
+where
+    W: RmpWrite,
+    I: IntoIterator<Item = (K, V)>,
+    I::IntoIter: ExactSizeIterator,
+    FK: FnMut(&mut W, K) -> Result<(), E>,
+    FV: FnMut(&mut W, V) -> Result<(), E>,
+    E: From<ValueWriteError<W::Error>>,
+{
+    let iter = iter.into_iter();
+    let len = u32::try_from(iter.len()).expect("map is too long for MessagePack");
+
+    write_map_len(wr, len)?;
+    for (key, val) in iter {
+        fk(wr, key)?;
+        fv(wr, val)?;
+    }
+
+    Ok(())
+}
+
+/// Encodes and attempts to write a map from an iterator of key-value pairs of unknown length.
+///
+/// Unlike [write_map_iter], this accepts any iterator, collecting its pairs into a temporary
+/// buffer first to find out the map length.
+///
+/// # Errors
+///
+/// This function will return the error produced by `fk` or `fv`, or `ValueWriteError` converted
+/// into `E` on any I/O error occurred while writing the map length.
+///
+/// # Panics
+///
+/// Panics if the iterator yields more than `u32::MAX` pairs.
+pub fn write_map_iter_buffered<W, I, K, V, FK, FV, E>(wr: &mut W, iter: I, fk: FK, fv: FV) -> Result<(), E>
+where
+    W: RmpWrite,
+    I: IntoIterator<Item = (K, V)>,
+    FK: FnMut(&mut W, K) -> Result<(), E>,
+    FV: FnMut(&mut W, V) -> Result<(), E>,
+    E: From<ValueWriteError<W::Error>>,
+{
+    let pairs: Vec<(K, V)> = iter.into_iter().collect();
+    write_map_iter(wr, pairs, fk, fv)
+}
@@ -1,8 +1,12 @@
 //! Provides various functions and structs for MessagePack encoding.
 
 mod bin;
+#[cfg(feature = "bytes")]
+mod buf;
+mod count;
 mod dec;
 mod ext;
+pub(crate) mod header;
 mod map;
 mod sint;
 mod str;
@@ -10,15 +14,21 @@ mod uint;
 mod vec;
 
 pub use self::bin::{write_bin, write_bin_len};
+#[cfg(feature = "bytes")]
+pub use self::buf::BufMutSink;
+pub use self::count::CountWrite;
 pub use self::dec::{write_f32, write_f64};
+pub use self::map::{write_map_iter, write_map_iter_buffered};
 pub use self::sint::{write_i16, write_i32, write_i64, write_i8, write_nfix, write_sint};
 pub use self::str::{write_str, write_str_len};
 pub use self::uint::{write_pfix, write_u16, write_u32, write_u64, write_u8, write_uint};
+pub use self::vec::{write_array_iter, write_array_iter_buffered};
 
 #[cfg(feature = "std")]
 use std::error;
 use core::fmt::{self, Display, Debug, Formatter};
 
+use self::header::Header;
 use crate::Marker;
 
 pub mod buffer;
@@ -113,6 +123,10 @@ mod sealed{
     #[cfg(not(feature = "std"))]
     impl Sealed for alloc::vec::Vec<u8> {}
     impl Sealed for super::ByteBuf {}
+    #[cfg(not(feature = "std"))]
+    impl<W: super::RmpWrite> Sealed for super::CountWrite<W> {}
+    #[cfg(all(feature = "bytes", not(feature = "std")))]
+    impl<B: ::bytes::BufMut> Sealed for super::BufMutSink<B> {}
 }
 
 
@@ -259,20 +273,7 @@ impl<E: RmpWriteErr> Display for ValueWriteError<E> {
 /// This function will return `ValueWriteError` on any I/O error occurred while writing either the
 /// marker or the data.
 pub fn write_array_len<W: RmpWrite>(wr: &mut W, len: u32) -> Result<Marker, ValueWriteError<W::Error>> {
-    let marker = if len < 16 {
-        write_marker(wr, Marker::FixArray(len as u8))?;
-        Marker::FixArray(len as u8)
-    } else if len <= u16::MAX as u32 {
-        write_marker(wr, Marker::Array16)?;
-        wr.write_data_u16(len as u16)?;
-        Marker::Array16
-    } else {
-        write_marker(wr, Marker::Array32)?;
-        wr.write_data_u32(len)?;
-        Marker::Array32
-    };
-
-    Ok(marker)
+    Header::array_len(len).write(wr)
 }
 
 /// Encodes and attempts to write the most efficient map length implementation to the given write,
@@ -283,20 +284,7 @@ pub fn write_array_len<W: RmpWrite>(wr: &mut W, len: u32) -> Result<Marker, Valu
 /// This function will return `ValueWriteError` on any I/O error occurred while writing either the
 /// marker or the data.
 pub fn write_map_len<W: RmpWrite>(wr: &mut W, len: u32) -> Result<Marker, ValueWriteError<W::Error>> {
-    let marker = if len < 16 {
-        write_marker(wr, Marker::FixMap(len as u8))?;
-        Marker::FixMap(len as u8)
-    } else if len <= u16::MAX as u32 {
-        write_marker(wr, Marker::Map16)?;
-        wr.write_data_u16(len as u16)?;
-        Marker::Map16
-    } else {
-        write_marker(wr, Marker::Map32)?;
-        wr.write_data_u32(len)?;
-        Marker::Map32
-    };
-
-    Ok(marker)
+    Header::map_len(len).write(wr)
 }
 
 /// Encodes and attempts to write the most efficient ext metadata implementation to the given
@@ -312,45 +300,5 @@ pub fn write_map_len<W: RmpWrite>(wr: &mut W, len: u32) -> Result<Marker, ValueW
 /// Panics if `ty` is negative, because it is reserved for future MessagePack extension including
 /// 2-byte type information.
 pub fn write_ext_meta<W: RmpWrite>(wr: &mut W, len: u32, ty: i8) -> Result<Marker, ValueWriteError<W::Error>> {
-    let marker = match len {
-        1 => {
-            write_marker(wr, Marker::FixExt1)?;
-            Marker::FixExt1
-        }
-        2 => {
-            write_marker(wr, Marker::FixExt2)?;
-            Marker::FixExt2
-        }
-        4 => {
-            write_marker(wr, Marker::FixExt4)?;
-            Marker::FixExt4
-        }
-        8 => {
-            write_marker(wr, Marker::FixExt8)?;
-            Marker::FixExt8
-        }
-        16 => {
-            write_marker(wr, Marker::FixExt16)?;
-            Marker::FixExt16
-        }
-        len if len < 256 => {
-            write_marker(wr, Marker::Ext8)?;
-            wr.write_data_u8(len as u8)?;
-            Marker::Ext8
-        }
-        len if len < 65536 => {
-            write_marker(wr, Marker::Ext16)?;
-            wr.write_data_u16(len as u16)?;
-            Marker::Ext16
-        }
-        len => {
-            write_marker(wr, Marker::Ext32)?;
-            wr.write_data_u32(len)?;
-            Marker::Ext32
-        }
-    };
-
-    wr.write_data_i8(ty)?;
-
-    Ok(marker)
+    Header::ext_meta(len, ty).write(wr)
 }
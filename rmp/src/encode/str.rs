@@ -1,31 +1,38 @@
-use super::{RmpWrite, write_marker};
+use super::header::Header;
+use super::RmpWrite;
 use crate::encode::ValueWriteError;
 use crate::Marker;
 
 /// Encodes and attempts to write the most efficient string length implementation to the given
 /// write, returning the marker used.
 ///
+/// This allows to stream the string data, for example from a file, instead of passing it to
+/// [write_str] as a single slice.
+///
+/// Only the marker and the length are written. The caller must then write exactly `len` bytes of
+/// valid UTF-8 to the same writer, otherwise the stream is corrupted: every value after it will
+/// be decoded from the wrong offset.
+///
 /// # Errors
 ///
 /// This function will return `ValueWriteError` on any I/O error occurred while writing either the
 /// marker or the data.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::encode::write_str_len;
+///
+/// let mut buf = Vec::new();
+/// write_str_len(&mut buf, 10).unwrap();
+/// for part in &["le", " ", "message"] {
+///     buf.extend_from_slice(part.as_bytes());
+/// }
+///
+/// assert_eq!("le message", rmp::decode::read_str_from_slice(&buf).unwrap().0);
+/// ```
 pub fn write_str_len<W: RmpWrite>(wr: &mut W, len: u32) -> Result<Marker, ValueWriteError<W::Error>> {
-    if len < 32 {
-        write_marker(wr, Marker::FixStr(len as u8))?;
-        Ok(Marker::FixStr(len as u8))
-    } else if len < 256 {
-        write_marker(wr, Marker::Str8)?;
-        wr.write_data_u8(len as u8)?;
-        Ok(Marker::Str8)
-    } else if len <= u16::MAX as u32 {
-        write_marker(wr, Marker::Str16)?;
-        wr.write_data_u16(len as u16)?;
-        Ok(Marker::Str16)
-    } else {
-        write_marker(wr, Marker::Str32)?;
-        wr.write_data_u32(len)?;
-        Ok(Marker::Str32)
-    }
+    Header::str_len(len).write(wr)
 }
 
 /// Encodes and attempts to write the most efficient string binary representation to the
@@ -1,3 +1,4 @@
+use super::header::Header;
 use super::{RmpWrite, write_marker};
 use crate::encode::{ValueWriteError};
 use crate::Marker;
@@ -130,22 +131,14 @@ pub fn write_u64<W: RmpWrite>(wr: &mut W, val: u64) -> Result<(), ValueWriteErro
 /// The first byte becomes the marker and the others (if present, up to 9) will represent the data
 /// itself.
 ///
+/// If the peer requires a fixed-width layout regardless of the value magnitude use `write_u8`,
+/// `write_u16`, `write_u32` or `write_u64` instead, which always emit the marker followed by the
+/// full-width payload.
+///
 /// # Errors
 ///
 /// This function will return `ValueWriteError` on any I/O error occurred while writing either the
 /// marker or the data.
 pub fn write_uint<W: RmpWrite>(wr: &mut W, val: u64) -> Result<Marker, ValueWriteError<W::Error>> {
-    if val < 128 {
-        write_pfix(wr, val as u8)
-            .and(Ok(Marker::FixPos(val as u8)))
-            .map_err(ValueWriteError::InvalidMarkerWrite)
-    } else if val < 256 {
-        write_u8(wr, val as u8).and(Ok(Marker::U8))
-    } else if val < 65536 {
-        write_u16(wr, val as u16).and(Ok(Marker::U16))
-    } else if val < 4294967296 {
-        write_u32(wr, val as u32).and(Ok(Marker::U32))
-    } else {
-        write_u64(wr, val).and(Ok(Marker::U64))
-    }
+    Header::uint(val).write(wr)
 }
@@ -1 +1,76 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
 
+use super::{write_array_len, RmpWrite, ValueWriteError};
+
+/// Encodes and attempts to write an array, taking its length from the given iterator.
+///
+/// The array length is written first, followed by every element encoded with the provided `f`
+/// closure. Because the length comes from the iterator itself, it can't get out of sync with the
+/// number of encoded elements.
+///
+/// # Errors
+///
+/// This function will return the error produced by `f`, or `ValueWriteError` converted into `E`
+/// on any I/O error occurred while writing the array length.
+///
+/// # Panics
+///
+/// Panics if the iterator reports more than `u32::MAX` elements, which can't be represented in
+/// MessagePack.
+///
+/// # Examples
+///
+/// ```
+/// use rmp::encode;
+///
+/// let mut buf = Vec::new();
+///
+/// encode::write_array_iter(&mut buf, &[1u8, 2, 3], |wr, &v| {
+///     encode::write_uint(wr, v.into()).map(|_| ())
+/// }).unwrap();
+///
+/// assert_eq!([0x93, 0x01, 0x02, 0x03], buf[..]);
+/// ```
+pub fn write_array_iter<W, I, F, E>(wr: &mut W, iter: I, mut f: F) -> Result<(), E>
+where
+    W: RmpWrite,
+    I: IntoIterator,
+    I::IntoIter: ExactSizeIterator,
+    F: FnMut(&mut W, I::Item) -> Result<(), E>,
+    E: From<ValueWriteError<W::Error>>,
+{
+    let iter = iter.into_iter();
+    let len = u32::try_from(iter.len()).expect("array is too long for MessagePack");
+
+    write_array_len(wr, len)?;
+    for item in iter {
+        f(wr, item)?;
+    }
+
+    Ok(())
+}
+
+/// Encodes and attempts to write an array from an iterator of unknown length.
+///
+/// Unlike [write_array_iter], this accepts any iterator, collecting its elements into a temporary
+/// buffer first to find out the array length.
+///
+/// # Errors
+///
+/// This function will return the error produced by `f`, or `ValueWriteError` converted into `E`
+/// on any I/O error occurred while writing the array length.
+///
+/// # Panics
+///
+/// Panics if the iterator yields more than `u32::MAX` elements.
+pub fn write_array_iter_buffered<W, I, F, E>(wr: &mut W, iter: I, f: F) -> Result<(), E>
+where
+    W: RmpWrite,
+    I: IntoIterator,
+    F: FnMut(&mut W, I::Item) -> Result<(), E>,
+    E: From<ValueWriteError<W::Error>>,
+{
+    let items: Vec<I::Item> = iter.into_iter().collect();
+    write_array_iter(wr, items, f)
+}
@@ -0,0 +1,130 @@
+//! Length-prefixed framing for sending MessagePack messages over a stream.
+//!
+//! Each frame is a 4-byte big-endian payload length followed by the payload itself. The payload is
+//! usually a single encoded MessagePack value, but it is not inspected.
+//!
+//! # Examples
+//!
+//! ```
+//! use rmp::frame::{read_frame, write_frame};
+//!
+//! let mut msg = Vec::new();
+//! rmp::encode::write_str(&mut msg, "le message").unwrap();
+//!
+//! let mut buf = Vec::new();
+//! write_frame(&mut buf, &msg).unwrap();
+//! assert_eq!([0x00, 0x00, 0x00, 0x0b], buf[..4]);
+//!
+//! assert_eq!(msg, read_frame(&mut &buf[..], 1024).unwrap());
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::decode::{RmpRead, RmpReadErr};
+use crate::encode::{RmpWrite, RmpWriteErr};
+
+/// Size of the length prefix of every frame.
+pub const FRAME_HEADER_SIZE: usize = 4;
+
+/// An error which can occur when writing a frame.
+#[derive(Debug)]
+pub enum FrameWriteError<E: RmpWriteErr> {
+    /// Failed to write the length prefix or the payload.
+    InvalidDataWrite(E),
+    /// The payload is longer than a 32-bit length can describe.
+    TooLarge(usize),
+}
+
+#[cfg(feature = "std")]
+impl<E: RmpWriteErr> std::error::Error for FrameWriteError<E> {
+    #[cold]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            FrameWriteError::InvalidDataWrite(ref err) => Some(err),
+            FrameWriteError::TooLarge(..) => None,
+        }
+    }
+}
+
+impl<E: RmpWriteErr> Display for FrameWriteError<E> {
+    #[cold]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match *self {
+            FrameWriteError::InvalidDataWrite(ref err) => write!(f, "failed to write frame: {}", err),
+            FrameWriteError::TooLarge(len) => write!(f, "frame payload of {} bytes is too large", len),
+        }
+    }
+}
+
+/// An error which can occur when reading a frame.
+#[derive(Debug)]
+pub enum FrameReadError<E: RmpReadErr> {
+    /// Failed to read the length prefix or the payload.
+    InvalidDataRead(E),
+    /// The declared payload length is greater than the allowed maximum.
+    ///
+    /// Returned before allocating or reading the payload.
+    TooLarge { len: u32, max: u32 },
+}
+
+#[cfg(feature = "std")]
+impl<E: RmpReadErr> std::error::Error for FrameReadError<E> {
+    #[cold]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            FrameReadError::InvalidDataRead(ref err) => Some(err),
+            FrameReadError::TooLarge { .. } => None,
+        }
+    }
+}
+
+impl<E: RmpReadErr> Display for FrameReadError<E> {
+    #[cold]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match *self {
+            FrameReadError::InvalidDataRead(ref err) => write!(f, "failed to read frame: {}", err),
+            FrameReadError::TooLarge { len, max } => {
+                write!(f, "frame payload of {} bytes exceeds the maximum of {}", len, max)
+            }
+        }
+    }
+}
+
+/// Writes the payload prefixed by its length as a 4-byte big-endian integer.
+///
+/// # Errors
+///
+/// Returns `FrameWriteError::TooLarge` without writing anything if the payload is longer than
+/// `u32::MAX` bytes.
+pub fn write_frame<W: RmpWrite>(wr: &mut W, payload: &[u8]) -> Result<(), FrameWriteError<W::Error>> {
+    if payload.len() > u32::MAX as usize {
+        return Err(FrameWriteError::TooLarge(payload.len()));
+    }
+
+    wr.write_bytes(&(payload.len() as u32).to_be_bytes()).map_err(FrameWriteError::InvalidDataWrite)?;
+    wr.write_bytes(payload).map_err(FrameWriteError::InvalidDataWrite)
+}
+
+/// Reads a frame written by [write_frame], returning its payload.
+///
+/// Exactly the frame is consumed from the reader, so consecutive frames can be read one by one.
+///
+/// # Errors
+///
+/// Returns `FrameReadError::TooLarge` if the declared length is greater than `max_len`, before
+/// allocating the payload, so that a hostile peer can't make the reader allocate a huge buffer.
+/// In that case only the length prefix is consumed.
+pub fn read_frame<R: RmpRead>(rd: &mut R, max_len: u32) -> Result<Vec<u8>, FrameReadError<R::Error>> {
+    let mut header = [0u8; FRAME_HEADER_SIZE];
+    rd.read_exact_buf(&mut header).map_err(FrameReadError::InvalidDataRead)?;
+
+    let len = u32::from_be_bytes(header);
+    if len > max_len {
+        return Err(FrameReadError::TooLarge { len, max: max_len });
+    }
+
+    let mut payload = Vec::new();
+    rd.read_exact_into_vec(&mut payload, len as usize).map_err(FrameReadError::InvalidDataRead)?;
+    Ok(payload)
+}
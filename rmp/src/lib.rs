@@ -151,12 +151,16 @@
 
 extern crate alloc;
 
+#[cfg(feature = "async-tokio")]
+pub mod async_encode;
 pub mod decode;
 pub mod encode;
+pub mod frame;
 mod marker;
+pub mod marker_bytes;
 mod errors;
 
-pub use crate::marker::Marker;
+pub use crate::marker::{Marker, ReservedMarkerError};
 
 /// Version of the MessagePack [spec](http://github.com/msgpack/msgpack/blob/master/spec.md).
 pub const MSGPACK_VERSION: u32 = 5;
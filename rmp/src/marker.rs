@@ -46,7 +46,10 @@ pub enum Marker {
 
 impl Marker {
     /// Construct a msgpack marker from a single byte.
-    pub fn from_u8(n: u8) -> Marker {
+    ///
+    /// This never fails, the reserved `0xc1` byte becomes [Marker::Reserved]. Use
+    /// [Marker::try_from_u8] to reject it.
+    pub const fn from_u8(n: u8) -> Marker {
         match n {
             0x00 ..= 0x7f => Marker::FixPos(n),
             0xe0 ..= 0xff => Marker::FixNeg(n as i8),
@@ -89,9 +92,45 @@ impl Marker {
         }
     }
 
+    /// Construct a msgpack marker from a single byte, rejecting the reserved `0xc1` byte.
+    ///
+    /// Use [Marker::from_u8] to map it to [Marker::Reserved] instead.
+    #[inline]
+    pub const fn try_from_u8(n: u8) -> Result<Marker, ReservedMarkerError> {
+        match Marker::from_u8(n) {
+            Marker::Reserved => Err(ReservedMarkerError),
+            marker => Ok(marker),
+        }
+    }
+
     /// Converts a marker object into a single-byte representation.
+    ///
+    /// This is the inverse of [Marker::from_u8], so `Marker::from_u8(m.to_u8()) == m` holds for
+    /// every marker with an in-range length.
+    #[inline]
     pub fn to_u8(&self) -> u8 {
-        match *self {
+        self.as_byte()
+    }
+
+    /// Returns the byte this marker is encoded as, including the value or length embedded in the
+    /// fixint, fixstr, fixarray and fixmap families.
+    ///
+    /// This is a `const fn`, so it can be used to define byte constants for protocol checks, and
+    /// the inverse of [Marker::from_u8] for every marker with an in-range length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmp::Marker;
+    ///
+    /// const STR8: u8 = Marker::Str8.as_byte();
+    ///
+    /// assert_eq!(0xd9, STR8);
+    /// assert_eq!(0x93, Marker::FixArray(3).as_byte());
+    /// assert_eq!(Marker::FixArray(3), 0x93);
+    /// ```
+    pub const fn as_byte(self) -> u8 {
+        match self {
             Marker::FixPos(val)   => val,
             Marker::FixNeg(val)   => val as u8,
 
@@ -144,6 +183,29 @@ impl Marker {
     }
 }
 
+/// An error returned when converting the reserved `0xc1` byte into a [Marker].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReservedMarkerError;
+
+impl core::fmt::Display for ReservedMarkerError {
+    #[cold]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("reserved marker byte 0xc1")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReservedMarkerError {}
+
+// There is intentionally no `PartialEq<Marker> for u8`, it would break type inference for
+// comparisons of `u8` with generic expressions in downstream crates.
+impl PartialEq<u8> for Marker {
+    #[inline]
+    fn eq(&self, other: &u8) -> bool {
+        self.as_byte() == *other
+    }
+}
+
 impl From<u8> for Marker {
     #[inline]
     fn from(val: u8) -> Marker {
@@ -0,0 +1,76 @@
+//! Named byte values of the MessagePack markers, for hand-building buffers in tests and protocol
+//! code.
+//!
+//! The names follow the [spec](https://github.com/msgpack/msgpack/blob/master/spec.md), and every
+//! constant is defined through [Marker::as_byte], so they can't disagree with the decoder. The
+//! fixint, fixstr, fixarray and fixmap families embed a value or a length in the marker byte,
+//! their constants are the first byte of the family.
+//!
+//! # Examples
+//!
+//! ```
+//! use rmp::marker_bytes::{FIXARRAY, UINT16};
+//!
+//! // [300]
+//! let buf = [FIXARRAY | 1, UINT16, 0x01, 0x2c];
+//!
+//! let mut rd = &buf[..];
+//! assert_eq!(1, rmp::decode::read_array_len(&mut rd).unwrap());
+//! assert_eq!(300u16, rmp::decode::read_int(&mut rd).unwrap());
+//! ```
+
+use crate::Marker;
+
+/// The first positive fixint, `0x00`; values up to `0x7f` are encoded as is.
+pub const POSITIVE_FIXINT: u8 = Marker::FixPos(0).as_byte();
+/// The fixmap family, `0x80`, OR-ed with a length of at most 15.
+pub const FIXMAP: u8          = Marker::FixMap(0).as_byte();
+/// The fixarray family, `0x90`, OR-ed with a length of at most 15.
+pub const FIXARRAY: u8        = Marker::FixArray(0).as_byte();
+/// The fixstr family, `0xa0`, OR-ed with a length of at most 31.
+pub const FIXSTR: u8          = Marker::FixStr(0).as_byte();
+/// The first negative fixint, `0xe0` for -32; the values up to -1 follow it.
+pub const NEGATIVE_FIXINT: u8 = Marker::FixNeg(-32).as_byte();
+
+pub const NIL: u8             = Marker::Null.as_byte();
+/// The reserved `0xc1`, which is never used.
+pub const NEVER_USED: u8      = Marker::Reserved.as_byte();
+pub const FALSE: u8           = Marker::False.as_byte();
+pub const TRUE: u8            = Marker::True.as_byte();
+
+pub const BIN8: u8            = Marker::Bin8.as_byte();
+pub const BIN16: u8           = Marker::Bin16.as_byte();
+pub const BIN32: u8           = Marker::Bin32.as_byte();
+
+pub const EXT8: u8            = Marker::Ext8.as_byte();
+pub const EXT16: u8           = Marker::Ext16.as_byte();
+pub const EXT32: u8           = Marker::Ext32.as_byte();
+
+pub const FLOAT32: u8         = Marker::F32.as_byte();
+pub const FLOAT64: u8         = Marker::F64.as_byte();
+
+pub const UINT8: u8           = Marker::U8.as_byte();
+pub const UINT16: u8          = Marker::U16.as_byte();
+pub const UINT32: u8          = Marker::U32.as_byte();
+pub const UINT64: u8          = Marker::U64.as_byte();
+
+pub const INT8: u8            = Marker::I8.as_byte();
+pub const INT16: u8           = Marker::I16.as_byte();
+pub const INT32: u8           = Marker::I32.as_byte();
+pub const INT64: u8           = Marker::I64.as_byte();
+
+pub const FIXEXT1: u8         = Marker::FixExt1.as_byte();
+pub const FIXEXT2: u8         = Marker::FixExt2.as_byte();
+pub const FIXEXT4: u8         = Marker::FixExt4.as_byte();
+pub const FIXEXT8: u8         = Marker::FixExt8.as_byte();
+pub const FIXEXT16: u8        = Marker::FixExt16.as_byte();
+
+pub const STR8: u8            = Marker::Str8.as_byte();
+pub const STR16: u8           = Marker::Str16.as_byte();
+pub const STR32: u8           = Marker::Str32.as_byte();
+
+pub const ARRAY16: u8         = Marker::Array16.as_byte();
+pub const ARRAY32: u8         = Marker::Array32.as_byte();
+
+pub const MAP16: u8           = Marker::Map16.as_byte();
+pub const MAP32: u8           = Marker::Map32.as_byte();
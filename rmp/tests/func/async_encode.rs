@@ -0,0 +1,97 @@
+use std::future::Future;
+use std::io::{Cursor, ErrorKind};
+
+use crate::msgpack::{async_encode, encode};
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(fut)
+}
+
+#[test]
+fn pass_uint_same_as_sync() {
+    for &val in &[0, 127, 128, 255, 256, 65535, 65536, 4294967295, 4294967296, u64::MAX] {
+        let mut buf = Vec::new();
+        let marker = block_on(async_encode::write_uint(&mut buf, val)).unwrap();
+
+        let mut expected = Vec::new();
+        assert_eq!(encode::write_uint(&mut expected, val).unwrap(), marker);
+        assert_eq!(expected, buf);
+    }
+}
+
+#[test]
+fn pass_sint_same_as_sync() {
+    let vals = [
+        i64::MIN, -2147483649, -2147483648, -32769, -32768, -129, -128, -33, -32, -1, 0, 127, 128,
+        i64::MAX,
+    ];
+    for &val in &vals {
+        let mut buf = Vec::new();
+        let marker = block_on(async_encode::write_sint(&mut buf, val)).unwrap();
+
+        let mut expected = Vec::new();
+        assert_eq!(encode::write_sint(&mut expected, val).unwrap(), marker);
+        assert_eq!(expected, buf);
+    }
+}
+
+#[test]
+fn pass_lens_same_as_sync() {
+    for &len in &[0, 1, 2, 4, 8, 15, 16, 31, 32, 255, 256, 65535, 65536, u32::MAX] {
+        let mut buf = Vec::new();
+        let mut expected = Vec::new();
+
+        block_on(async {
+            async_encode::write_array_len(&mut buf, len).await.unwrap();
+            async_encode::write_map_len(&mut buf, len).await.unwrap();
+            async_encode::write_ext_meta(&mut buf, len, -1).await.unwrap();
+        });
+        encode::write_array_len(&mut expected, len).unwrap();
+        encode::write_map_len(&mut expected, len).unwrap();
+        encode::write_ext_meta(&mut expected, len, -1).unwrap();
+
+        assert_eq!(expected, buf);
+    }
+}
+
+#[test]
+fn pass_str_and_bin_same_as_sync() {
+    for &len in &[0, 31, 32, 255, 256, 65536] {
+        let data = "x".repeat(len);
+
+        let mut buf = Vec::new();
+        block_on(async {
+            async_encode::write_str(&mut buf, &data).await.unwrap();
+            async_encode::write_bin(&mut buf, data.as_bytes()).await.unwrap();
+        });
+
+        let mut expected = Vec::new();
+        encode::write_str(&mut expected, &data).unwrap();
+        encode::write_bin(&mut expected, data.as_bytes()).unwrap();
+        assert_eq!(expected, buf);
+    }
+}
+
+#[test]
+fn pass_nil_bool_f64() {
+    let mut buf = Vec::new();
+    block_on(async {
+        async_encode::write_nil(&mut buf).await.unwrap();
+        async_encode::write_bool(&mut buf, true).await.unwrap();
+        async_encode::write_bool(&mut buf, false).await.unwrap();
+        async_encode::write_f64(&mut buf, -0.0).await.unwrap();
+    });
+
+    assert_eq!([0xc0, 0xc3, 0xc2, 0xcb, 0x80, 0, 0, 0, 0, 0, 0, 0], buf[..]);
+}
+
+#[test]
+fn fail_write_into_too_small_buffer() {
+    let mut buf = [0u8; 2];
+    let mut wr = Cursor::new(&mut buf[..]);
+
+    match block_on(async_encode::write_uint(&mut wr, 65536)) {
+        Err(err) => assert_eq!(ErrorKind::WriteZero, err.kind()),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
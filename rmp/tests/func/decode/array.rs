@@ -89,3 +89,178 @@ fn from_null_read_array_len() {
     }
     assert_eq!(1, cur.position());
 }
+
+#[test]
+fn from_arrays_read_array_len_marker() {
+    let buf: &[u8] = &[0x93, 0xdc, 0x00, 0x03, 0xdd, 0x00, 0x00, 0x00, 0x03];
+    let mut cur = Cursor::new(buf);
+
+    assert_eq!((3, Marker::FixArray(3)), read_array_len_marker(&mut cur).unwrap());
+    assert_eq!((3, Marker::Array16), read_array_len_marker(&mut cur).unwrap());
+    assert_eq!((3, Marker::Array32), read_array_len_marker(&mut cur).unwrap());
+    assert_eq!(9, cur.position());
+}
+
+#[test]
+fn from_array16_unexpected_eof_read_array_len_marker() {
+    let buf: &[u8] = &[0xdc, 0x00];
+    let mut cur = Cursor::new(buf);
+
+    match read_array_len_marker(&mut cur) {
+        Err(ValueReadError::InvalidDataRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_array_read_fixed_array() {
+    let buf: &[u8] = &[0x92, 0x01, 0x02, 0xdc, 0x00, 0x02];
+    let mut cur = Cursor::new(buf);
+
+    read_fixed_array(&mut cur, 2).unwrap();
+    assert_eq!(1, cur.position());
+    assert_eq!(1, read_pfix(&mut cur).unwrap());
+    assert_eq!(2, read_pfix(&mut cur).unwrap());
+    read_fixed_array(&mut cur, 2).unwrap();
+    assert_eq!(6, cur.position());
+}
+
+#[test]
+fn from_array_read_fixed_array_length_mismatch() {
+    let buf: &[u8] = &[0x93, 0x01, 0x02, 0x03];
+    let mut cur = Cursor::new(buf);
+
+    match read_fixed_array(&mut cur, 2) {
+        Err(DecodeError::ArrayLengthMismatch { expected: 2, actual: 3 }) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(1, cur.position());
+}
+
+#[test]
+fn from_map_read_fixed_array() {
+    let buf: &[u8] = &[0x82];
+    let mut cur = Cursor::new(buf);
+
+    match read_fixed_array(&mut cur, 2) {
+        Err(DecodeError::TypeMismatch(Marker::FixMap(2))) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_array_read_f64_array_into() {
+    let mut buf = vec![0xdc, 0x01, 0x2c];
+    for i in 0..300 {
+        buf.push(0xcb);
+        buf.extend_from_slice(&(f64::from(i) * 0.5).to_bits().to_be_bytes());
+    }
+    let mut cur = Cursor::new(&buf[..]);
+
+    let mut out = [0.0; 300];
+    read_f64_array_into(&mut cur, &mut out).unwrap();
+    for (i, val) in out.iter().enumerate() {
+        assert_eq!(i as f64 * 0.5, *val);
+    }
+    assert_eq!(buf.len() as u64, cur.position());
+}
+
+#[test]
+fn from_empty_array_read_f64_array_into() {
+    let buf: &[u8] = &[0x90];
+    let mut cur = Cursor::new(buf);
+
+    read_f64_array_into(&mut cur, &mut []).unwrap();
+    assert_eq!(1, cur.position());
+}
+
+#[test]
+fn from_array_read_f64_array_into_length_mismatch() {
+    let buf: &[u8] = &[0x92, 0xcb, 0, 0, 0, 0, 0, 0, 0, 0, 0xcb, 0, 0, 0, 0, 0, 0, 0, 0];
+    let mut cur = Cursor::new(buf);
+
+    match read_f64_array_into(&mut cur, &mut [0.0; 3]) {
+        Err(DecodeError::ArrayLengthMismatch { expected: 3, actual: 2 }) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(1, cur.position());
+}
+
+#[test]
+fn from_array_read_f64_array_into_type_mismatch() {
+    let buf: &[u8] = &[0x92, 0xcb, 0x3f, 0xf0, 0, 0, 0, 0, 0, 0, 0xca, 0x3f, 0x80, 0, 0, 0, 0, 0, 0];
+    let mut cur = Cursor::new(buf);
+
+    match read_f64_array_into(&mut cur, &mut [0.0; 2]) {
+        Err(DecodeError::TypeMismatch(Marker::F32)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_array_of_f32_at_end_of_input_read_f64_array_into() {
+    // [1.0f32, 2.0f32] is shorter than two f64 elements, but fails on the first marker.
+    let buf: &[u8] = &[0x92, 0xca, 0x3f, 0x80, 0, 0, 0xca, 0x40, 0, 0, 0];
+    let mut cur = Cursor::new(buf);
+
+    match read_f64_array_into(&mut cur, &mut [0.0; 2]) {
+        Err(DecodeError::TypeMismatch(Marker::F32)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(2, cur.position());
+}
+
+#[test]
+fn from_array_of_ints_at_end_of_input_read_f64_array_into() {
+    let buf: &[u8] = &[0x93, 0x01, 0x02, 0x03];
+    let mut cur = Cursor::new(buf);
+
+    match read_f64_array_into(&mut cur, &mut [0.0; 3]) {
+        Err(DecodeError::TypeMismatch(Marker::FixPos(1))) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(2, cur.position());
+}
+
+#[test]
+fn from_array_read_f64_array_into_unexpected_eof() {
+    let buf: &[u8] = &[0x92, 0xcb, 0x3f, 0xf0, 0, 0, 0, 0, 0, 0];
+    let mut cur = Cursor::new(buf);
+
+    match read_f64_array_into(&mut cur, &mut [0.0; 2]) {
+        Err(DecodeError::InvalidDataRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_array_with_mixed_markers_read_i64_array_into() {
+    let buf: &[u8] = &[0x95, 0x01, 0xff, 0xcd, 0x01, 0x2c, 0xd0, 0x80, 0xcf, 0, 0, 0, 0, 0, 0, 0, 0x2a];
+    let mut cur = Cursor::new(buf);
+
+    let mut out = [0; 5];
+    read_i64_array_into(&mut cur, &mut out).unwrap();
+    assert_eq!([1, -1, 300, -128, 42], out);
+    assert_eq!(buf.len() as u64, cur.position());
+}
+
+#[test]
+fn from_array_read_u32_array_into_out_of_range() {
+    let buf: &[u8] = &[0x92, 0x01, 0xff];
+    let mut cur = Cursor::new(buf);
+
+    match read_u32_array_into(&mut cur, &mut [0; 2]) {
+        Err(DecodeError::OutOfRange) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_array_read_u32_array_into() {
+    let buf: &[u8] = &[0x93, 0x00, 0xcc, 0xff, 0xce, 0xff, 0xff, 0xff, 0xff];
+    let mut cur = Cursor::new(buf);
+
+    let mut out = [0; 3];
+    read_u32_array_into(&mut cur, &mut out).unwrap();
+    assert_eq!([0, 255, u32::MAX], out);
+}
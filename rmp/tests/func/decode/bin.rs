@@ -59,3 +59,51 @@ fn from_bin32_max_read_len() {
     assert_eq!(4294967295, read_bin_len(&mut cur).unwrap());
     assert_eq!(5, cur.position());
 }
+
+#[test]
+fn from_bin8_read_bin_into_uninit() {
+    let buf: &[u8] = &[0xc4, 0x03, 0x01, 0x02, 0x03, 0xc0];
+    let mut cur = Cursor::new(buf);
+    let mut out = vec![0xff];
+
+    assert_eq!(3, read_bin_into_uninit(&mut cur, &mut out).unwrap());
+    assert_eq!([0xff, 0x01, 0x02, 0x03], out[..]);
+    assert_eq!(5, cur.position());
+}
+
+#[test]
+fn from_bin32_read_bin_into_uninit_large() {
+    let data: Vec<u8> = (0..200_000u32).map(|i| i as u8).collect();
+    let mut buf = vec![0xc6];
+    buf.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    buf.extend_from_slice(&data);
+
+    let mut out = Vec::new();
+    assert_eq!(200_000, read_bin_into_uninit(&mut Cursor::new(&buf[..]), &mut out).unwrap());
+    assert_eq!(data, out);
+}
+
+#[test]
+fn from_truncated_bin_read_bin_into_uninit_leaves_buffer() {
+    // Declares 4 GiB of data, but only two bytes follow.
+    let buf: &[u8] = &[0xc6, 0xff, 0xff, 0xff, 0xff, 0x01, 0x02];
+    let mut out = vec![0xaa];
+
+    match read_bin_into_uninit(&mut Cursor::new(buf), &mut out) {
+        Err(ValueReadError::InvalidDataRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!([0xaa], out[..]);
+    assert!(out.capacity() <= 64 * 1024 + 1);
+}
+
+#[test]
+fn from_nil_read_bin_into_uninit() {
+    let buf: &[u8] = &[0xc0];
+    let mut out = Vec::new();
+
+    match read_bin_into_uninit(&mut Cursor::new(buf), &mut out) {
+        Err(ValueReadError::TypeMismatch(Marker::Null)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -0,0 +1,74 @@
+use ::bytes::{Buf, Bytes};
+
+use crate::msgpack::decode::bytes::BytesReadError;
+use crate::msgpack::decode::*;
+use crate::msgpack::encode::{self, BufMutSink};
+
+#[test]
+fn from_chained_buf_source() {
+    let buf = Bytes::from_static(&[0xcd, 0x01]).chain(Bytes::from_static(&[0x2c, 0xc3]));
+    let mut rd = BufSource::new(buf);
+
+    assert_eq!(300, read_u16(&mut rd).unwrap());
+    assert!(read_bool(&mut rd).unwrap());
+    assert_eq!(0, rd.get_ref().remaining());
+}
+
+#[test]
+fn from_buf_source_unexpected_eof() {
+    let mut rd = BufSource::new(Bytes::from_static(&[0xcd, 0x01]));
+
+    match read_u16(&mut rd) {
+        Err(ValueReadError::InvalidDataRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn round_trip_buf_mut_sink() {
+    let mut wr = BufMutSink::new(::bytes::BytesMut::new());
+    encode::write_str(&mut wr, "le message").unwrap();
+    encode::write_bin(&mut wr, &[0xca, 0xfe]).unwrap();
+
+    let mut buf = wr.into_inner().freeze();
+    let base = buf.as_ptr();
+
+    let s = read_str_bytes(&mut buf).unwrap();
+    assert_eq!(b"le message", &s[..]);
+    // No copy is made, the result points into the original buffer.
+    assert_eq!(unsafe { base.add(1) }, s.as_ptr());
+
+    let bin = read_bin_bytes(&mut buf).unwrap();
+    assert_eq!(&[0xca, 0xfe], &bin[..]);
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn fail_bin_bytes_insufficient_keeps_buf() {
+    let mut buf = Bytes::from_static(&[0xc4, 0x03, 0x01, 0x02]);
+
+    match read_bin_bytes(&mut buf) {
+        Err(ValueReadError::InvalidDataRead(BytesReadError::InsufficientBytes { expected: 3, actual: 2, position: 2 })) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(4, buf.len());
+}
+
+#[test]
+fn fail_str_bytes_invalid_utf8_keeps_buf() {
+    let mut buf = Bytes::from_static(&[0xa2, 0xc3, 0x28]);
+
+    match read_str_bytes(&mut buf) {
+        Err(DecodeStringError::InvalidUtf8(&[0xc3, 0x28], ..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(3, buf.len());
+}
+
+#[test]
+fn fail_buf_mut_sink_capacity_overflow() {
+    let mut arr = [0u8; 2];
+    let mut wr = BufMutSink::new(&mut arr[..]);
+
+    encode::write_u16(&mut wr, 300).unwrap_err();
+}
@@ -0,0 +1,95 @@
+use crate::msgpack::decode::bytes::BytesReadError;
+use crate::msgpack::decode::*;
+
+#[test]
+fn with_position_reports_absolute_offsets() {
+    let buf: &[u8] = &[0xc0, 0xcd, 0x01, 0x2c, 0xc3];
+    let mut rd = Bytes::with_position(buf, 1);
+
+    assert_eq!(1, rd.position());
+    assert_eq!(4, rd.remaining());
+    assert_eq!(300, read_u16(&mut rd).unwrap());
+    assert_eq!(4, rd.position());
+    assert_eq!(1, rd.remaining());
+    assert_eq!(&[0xc3], rd.remaining_slice());
+}
+
+#[test]
+fn with_position_at_end() {
+    let buf: &[u8] = &[0xc0];
+    let mut rd = Bytes::with_position(buf, 1);
+
+    assert_eq!(0, rd.remaining());
+    match read_nil(&mut rd) {
+        Err(ValueReadError::InvalidMarkerRead(BytesReadError::InsufficientBytes { position: 1, .. })) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+#[should_panic]
+fn with_position_out_of_bounds() {
+    Bytes::with_position(&[0xc0], 2);
+}
+
+#[test]
+fn read_str_slice_borrows_from_buffer() {
+    let buf: &[u8] = &[0xa2, 0x6f, 0x6b, 0xa0, 0xc0];
+    let mut rd = Bytes::new(buf);
+
+    let s = rd.read_str_slice().unwrap();
+    assert_eq!("ok", s);
+    assert_eq!(buf[1..].as_ptr(), s.as_ptr());
+    assert_eq!(3, rd.position());
+    assert_eq!("", rd.read_str_slice().unwrap());
+    assert_eq!(&[0xc0], rd.remaining_slice());
+}
+
+#[test]
+fn read_str_slice_insufficient_bytes() {
+    let buf: &[u8] = &[0xa4, 0x6f, 0x6b];
+    let mut rd = Bytes::new(buf);
+
+    match rd.read_str_slice() {
+        Err(DecodeStringError::InvalidDataRead(BytesReadError::InsufficientBytes { expected: 4, actual: 2, position: 1 })) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(0, rd.position());
+}
+
+#[test]
+fn read_str_slice_invalid_utf8() {
+    let buf: &[u8] = &[0xa2, 0xc3, 0x28];
+    let mut rd = Bytes::new(buf);
+
+    match rd.read_str_slice() {
+        Err(DecodeStringError::InvalidUtf8(data, _)) => assert_eq!(&[0xc3, 0x28], data),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(0, rd.position());
+}
+
+#[test]
+fn read_str_slice_type_mismatch() {
+    let buf: &[u8] = &[0xc0];
+    let mut rd = Bytes::new(buf);
+
+    match rd.read_str_slice() {
+        Err(DecodeStringError::TypeMismatch(crate::msgpack::Marker::Null)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn read_str_slice_invalid_utf8_as_bytes_borrows_input() {
+    let buf: &[u8] = &[0xa3, 0x61, 0xff, 0x62];
+    let mut rd = Bytes::new(buf);
+
+    let err = rd.read_str_slice().unwrap_err();
+    let data = err.as_bytes().unwrap();
+    assert_eq!(buf[1..].as_ptr(), data.as_ptr());
+    assert_eq!("a\u{fffd}b", String::from_utf8_lossy(data));
+
+    let err = Bytes::new(&[0xc0]).read_str_slice().unwrap_err();
+    assert_eq!(None, err.as_bytes());
+}
@@ -0,0 +1,102 @@
+use super::Cursor;
+
+use crate::msgpack::decode::*;
+use crate::msgpack::encode;
+use crate::msgpack::Marker;
+
+fn assert_non_canonical<T: std::fmt::Debug>(res: Result<T, DecodeError<impl RmpReadErr>>, expected: Marker) {
+    match res {
+        Err(DecodeError::NonCanonical(marker)) => assert_eq!(expected, marker),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn pass_int_canonical_round_trip_boundaries() {
+    let vals = [
+        0, 127, 128, 255, 256, 65535, 65536, 4294967295, 4294967296, i64::MAX,
+        -1, -32, -33, -128, -129, -32768, -32769, -2147483648, -2147483649, i64::MIN,
+    ];
+    for &val in &vals {
+        let mut buf = Vec::new();
+        encode::write_sint(&mut buf, val).unwrap();
+        let mut cur = Cursor::new(&buf[..]);
+
+        assert_eq!(val, read_int_canonical::<i64, _>(&mut cur).unwrap());
+        assert_eq!(buf.len() as u64, cur.position());
+    }
+
+    let mut buf = Vec::new();
+    encode::write_uint(&mut buf, u64::MAX).unwrap();
+    assert_eq!(u64::MAX, read_int_canonical::<u64, _>(&mut Cursor::new(&buf[..])).unwrap());
+}
+
+#[test]
+fn fail_int_canonical_wider_unsigned() {
+    assert_non_canonical(read_int_canonical::<u64, _>(&mut Cursor::new(&[0xcc, 0x7f][..])), Marker::U8);
+    assert_non_canonical(read_int_canonical::<u64, _>(&mut Cursor::new(&[0xcd, 0x00, 0xff][..])), Marker::U16);
+    assert_non_canonical(read_int_canonical::<u64, _>(&mut Cursor::new(&[0xce, 0x00, 0x00, 0xff, 0xff][..])), Marker::U32);
+    assert_non_canonical(read_int_canonical::<u64, _>(&mut Cursor::new(&[0xcf, 0, 0, 0, 0, 0, 0, 0, 0x05][..])), Marker::U64);
+}
+
+#[test]
+fn fail_int_canonical_wider_signed() {
+    assert_non_canonical(read_int_canonical::<i64, _>(&mut Cursor::new(&[0xd0, 0xe0][..])), Marker::I8);
+    assert_non_canonical(read_int_canonical::<i64, _>(&mut Cursor::new(&[0xd1, 0xff, 0x80][..])), Marker::I16);
+    assert_non_canonical(read_int_canonical::<i64, _>(&mut Cursor::new(&[0xd2, 0xff, 0xff, 0x80, 0x00][..])), Marker::I32);
+    assert_non_canonical(read_int_canonical::<i64, _>(&mut Cursor::new(&[0xd3, 0xff, 0xff, 0xff, 0xff, 0x80, 0, 0, 0][..])), Marker::I64);
+}
+
+#[test]
+fn fail_int_canonical_non_negative_as_signed() {
+    assert_non_canonical(read_int_canonical::<i64, _>(&mut Cursor::new(&[0xd0, 0x01][..])), Marker::I8);
+    assert_non_canonical(read_int_canonical::<i64, _>(&mut Cursor::new(&[0xd1, 0x7f, 0xff][..])), Marker::I16);
+    assert_non_canonical(read_int_canonical::<i64, _>(&mut Cursor::new(&[0xd3, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff][..])), Marker::I64);
+}
+
+#[test]
+fn fail_int_canonical_out_of_range() {
+    match read_int_canonical::<u8, _>(&mut Cursor::new(&[0xcd, 0x01, 0x00][..])) {
+        Err(DecodeError::OutOfRange) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn pass_len_canonical_round_trip_boundaries() {
+    for &len in &[0, 15, 16, 31, 32, 255, 256, 65535, 65536, u32::MAX] {
+        let mut buf = Vec::new();
+        encode::write_str_len(&mut buf, len).unwrap();
+        encode::write_bin_len(&mut buf, len).unwrap();
+        encode::write_array_len(&mut buf, len).unwrap();
+        encode::write_map_len(&mut buf, len).unwrap();
+        let mut cur = Cursor::new(&buf[..]);
+
+        assert_eq!(len, read_str_len_canonical(&mut cur).unwrap());
+        assert_eq!(len, read_bin_len_canonical(&mut cur).unwrap());
+        assert_eq!(len, read_array_len_canonical(&mut cur).unwrap());
+        assert_eq!(len, read_map_len_canonical(&mut cur).unwrap());
+        assert_eq!(buf.len() as u64, cur.position());
+    }
+}
+
+#[test]
+fn fail_len_canonical_wider() {
+    assert_non_canonical(read_str_len_canonical(&mut Cursor::new(&[0xd9, 0x1f][..])), Marker::Str8);
+    assert_non_canonical(read_str_len_canonical(&mut Cursor::new(&[0xda, 0x00, 0xff][..])), Marker::Str16);
+    assert_non_canonical(read_str_len_canonical(&mut Cursor::new(&[0xdb, 0x00, 0x00, 0xff, 0xff][..])), Marker::Str32);
+    assert_non_canonical(read_bin_len_canonical(&mut Cursor::new(&[0xc5, 0x00, 0xff][..])), Marker::Bin16);
+    assert_non_canonical(read_bin_len_canonical(&mut Cursor::new(&[0xc6, 0x00, 0x00, 0xff, 0xff][..])), Marker::Bin32);
+    assert_non_canonical(read_array_len_canonical(&mut Cursor::new(&[0xdc, 0x00, 0x0f][..])), Marker::Array16);
+    assert_non_canonical(read_array_len_canonical(&mut Cursor::new(&[0xdd, 0x00, 0x00, 0xff, 0xff][..])), Marker::Array32);
+    assert_non_canonical(read_map_len_canonical(&mut Cursor::new(&[0xde, 0x00, 0x0f][..])), Marker::Map16);
+    assert_non_canonical(read_map_len_canonical(&mut Cursor::new(&[0xdf, 0x00, 0x00, 0xff, 0xff][..])), Marker::Map32);
+}
+
+#[test]
+fn fail_len_canonical_type_mismatch() {
+    match read_array_len_canonical(&mut Cursor::new(&[0x80][..])) {
+        Err(DecodeError::TypeMismatch(Marker::FixMap(0))) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
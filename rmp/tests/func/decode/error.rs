@@ -0,0 +1,80 @@
+use crate::msgpack::decode::bytes::BytesReadError;
+use crate::msgpack::decode::*;
+use crate::msgpack::Marker;
+
+fn read_pair<R: RmpRead>(rd: &mut R) -> Result<(u8, u16), DecodeError<R::Error>> {
+    read_nil(rd)?;
+    Ok((read_int(rd)?, read_int(rd)?))
+}
+
+#[test]
+fn from_value_and_num_errors() {
+    let mut rd = Bytes::new(&[0xc0, 0x01, 0xcd, 0x01, 0x2c]);
+    assert_eq!((1, 300), read_pair(&mut rd).unwrap());
+
+    match read_pair(&mut Bytes::new(&[0xc3])) {
+        Err(DecodeError::TypeMismatch(Marker::True)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    match read_pair(&mut Bytes::new(&[0xc0, 0xcd, 0x01, 0x2c])) {
+        Err(DecodeError::OutOfRange) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    match read_pair(&mut Bytes::new(&[0xc0, 0x01])) {
+        Err(DecodeError::InvalidMarkerRead(BytesReadError::InsufficientBytes { position: 2, .. })) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_string_error_drops_borrowed_data() {
+    fn read_name(buf: &[u8]) -> Result<(), DecodeError<BytesReadError>> {
+        read_str_from_slice(buf)?;
+        Ok(())
+    }
+
+    match read_name(&[0xa2, 0xc3, 0x28]) {
+        Err(DecodeError::InvalidUtf8(err)) => assert_eq!(0, err.valid_up_to()),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let mut out = [0u8; 1];
+    match read_str(&mut Bytes::new(&[0xa2, 0x6f, 0x6b]), &mut out).map_err(DecodeError::from) {
+        Err(DecodeError::BufferSizeTooSmall(2)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn canonical_and_array_errors_are_decode_errors() {
+    match read_int_canonical::<u8, _>(&mut Bytes::new(&[0xcc, 0x01])) {
+        Err(DecodeError::NonCanonical(Marker::U8)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let mut out = [0u32; 2];
+    match read_u32_array_into(&mut Bytes::new(&[0x91, 0x01]), &mut out) {
+        Err(DecodeError::ArrayLengthMismatch { expected: 2, actual: 1 }) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn map_read_err_bridges_readers() {
+    fn to_io(err: BytesReadError) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::UnexpectedEof, err.to_string())
+    }
+
+    let err = read_pair(&mut Bytes::new(&[])).unwrap_err().map_read_err(to_io);
+    match err {
+        DecodeError::InvalidMarkerRead(ref err) => assert_eq!(std::io::ErrorKind::UnexpectedEof, err.kind()),
+        ref other => panic!("unexpected result: {:?}", other),
+    }
+
+    let err = read_pair(&mut Bytes::new(&[0xc3])).unwrap_err().map_read_err(to_io);
+    let err: std::io::Error = err.into();
+    assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+}
@@ -126,3 +126,35 @@ fn from_ext32_read_ext_meta() {
     assert_eq!(ExtMeta { typeid: 1, size: 4294967295 }, read_ext_meta(&mut cur).unwrap());
     assert_eq!(6, cur.position());
 }
+
+#[test]
+fn from_ext32_read_ext_meta_max() {
+    let buf: &[u8] = &[0xc9, 0x00, 0x00, 0x04, 0x00, 0x01];
+    let mut cur = Cursor::new(buf);
+
+    assert_eq!(ExtMeta { typeid: 1, size: 1024 }, read_ext_meta_max(&mut cur, 1024).unwrap());
+    assert_eq!(6, cur.position());
+}
+
+#[test]
+fn from_ext32_read_ext_meta_max_fail_limit_exceeded() {
+    let buf: &[u8] = &[0xc9, 0xff, 0xff, 0xff, 0xff, 0x01, 0x00];
+    let mut cur = Cursor::new(buf);
+
+    match read_ext_meta_max(&mut cur, 1024) {
+        Err(DecodeError::LengthLimitExceeded { len: 4294967295, max: 1024 }) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(6, cur.position());
+}
+
+#[test]
+fn from_fixext16_read_ext_meta_max_fail_limit_exceeded() {
+    let buf: &[u8] = &[0xd8, 0x01];
+    let mut cur = Cursor::new(buf);
+
+    match read_ext_meta_max(&mut cur, 8) {
+        Err(DecodeError::LengthLimitExceeded { len: 16, max: 8 }) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
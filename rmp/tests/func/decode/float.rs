@@ -106,3 +106,43 @@ fn from_null_read_f64() {
     }
     assert_eq!(1, cur.position());
 }
+
+#[test]
+fn from_f32_nan_payload_bit_exact() {
+    // Quiet NaN with payload, signaling NaN, negative quiet NaN with payload.
+    for &bits in &[0x7fc1_2345u32, 0x7fa0_0001, 0xffc1_2345] {
+        let buf = [0xca, (bits >> 24) as u8, (bits >> 16) as u8, (bits >> 8) as u8, bits as u8];
+        let mut cur = Cursor::new(&buf[..]);
+
+        assert_eq!(bits, read_f32(&mut cur).unwrap().to_bits());
+        assert_eq!(5, cur.position());
+    }
+}
+
+#[test]
+fn from_f64_nan_payload_bit_exact() {
+    for &bits in &[0x7ff8_dead_beef_0001u64, 0x7ff0_0000_0000_0001, 0xfff8_dead_beef_0001] {
+        let mut buf = vec![0xcb];
+        buf.extend_from_slice(&bits.to_be_bytes());
+        let mut cur = Cursor::new(&buf[..]);
+
+        assert_eq!(bits, read_f64(&mut cur).unwrap().to_bits());
+        assert_eq!(9, cur.position());
+    }
+}
+
+#[test]
+fn from_f32_zero_minus_bit_exact() {
+    let buf: &[u8] = &[0xca, 0x80, 0x00, 0x00, 0x00];
+    let mut cur = Cursor::new(buf);
+
+    assert_eq!((-0.0f32).to_bits(), read_f32(&mut cur).unwrap().to_bits());
+}
+
+#[test]
+fn from_f64_zero_minus_bit_exact() {
+    let buf: &[u8] = &[0xcb, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+    let mut cur = Cursor::new(buf);
+
+    assert_eq!((-0.0f64).to_bits(), read_f64(&mut cur).unwrap().to_bits());
+}
@@ -0,0 +1,97 @@
+use super::Cursor;
+
+use crate::msgpack::decode::*;
+use crate::msgpack::Marker;
+
+#[test]
+fn from_bin8_into_heapless() {
+    let buf: &[u8] = &[0xc4, 0x03, 0x01, 0x02, 0x03];
+    let mut cur = Cursor::new(buf);
+
+    let bin = read_bin_heapless::<_, 3>(&mut cur).unwrap();
+    assert_eq!([0x01, 0x02, 0x03], bin[..]);
+    assert_eq!(5, cur.position());
+}
+
+#[test]
+fn from_bin8_into_heapless_capacity_exceeded() {
+    let buf: &[u8] = &[0xc4, 0x03, 0x01, 0x02, 0x03];
+    let mut cur = Cursor::new(buf);
+
+    match read_bin_heapless::<_, 2>(&mut cur) {
+        Err(HeaplessReadError::CapacityExceeded(3)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    // The body is left untouched.
+    assert_eq!(2, cur.position());
+}
+
+#[test]
+fn from_fixstr_into_heapless() {
+    let buf: &[u8] = &[0xaa, 0x6c, 0x65, 0x20, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65];
+    let mut cur = Cursor::new(buf);
+
+    assert_eq!("le message", read_str_heapless::<_, 10>(&mut cur).unwrap());
+    assert_eq!(11, cur.position());
+}
+
+#[test]
+fn from_fixstr_into_heapless_capacity_exceeded() {
+    let buf: &[u8] = &[0xaa, 0x6c, 0x65, 0x20, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65];
+    let mut cur = Cursor::new(buf);
+
+    match read_str_heapless::<_, 9>(&mut cur) {
+        Err(HeaplessReadError::CapacityExceeded(10)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(1, cur.position());
+}
+
+#[test]
+fn from_fixstr_into_heapless_invalid_utf8() {
+    let buf: &[u8] = &[0xa2, 0xc3, 0x28];
+    let mut cur = Cursor::new(buf);
+
+    match read_str_heapless::<_, 4>(&mut cur) {
+        Err(HeaplessReadError::InvalidUtf8(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_bin_into_heapless_str_type_mismatch() {
+    let buf: &[u8] = &[0xc4, 0x00];
+    let mut cur = Cursor::new(buf);
+
+    match read_str_heapless::<_, 4>(&mut cur) {
+        Err(HeaplessReadError::TypeMismatch(Marker::Bin8)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_fixarray_into_heapless() {
+    let buf: &[u8] = &[0x93, 0x01, 0xcc, 0x80, 0x03];
+    let mut cur = Cursor::new(buf);
+
+    let arr: heapless::Vec<u8, 4> = read_array_heapless(&mut cur, |rd| {
+        read_int(rd).map_err(|_| HeaplessReadError::TypeMismatch(Marker::Reserved))
+    }).unwrap();
+    assert_eq!([1, 128, 3], arr[..]);
+    assert_eq!(5, cur.position());
+}
+
+#[test]
+fn from_fixarray_into_heapless_capacity_exceeded() {
+    let buf: &[u8] = &[0x93, 0x01, 0x02, 0x03];
+    let mut cur = Cursor::new(buf);
+
+    let res: Result<heapless::Vec<u8, 2>, _> = read_array_heapless(&mut cur, |rd| {
+        read_u8(rd).map_err(HeaplessReadError::from)
+    });
+    match res {
+        Err(HeaplessReadError::CapacityExceeded(3)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(1, cur.position());
+}
@@ -0,0 +1,132 @@
+use super::Cursor;
+
+use crate::msgpack::decode::*;
+use crate::msgpack::Marker;
+
+/// Results of decoding into `u8`, `u16`, `u32`, `u64`, `i8`, `i16`, `i32` and `i64`, with `None`
+/// for `OutOfRange`.
+type Row = [Option<i128>; 8];
+
+fn decode_all(buf: &[u8], lenient: bool) -> Row {
+    fn decode<T: num_traits::FromPrimitive + Into<i128>>(buf: &[u8], lenient: bool) -> Option<i128> {
+        let mut cur = Cursor::new(buf);
+        let res = if lenient { read_int_lenient::<T, _>(&mut cur) } else { read_int::<T, _>(&mut cur) };
+        match res {
+            Ok(val) => {
+                assert_eq!(buf.len() as u64, cur.position());
+                Some(val.into())
+            }
+            Err(NumValueReadError::OutOfRange) => None,
+            Err(err) => panic!("unexpected error: {:?}", err),
+        }
+    }
+
+    [
+        decode::<u8>(buf, lenient),
+        decode::<u16>(buf, lenient),
+        decode::<u32>(buf, lenient),
+        decode::<u64>(buf, lenient),
+        decode::<i8>(buf, lenient),
+        decode::<i16>(buf, lenient),
+        decode::<i32>(buf, lenient),
+        decode::<i64>(buf, lenient),
+    ]
+}
+
+const N: Option<i128> = None;
+const M1: Option<i128> = Some(-1);
+const U8_MAX: Option<i128> = Some(u8::MAX as i128);
+const U16_MAX: Option<i128> = Some(u16::MAX as i128);
+const U32_MAX: Option<i128> = Some(u32::MAX as i128);
+const U64_MAX: Option<i128> = Some(u64::MAX as i128);
+const FIVE: Option<i128> = Some(5);
+
+/// Every integer marker, with the expected results of `read_int` and `read_int_lenient`.
+const TABLE: &[(&[u8], Row, Row)] = &[
+    // FixPos(5)
+    (&[0x05], [FIVE; 8], [FIVE; 8]),
+    // FixNeg(-1)
+    (&[0xff], [N, N, N, N, M1, M1, M1, M1], [U8_MAX, U8_MAX, U8_MAX, U8_MAX, M1, M1, M1, M1]),
+    // U8(5), U16(5), U32(5), U64(5)
+    (&[0xcc, 0x05], [FIVE; 8], [FIVE; 8]),
+    (&[0xcd, 0x00, 0x05], [FIVE; 8], [FIVE; 8]),
+    (&[0xce, 0x00, 0x00, 0x00, 0x05], [FIVE; 8], [FIVE; 8]),
+    (&[0xcf, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05], [FIVE; 8], [FIVE; 8]),
+    // U8(u8::MAX)
+    (&[0xcc, 0xff],
+     [U8_MAX, U8_MAX, U8_MAX, U8_MAX, N, U8_MAX, U8_MAX, U8_MAX],
+     [U8_MAX, U8_MAX, U8_MAX, U8_MAX, M1, U8_MAX, U8_MAX, U8_MAX]),
+    // U16(u16::MAX)
+    (&[0xcd, 0xff, 0xff],
+     [N, U16_MAX, U16_MAX, U16_MAX, N, N, U16_MAX, U16_MAX],
+     [N, U16_MAX, U16_MAX, U16_MAX, M1, M1, U16_MAX, U16_MAX]),
+    // U32(u32::MAX)
+    (&[0xce, 0xff, 0xff, 0xff, 0xff],
+     [N, N, U32_MAX, U32_MAX, N, N, N, U32_MAX],
+     [N, N, U32_MAX, U32_MAX, M1, M1, M1, U32_MAX]),
+    // U64(u64::MAX)
+    (&[0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+     [N, N, N, U64_MAX, N, N, N, N],
+     [N, N, N, U64_MAX, M1, M1, M1, M1]),
+    // I8(5), I16(5), I32(5), I64(5)
+    (&[0xd0, 0x05], [FIVE; 8], [FIVE; 8]),
+    (&[0xd1, 0x00, 0x05], [FIVE; 8], [FIVE; 8]),
+    (&[0xd2, 0x00, 0x00, 0x00, 0x05], [FIVE; 8], [FIVE; 8]),
+    (&[0xd3, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05], [FIVE; 8], [FIVE; 8]),
+    // I8(-1)
+    (&[0xd0, 0xff],
+     [N, N, N, N, M1, M1, M1, M1],
+     [U8_MAX, U8_MAX, U8_MAX, U8_MAX, M1, M1, M1, M1]),
+    // I16(-1)
+    (&[0xd1, 0xff, 0xff],
+     [N, N, N, N, M1, M1, M1, M1],
+     [N, U16_MAX, U16_MAX, U16_MAX, M1, M1, M1, M1]),
+    // I32(-1)
+    (&[0xd2, 0xff, 0xff, 0xff, 0xff],
+     [N, N, N, N, M1, M1, M1, M1],
+     [N, N, U32_MAX, U32_MAX, M1, M1, M1, M1]),
+    // I64(-1)
+    (&[0xd3, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+     [N, N, N, N, M1, M1, M1, M1],
+     [N, N, N, U64_MAX, M1, M1, M1, M1]),
+    // I16(i16::MIN) into i8 doesn't fit either way.
+    (&[0xd1, 0x80, 0x00],
+     [N, N, N, N, N, Some(i16::MIN as i128), Some(i16::MIN as i128), Some(i16::MIN as i128)],
+     [N, Some(0x8000), Some(0x8000), Some(0x8000), N, Some(i16::MIN as i128), Some(i16::MIN as i128), Some(i16::MIN as i128)]),
+];
+
+#[test]
+fn from_every_marker_read_int() {
+    for &(buf, expected, _) in TABLE {
+        assert_eq!(expected, decode_all(buf, false), "input {:x?}", buf);
+    }
+}
+
+#[test]
+fn from_every_marker_read_int_lenient() {
+    for &(buf, _, expected) in TABLE {
+        assert_eq!(expected, decode_all(buf, true), "input {:x?}", buf);
+    }
+}
+
+#[test]
+fn from_nil_read_int_lenient() {
+    let buf: &[u8] = &[0xc0];
+    let mut cur = Cursor::new(buf);
+
+    match read_int_lenient::<i64, _>(&mut cur) {
+        Err(NumValueReadError::TypeMismatch(Marker::Null)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_u32_unexpected_eof_read_int_lenient() {
+    let buf: &[u8] = &[0xce, 0xff, 0xff];
+    let mut cur = Cursor::new(buf);
+
+    match read_int_lenient::<i32, _>(&mut cur) {
+        Err(NumValueReadError::InvalidDataRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -0,0 +1,57 @@
+use super::Cursor;
+
+use crate::msgpack::decode::*;
+use crate::msgpack::Marker;
+
+#[test]
+fn from_every_length_family() {
+    let cases: &[(&[u8], LenPrefix)] = &[
+        (&[0x93], LenPrefix::Array(3)),
+        (&[0xdc, 0x01, 0x00], LenPrefix::Array(256)),
+        (&[0xdd, 0x00, 0x01, 0x00, 0x00], LenPrefix::Array(65536)),
+        (&[0x8f], LenPrefix::Map(15)),
+        (&[0xde, 0x00, 0x10], LenPrefix::Map(16)),
+        (&[0xdf, 0xff, 0xff, 0xff, 0xff], LenPrefix::Map(u32::MAX)),
+        (&[0xbf], LenPrefix::Str(31)),
+        (&[0xd9, 0x20], LenPrefix::Str(32)),
+        (&[0xda, 0x01, 0x00], LenPrefix::Str(256)),
+        (&[0xdb, 0x00, 0x01, 0x00, 0x00], LenPrefix::Str(65536)),
+        (&[0xc4, 0x00], LenPrefix::Bin(0)),
+        (&[0xc5, 0x01, 0x00], LenPrefix::Bin(256)),
+        (&[0xc6, 0x00, 0x01, 0x00, 0x00], LenPrefix::Bin(65536)),
+        (&[0xd4, 0x01], LenPrefix::Ext(1, 1)),
+        (&[0xd8, 0xff], LenPrefix::Ext(-1, 16)),
+        (&[0xc7, 0x03, 0x02], LenPrefix::Ext(2, 3)),
+        (&[0xc8, 0x01, 0x00, 0x7f], LenPrefix::Ext(127, 256)),
+        (&[0xc9, 0x00, 0x01, 0x00, 0x00, 0x80], LenPrefix::Ext(-128, 65536)),
+    ];
+
+    for &(buf, prefix) in cases {
+        let mut cur = Cursor::new(buf);
+        assert_eq!(prefix, read_len_prefix(&mut cur).unwrap());
+        assert_eq!(buf.len() as u64, cur.position());
+    }
+}
+
+#[test]
+fn from_value_without_length() {
+    let buf: &[u8] = &[0xcd, 0x01, 0x2c];
+    let mut cur = Cursor::new(buf);
+
+    match read_len_prefix(&mut cur) {
+        Err(ValueReadError::TypeMismatch(Marker::U16)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(1, cur.position());
+}
+
+#[test]
+fn from_truncated_length() {
+    let buf: &[u8] = &[0xdd, 0x00, 0x01];
+    let mut cur = Cursor::new(buf);
+
+    match read_len_prefix(&mut cur) {
+        Err(ValueReadError::InvalidDataRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -0,0 +1,74 @@
+use std::io::{self, ErrorKind};
+
+use super::Cursor;
+
+use crate::msgpack::decode::*;
+
+fn limit_of(err: &io::Error) -> Option<u64> {
+    assert_eq!(ErrorKind::InvalidData, err.kind());
+    err.get_ref().and_then(|err| err.downcast_ref::<LimitExceeded>()).map(LimitExceeded::limit)
+}
+
+#[test]
+fn pass_read_up_to_limit() {
+    let buf = [0x92, 0xcd, 0x01, 0x2c, 0xa2, 0x6c, 0x65];
+    let mut rd = LimitRead::new(Cursor::new(&buf[..]), buf.len() as u64);
+
+    assert_eq!(2, read_array_len(&mut rd).unwrap());
+    assert_eq!(300, read_u16(&mut rd).unwrap());
+    let mut out = [0u8; 2];
+    assert_eq!("le", read_str(&mut rd, &mut out).unwrap());
+
+    assert_eq!(0, rd.remaining());
+    assert_eq!(7, rd.limit());
+    assert_eq!(7, rd.into_inner().position());
+}
+
+#[test]
+fn fail_read_marker_past_limit() {
+    let buf = [0xc0, 0xc0];
+    let mut rd = LimitRead::new(Cursor::new(&buf[..]), 1);
+
+    read_nil(&mut rd).unwrap();
+    match read_nil(&mut rd) {
+        Err(ValueReadError::InvalidMarkerRead(err)) => assert_eq!(Some(1), limit_of(&err)),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_read_data_past_limit() {
+    let buf = [0xcf, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2a];
+    let mut rd = LimitRead::new(Cursor::new(&buf[..]), 5);
+
+    match read_u64(&mut rd) {
+        Err(ValueReadError::InvalidDataRead(err)) => assert_eq!(Some(5), limit_of(&err)),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(0, rd.remaining());
+}
+
+#[test]
+fn fail_read_bin_into_vec_past_limit() {
+    let mut buf = vec![0xc5, 0x01, 0x00];
+    buf.extend_from_slice(&[0u8; 256]);
+    let mut rd = LimitRead::new(Cursor::new(&buf[..]), 100);
+
+    let mut out = Vec::new();
+    match read_bin_into_uninit(&mut rd, &mut out) {
+        Err(ValueReadError::InvalidDataRead(err)) => assert_eq!(Some(100), limit_of(&err)),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(out.is_empty());
+}
+
+#[test]
+fn pass_inner_eof_is_not_limit() {
+    let buf = [0xcd, 0x01];
+    let mut rd = LimitRead::new(Cursor::new(&buf[..]), 1024);
+
+    match read_u16(&mut rd) {
+        Err(ValueReadError::InvalidDataRead(err)) => assert_eq!(ErrorKind::UnexpectedEof, err.kind()),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
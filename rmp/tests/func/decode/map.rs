@@ -59,3 +59,61 @@ fn from_null_read_map_len() {
     }
     assert_eq!(1, cur.position());
 }
+
+#[test]
+fn from_maps_read_map_len_marker() {
+    let buf: &[u8] = &[0x8f, 0xde, 0x00, 0x0f, 0xdf, 0x00, 0x00, 0x00, 0x0f];
+    let mut cur = Cursor::new(buf);
+
+    assert_eq!((15, Marker::FixMap(15)), read_map_len_marker(&mut cur).unwrap());
+    assert_eq!((15, Marker::Map16), read_map_len_marker(&mut cur).unwrap());
+    assert_eq!((15, Marker::Map32), read_map_len_marker(&mut cur).unwrap());
+    assert_eq!(9, cur.position());
+}
+
+#[test]
+fn from_null_read_map_len_marker() {
+    let buf: &[u8] = &[0xc0];
+    let mut cur = Cursor::new(buf);
+
+    match read_map_len_marker(&mut cur) {
+        Err(ValueReadError::TypeMismatch(Marker::Null)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(1, cur.position());
+}
+
+#[test]
+fn from_fixmap_read_map_strict() {
+    // {2: 20, 1: 300}
+    let buf: &[u8] = &[0x82, 0x02, 0x14, 0x01, 0xcd, 0x01, 0x2c];
+    let mut cur = Cursor::new(buf);
+
+    let map = read_map_strict(&mut cur, |rd| Ok(read_int::<u8, _>(rd)?), |rd| Ok(read_int::<u16, _>(rd)?)).unwrap();
+    assert_eq!(vec![(1, 300), (2, 20)], map.into_iter().collect::<Vec<_>>());
+    assert_eq!(7, cur.position());
+}
+
+#[test]
+fn from_fixmap_with_repeated_key_read_map_strict() {
+    // {5: 1, 6: 2, 5: 3}, where the last key is written as an u64.
+    let buf: &[u8] = &[0x83, 0x05, 0x01, 0x06, 0x02, 0xcf, 0, 0, 0, 0, 0, 0, 0, 0x05, 0x03];
+    let mut cur = Cursor::new(buf);
+
+    match read_map_strict(&mut cur, |rd| Ok(read_int::<u64, _>(rd)?), |rd| Ok(read_int::<u8, _>(rd)?)) {
+        Err(DecodeError::DuplicateKey) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(14, cur.position());
+}
+
+#[test]
+fn from_null_read_map_strict() {
+    let buf: &[u8] = &[0xc0];
+    let mut cur = Cursor::new(buf);
+
+    match read_map_strict(&mut cur, |rd| Ok(read_int::<u8, _>(rd)?), |rd| Ok(read_int::<u8, _>(rd)?)) {
+        Err(DecodeError::TypeMismatch(Marker::Null)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -1,11 +1,25 @@
 mod array;
 mod bin;
 mod bool;
+#[cfg(feature = "bytes")]
+mod buf;
+mod bytes;
+mod canonical;
+mod error;
 mod ext;
 mod float;
+mod int;
+mod len_prefix;
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "std")]
+mod limit;
 mod map;
 mod null;
+#[cfg(feature = "std")]
+mod peek;
 mod sint;
+mod skip;
 mod string;
 mod uint;
 
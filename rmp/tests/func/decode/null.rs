@@ -1,6 +1,7 @@
 use super::Cursor;
 
 use crate::msgpack::decode::*;
+use crate::msgpack::Marker;
 
 #[test]
 fn pass() {
@@ -64,3 +65,44 @@ fn interrupt_safe() {
 
     assert_eq!(1, cur.state());
 }
+
+#[test]
+fn pass_read_option_none() {
+    let buf = [0xc0, 0x80];
+    let mut cur = Cursor::new(&buf[..]);
+
+    assert_eq!(None, read_option(&mut cur, marker_to_len).unwrap());
+    assert_eq!(1, cur.position());
+}
+
+#[test]
+fn pass_read_option_some_keeps_marker() {
+    let buf = [0xde, 0x01, 0x00];
+    let mut cur = Cursor::new(&buf[..]);
+
+    assert_eq!(Some(256), read_option(&mut cur, marker_to_len).unwrap());
+    assert_eq!(3, cur.position());
+}
+
+#[test]
+fn fail_read_option_from_inner() {
+    let buf = [0xc3];
+    let mut cur = Cursor::new(&buf[..]);
+
+    match read_option(&mut cur, marker_to_len) {
+        Err(ValueReadError::TypeMismatch(Marker::True)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(1, cur.position());
+}
+
+#[test]
+fn fail_read_option_unexpected_eof() {
+    let buf = [];
+    let mut cur = Cursor::new(&buf[..]);
+
+    match read_option(&mut cur, marker_to_len) {
+        Err(ValueReadError::InvalidMarkerRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
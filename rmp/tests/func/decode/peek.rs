@@ -0,0 +1,81 @@
+use std::io::{BufRead, BufReader, ErrorKind, Read};
+
+use crate::msgpack::decode::*;
+
+#[test]
+fn pass_peek_header_of_every_family() {
+    let cases: &[&[u8]] = &[
+        &[0xc0],
+        &[0x2a],
+        &[0xcc, 0xff],
+        &[0xd3, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01],
+        &[0xcb, 0x40, 0x09, 0x21, 0xfb, 0x54, 0x44, 0x2d, 0x18],
+        &[0xa3],
+        &[0xda, 0x01, 0x00],
+        &[0xc6, 0x00, 0x01, 0x00, 0x00],
+        &[0xdc, 0x00, 0x10],
+        &[0x81],
+        &[0xd4, 0x01],
+        &[0xc9, 0x00, 0x00, 0x01, 0x00, 0x05],
+    ];
+
+    for &header in cases {
+        let mut buf = header.to_vec();
+        buf.extend_from_slice(&[0xaa; 16]);
+
+        let mut rd = PeekReader::new(&buf[..]);
+        assert_eq!(header, rd.peek_header().unwrap());
+        assert_eq!(header, rd.peek_header().unwrap());
+    }
+}
+
+#[test]
+fn pass_peek_header_across_small_buffers() {
+    // u64::MAX, [ext16(7, 256 bytes)]
+    let buf = [
+        0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0x91, 0xc8, 0x01, 0x00, 0x07,
+    ];
+
+    for capacity in 1..4 {
+        let mut rd = PeekReader::new(BufReader::with_capacity(capacity, &buf[..]));
+
+        assert_eq!(buf[..9], *rd.peek_header().unwrap());
+        assert_eq!(u64::MAX, read_u64(&mut rd).unwrap());
+
+        assert_eq!([0x91], rd.peek_header().unwrap());
+        rd.consume(1);
+        assert_eq!(buf[10..], *rd.peek_header().unwrap());
+        assert_eq!(ExtMeta { typeid: 7, size: 256 }, read_ext_meta(&mut rd).unwrap());
+
+        assert!(rd.peek_header().unwrap().is_empty());
+    }
+}
+
+#[test]
+fn pass_resync_after_peeked_header() {
+    // A reserved byte is skipped, then a u16 is read.
+    let buf = [0xc1, 0xcd, 0x01, 0x2c];
+    let mut rd = PeekReader::new(BufReader::with_capacity(2, &buf[..]));
+
+    assert_eq!([0xc1], rd.peek_header().unwrap());
+    rd.consume(1);
+    assert_eq!([0xcd, 0x01, 0x2c], rd.peek_header().unwrap());
+
+    let mut rest = Vec::new();
+    rd.read_to_end(&mut rest).unwrap();
+    assert_eq!(buf[1..], rest[..]);
+}
+
+#[test]
+fn fail_peek_truncated_header() {
+    let buf = [0xdd, 0x00, 0x01];
+    let mut rd = PeekReader::new(BufReader::with_capacity(1, &buf[..]));
+
+    assert_eq!(ErrorKind::UnexpectedEof, rd.peek_header().unwrap_err().kind());
+
+    // The partial header is still there.
+    let mut rest = Vec::new();
+    rd.read_to_end(&mut rest).unwrap();
+    assert_eq!(buf[..], rest[..]);
+}
@@ -0,0 +1,90 @@
+use super::Cursor;
+
+use crate::msgpack::decode::*;
+use crate::msgpack::Marker;
+
+#[test]
+fn pass_skip_every_family() {
+    let buf: &[u8] = &[
+        // [nil, true, -1, 300u16, 1.5f32, "ab", bin8 [1], fixext1(5, 0), {1: [], 2: {}}]
+        0x99, 0xc0, 0xc3, 0xff, 0xcd, 0x01, 0x2c, 0xca, 0x3f, 0xc0, 0x00, 0x00, 0xa2, 0x61, 0x62,
+        0xc4, 0x01, 0x01, 0xd4, 0x05, 0x00, 0x82, 0x01, 0x90, 0x02, 0x80,
+        // Next value.
+        0x2a,
+    ];
+    let mut cur = Cursor::new(buf);
+
+    skip_value(&mut cur, usize::MAX).unwrap();
+    assert_eq!(buf.len() as u64 - 1, cur.position());
+    assert_eq!(42, read_pfix(&mut cur).unwrap());
+}
+
+#[test]
+fn pass_skip_ext32() {
+    let buf: &[u8] = &[0xc9, 0x00, 0x00, 0x00, 0x02, 0x05, 0xaa, 0xbb];
+    let mut cur = Cursor::new(buf);
+
+    skip_value(&mut cur, 0).unwrap();
+    assert_eq!(8, cur.position());
+}
+
+#[test]
+fn pass_skip_value_data_after_marker() {
+    let buf: &[u8] = &[0x92, 0x01, 0x91, 0x02, 0x03];
+    let mut cur = Cursor::new(buf);
+
+    let marker = read_marker(&mut cur).unwrap();
+    skip_value_data(&mut cur, marker, 2).unwrap();
+    assert_eq!(4, cur.position());
+}
+
+#[test]
+fn pass_skip_deeply_nested_without_limit() {
+    let mut buf = vec![0x91; 100_000];
+    buf.push(0xc0);
+    let mut cur = Cursor::new(&buf[..]);
+
+    skip_value(&mut cur, usize::MAX).unwrap();
+    assert_eq!(buf.len() as u64, cur.position());
+}
+
+#[test]
+fn fail_skip_depth_limit() {
+    let buf: &[u8] = &[0x91, 0x91, 0x91, 0xc0];
+
+    skip_value(&mut Cursor::new(buf), 3).unwrap();
+    match skip_value(&mut Cursor::new(buf), 2) {
+        Err(DecodeError::DepthLimitExceeded) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_skip_reserved() {
+    let buf: &[u8] = &[0x92, 0x01, 0xc1];
+
+    match skip_value(&mut Cursor::new(buf), usize::MAX) {
+        Err(DecodeError::TypeMismatch(Marker::Reserved)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_skip_truncated_data() {
+    let buf: &[u8] = &[0x91, 0xa3, 0x61, 0x62];
+
+    match skip_value(&mut Cursor::new(buf), usize::MAX) {
+        Err(DecodeError::InvalidDataRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_skip_truncated_array() {
+    let buf: &[u8] = &[0x92, 0x01];
+
+    match skip_value(&mut Cursor::new(buf), usize::MAX) {
+        Err(DecodeError::InvalidMarkerRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
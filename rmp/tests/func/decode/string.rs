@@ -183,6 +183,71 @@ fn from_str_strfix_invalid_utf8() {
     assert_eq!(3, cur.position());
 }
 
+#[test]
+fn from_str_strfix_invalid_utf8_as_bytes() {
+    let buf: &[u8] = &[0xa2, 0xc3, 0x28];
+    let mut cur = Cursor::new(buf);
+
+    let out: &mut [u8] = &mut [0u8; 16];
+    let out_ptr = out.as_ptr();
+
+    let err = read_str(&mut cur, out).unwrap_err();
+    assert_eq!(Some(&[0xc3, 0x28][..]), err.as_bytes());
+    assert_eq!(out_ptr, err.as_bytes().unwrap().as_ptr());
+}
+
+#[test]
+fn from_str_invalid_utf8_error_position() {
+    // "ab", an invalid 2 octet sequence, "c".
+    let buf: &[u8] = &[0xa5, 0x61, 0x62, 0xc3, 0x28, 0x63];
+    let mut cur = Cursor::new(buf);
+
+    let out: &mut [u8] = &mut [0u8; 16];
+    let err = read_str(&mut cur, out).unwrap_err();
+
+    let utf8_err = err.utf8_error().unwrap();
+    assert_eq!(2, utf8_err.valid_up_to());
+    assert_eq!(Some(1), utf8_err.error_len());
+    assert_eq!(Some(&buf[1..]), err.as_bytes());
+}
+
+#[test]
+fn from_str_from_slice_invalid_utf8_reports_string_data() {
+    // A truncated 3 octet sequence, followed by another value.
+    let buf = [0xa3, 0x61, 0xe2, 0x82, 0xc0];
+    let err = read_str_from_slice(&buf).unwrap_err();
+
+    assert_eq!(Some(&buf[1..4]), err.as_bytes());
+    let utf8_err = err.utf8_error().unwrap();
+    assert_eq!(1, utf8_err.valid_up_to());
+    assert_eq!(None, utf8_err.error_len());
+}
+
+#[test]
+fn from_str_utf8_error_is_none_for_other_errors() {
+    let buf: &[u8] = &[0xc0];
+    let mut cur = Cursor::new(buf);
+
+    let out: &mut [u8] = &mut [0u8; 16];
+    let err = read_str(&mut cur, out).unwrap_err();
+    assert!(err.utf8_error().is_none());
+    assert!(err.as_bytes().is_none());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn from_str_invalid_utf8_display_has_position() {
+    let buf: &[u8] = &[0xa2, 0xc3, 0x28];
+    let mut cur = Cursor::new(buf);
+
+    let out: &mut [u8] = &mut [0u8; 16];
+    let err = read_str(&mut cur, out).unwrap_err();
+    assert_eq!(
+        "error while decoding string: invalid utf-8 sequence of 1 bytes from index 0",
+        err.to_string()
+    );
+}
+
 #[test]
 fn from_str_strfix_buffer_too_small() {
     let buf: &[u8] = &[0xaa, 0x6c, 0x65, 0x20, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65];
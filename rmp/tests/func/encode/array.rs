@@ -27,3 +27,37 @@ fn pass_pack_len_u32() {
 
     assert_eq!([0xdd, 0xff, 0xff, 0xff, 0xff], buf);
 }
+
+#[test]
+fn pass_pack_iter() {
+    let mut buf = Vec::new();
+
+    write_array_iter(&mut buf, vec![1u8, 2, 3], write_u8).unwrap();
+
+    assert_eq!([0x93, 0xcc, 0x01, 0xcc, 0x02, 0xcc, 0x03], buf[..]);
+}
+
+#[test]
+fn pass_pack_iter_empty() {
+    let mut buf = Vec::new();
+
+    write_array_iter(&mut buf, Vec::<u8>::new(), write_u8).unwrap();
+
+    assert_eq!([0x90], buf[..]);
+}
+
+#[test]
+fn pass_pack_iter_buffered() {
+    let mut buf = Vec::new();
+
+    write_array_iter_buffered(&mut buf, (0u8..10).filter(|v| v % 3 == 0), write_u8).unwrap();
+
+    assert_eq!([0x94, 0xcc, 0x00, 0xcc, 0x03, 0xcc, 0x06, 0xcc, 0x09], buf[..]);
+}
+
+#[test]
+fn fail_pack_iter_element_error() {
+    let mut buf = [0x00, 0x00, 0x00];
+
+    write_array_iter(&mut &mut buf[..], vec![1u8, 2, 3], write_u8).err().unwrap();
+}
@@ -27,3 +27,18 @@ fn pass_pack_len_u32() {
 
     assert_eq!([0xc6, 0xff, 0xff, 0xff, 0xff], buf);
 }
+
+#[test]
+fn pass_pack_len_then_stream_body() {
+    let data: Vec<u8> = (0..70000u32).map(|i| i as u8).collect();
+
+    let mut streamed = Vec::new();
+    assert_eq!(Marker::Bin32, write_bin_len(&mut streamed, data.len() as u32).unwrap());
+    for chunk in data.chunks(4096) {
+        streamed.extend_from_slice(chunk);
+    }
+
+    let mut expected = Vec::new();
+    write_bin(&mut expected, &data).unwrap();
+    assert_eq!(expected, streamed);
+}
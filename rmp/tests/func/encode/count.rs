@@ -0,0 +1,32 @@
+use crate::msgpack::encode::*;
+
+#[test]
+fn pass_count_matches_output_len() {
+    let mut wr = CountWrite::new(Vec::new());
+
+    write_map_len(&mut wr, 1).unwrap();
+    write_str(&mut wr, "key").unwrap();
+    write_u64(&mut wr, 42).unwrap();
+
+    assert_eq!(1 + 4 + 9, wr.count());
+    assert_eq!(wr.count(), wr.get_ref().len() as u64);
+    assert_eq!(vec![0x81, 0xa3, 0x6b, 0x65, 0x79, 0xcf, 0, 0, 0, 0, 0, 0, 0, 0x2a], wr.into_inner());
+}
+
+#[test]
+fn pass_count_starts_at_zero() {
+    let wr = CountWrite::new(Vec::<u8>::new());
+
+    assert_eq!(0, wr.count());
+}
+
+#[test]
+fn pass_count_ignores_writes_to_inner() {
+    let mut wr = CountWrite::new(Vec::new());
+
+    write_nil(&mut wr).unwrap();
+    write_nil(wr.get_mut()).unwrap();
+
+    assert_eq!(1, wr.count());
+    assert_eq!(2, wr.get_ref().len());
+}
@@ -19,3 +19,30 @@ fn pass_pack_f64() {
 
     assert_eq!([0xcb, 0x7f, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], buf);
 }
+
+#[test]
+fn pass_pack_f32_nan_payload_bit_exact() {
+    let mut buf = [0x00, 0x00, 0x00, 0x00, 0x00];
+
+    write_f32(&mut &mut buf[..], f32::from_bits(0x7fa0_0001)).ok().unwrap();
+
+    assert_eq!([0xca, 0x7f, 0xa0, 0x00, 0x01], buf);
+}
+
+#[test]
+fn pass_pack_f64_nan_payload_bit_exact() {
+    let mut buf = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+    write_f64(&mut &mut buf[..], f64::from_bits(0xfff8_dead_beef_0001)).ok().unwrap();
+
+    assert_eq!([0xcb, 0xff, 0xf8, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x01], buf);
+}
+
+#[test]
+fn pass_pack_f64_zero_minus() {
+    let mut buf = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+    write_f64(&mut &mut buf[..], -0.0).ok().unwrap();
+
+    assert_eq!([0xcb, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], buf);
+}
@@ -122,6 +122,40 @@ fn pass_pack_i64() {
     assert_eq!([0xd3, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], buf);
 }
 
+#[test]
+fn pass_pack_fixed_width_small_values() {
+    let mut buf = Vec::new();
+
+    write_u8(&mut buf, 0).unwrap();
+    write_u16(&mut buf, 1).unwrap();
+    write_u32(&mut buf, 2).unwrap();
+    write_u64(&mut buf, 3).unwrap();
+
+    assert_eq!(vec![
+        0xcc, 0x00,
+        0xcd, 0x00, 0x01,
+        0xce, 0x00, 0x00, 0x00, 0x02,
+        0xcf, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03,
+    ], buf);
+}
+
+#[test]
+fn pass_pack_fixed_width_small_signed_values() {
+    let mut buf = Vec::new();
+
+    write_i8(&mut buf, 0).unwrap();
+    write_i16(&mut buf, -1).unwrap();
+    write_i32(&mut buf, 2).unwrap();
+    write_i64(&mut buf, -3).unwrap();
+
+    assert_eq!(vec![
+        0xd0, 0x00,
+        0xd1, 0xff, 0xff,
+        0xd2, 0x00, 0x00, 0x00, 0x02,
+        0xd3, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfd,
+    ], buf);
+}
+
 #[test]
 fn pass_pack_uint_fix() {
     let mut buf = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
@@ -27,3 +27,37 @@ fn pass_pack_len_u32() {
 
     assert_eq!([0xdf, 0xff, 0xff, 0xff, 0xff], buf);
 }
+
+#[test]
+fn pass_pack_iter() {
+    let mut buf = Vec::new();
+
+    write_map_iter(&mut buf, vec![("a", 1u8), ("b", 2)],
+        write_str,
+        write_u8,
+    ).unwrap();
+
+    assert_eq!([0x82, 0xa1, 0x61, 0xcc, 0x01, 0xa1, 0x62, 0xcc, 0x02], buf[..]);
+}
+
+#[test]
+fn pass_pack_iter_buffered() {
+    let mut buf = Vec::new();
+
+    write_map_iter_buffered(&mut buf, (0u8..4).filter(|v| v % 2 == 1).map(|v| (v, v * 2)),
+        write_u8,
+        write_u8,
+    ).unwrap();
+
+    assert_eq!([0x82, 0xcc, 0x01, 0xcc, 0x02, 0xcc, 0x03, 0xcc, 0x06], buf[..]);
+}
+
+#[test]
+fn fail_pack_iter_value_error() {
+    let mut buf = [0x00, 0x00, 0x00];
+
+    write_map_iter(&mut &mut buf[..], vec![(1u8, 2u8)],
+        write_u8,
+        write_u8,
+    ).err().unwrap();
+}
@@ -36,3 +36,18 @@ fn pass_pack_len_u32() {
 
     assert_eq!([0xdb, 0xff, 0xff, 0xff, 0xff], buf);
 }
+
+#[test]
+fn pass_pack_len_then_stream_body() {
+    let data = "le message ".repeat(100);
+
+    let mut streamed = Vec::new();
+    assert_eq!(Marker::Str16, write_str_len(&mut streamed, data.len() as u32).unwrap());
+    for chunk in data.as_bytes().chunks(7) {
+        streamed.extend_from_slice(chunk);
+    }
+
+    let mut expected = Vec::new();
+    write_str(&mut expected, &data).unwrap();
+    assert_eq!(expected, streamed);
+}
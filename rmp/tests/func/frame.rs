@@ -0,0 +1,81 @@
+use crate::msgpack::decode::{self, RmpRead};
+use crate::msgpack::encode;
+use crate::msgpack::frame::*;
+
+#[test]
+fn pass_write_frame() {
+    let mut buf = Vec::new();
+    write_frame(&mut buf, &[0xc3, 0xc2]).unwrap();
+
+    assert_eq!(vec![0x00, 0x00, 0x00, 0x02, 0xc3, 0xc2], buf);
+}
+
+#[test]
+fn pass_round_trip_consecutive_frames() {
+    let mut first = Vec::new();
+    encode::write_str(&mut first, "le message").unwrap();
+    let mut second = Vec::new();
+    encode::write_array_len(&mut second, 0).unwrap();
+
+    let mut buf = Vec::new();
+    write_frame(&mut buf, &first).unwrap();
+    write_frame(&mut buf, &[]).unwrap();
+    write_frame(&mut buf, &second).unwrap();
+
+    let mut rd = &buf[..];
+    assert_eq!(first, read_frame(&mut rd, 16).unwrap());
+    assert!(read_frame(&mut rd, 16).unwrap().is_empty());
+    let frame = read_frame(&mut rd, 16).unwrap();
+    assert_eq!(0, decode::read_array_len(&mut &frame[..]).unwrap());
+    assert!(rd.is_empty());
+}
+
+#[test]
+fn pass_read_frame_at_max_len() {
+    let buf = [0x00, 0x00, 0x00, 0x02, 0xc3, 0xc2];
+
+    assert_eq!(vec![0xc3, 0xc2], read_frame(&mut &buf[..], 2).unwrap());
+}
+
+#[test]
+fn fail_read_frame_too_large() {
+    let buf = [0xff, 0xff, 0xff, 0xff, 0xc0];
+    let mut rd = &buf[..];
+
+    match read_frame(&mut rd, 1024) {
+        Err(FrameReadError::TooLarge { len: 0xffff_ffff, max: 1024 }) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    // Only the header is consumed.
+    assert_eq!(0xc0, rd.read_u8().unwrap());
+}
+
+#[test]
+fn fail_read_frame_truncated_payload() {
+    let buf = [0x00, 0x00, 0x00, 0x03, 0xc3, 0xc2];
+
+    match read_frame(&mut &buf[..], 1024) {
+        Err(FrameReadError::InvalidDataRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_read_frame_truncated_header() {
+    let buf = [0x00, 0x00];
+
+    match read_frame(&mut &buf[..], 1024) {
+        Err(FrameReadError::InvalidDataRead(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_write_frame_short_writer() {
+    let mut buf = [0u8; 5];
+
+    match write_frame(&mut &mut buf[..], &[0xc3, 0xc2]) {
+        Err(FrameWriteError::InvalidDataWrite(..)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -0,0 +1,104 @@
+use crate::msgpack::{marker_bytes, Marker, ReservedMarkerError};
+
+#[test]
+fn try_from_u8_rejects_reserved() {
+    assert_eq!(Err(ReservedMarkerError), Marker::try_from_u8(0xc1));
+    assert_eq!(Marker::Reserved, Marker::from_u8(0xc1));
+    assert_eq!(Marker::Reserved, Marker::from(0xc1));
+}
+
+#[test]
+fn try_from_u8_accepts_everything_else() {
+    for n in (0..=0xffu8).filter(|&n| n != 0xc1) {
+        assert_eq!(Ok(Marker::from_u8(n)), Marker::try_from_u8(n));
+    }
+}
+
+#[test]
+fn to_u8_round_trips_every_byte() {
+    for n in 0..=0xffu8 {
+        assert_eq!(n, Marker::from_u8(n).to_u8());
+    }
+}
+
+#[test]
+fn as_byte_round_trips_every_marker() {
+    for n in (0..=0xffu8).filter(|&n| n != 0xc1) {
+        let marker = Marker::from_u8(n);
+        assert_eq!(n, marker.as_byte());
+        assert_eq!(marker, Marker::from_u8(marker.as_byte()));
+    }
+}
+
+#[test]
+fn as_byte_keeps_embedded_values() {
+    assert_eq!(0x7f, Marker::FixPos(0x7f).as_byte());
+    assert_eq!(0xe0, Marker::FixNeg(-32).as_byte());
+    assert_eq!(0xbf, Marker::FixStr(31).as_byte());
+    assert_eq!(0x9f, Marker::FixArray(15).as_byte());
+    assert_eq!(0x80, Marker::FixMap(0).as_byte());
+}
+
+#[test]
+fn compare_with_raw_byte() {
+    const NULL: u8 = Marker::Null.as_byte();
+
+    assert_eq!(0xc0, NULL);
+    assert!(Marker::Null == 0xc0);
+    assert!(Marker::FixMap(1) != 0x82);
+    assert!(Marker::Reserved == 0xc1);
+}
+
+#[test]
+fn marker_bytes_match_spec() {
+    let expected = [
+        (marker_bytes::POSITIVE_FIXINT, 0x00),
+        (marker_bytes::FIXMAP, 0x80),
+        (marker_bytes::FIXARRAY, 0x90),
+        (marker_bytes::FIXSTR, 0xa0),
+        (marker_bytes::NIL, 0xc0),
+        (marker_bytes::NEVER_USED, 0xc1),
+        (marker_bytes::FALSE, 0xc2),
+        (marker_bytes::TRUE, 0xc3),
+        (marker_bytes::BIN8, 0xc4),
+        (marker_bytes::BIN16, 0xc5),
+        (marker_bytes::BIN32, 0xc6),
+        (marker_bytes::EXT8, 0xc7),
+        (marker_bytes::EXT16, 0xc8),
+        (marker_bytes::EXT32, 0xc9),
+        (marker_bytes::FLOAT32, 0xca),
+        (marker_bytes::FLOAT64, 0xcb),
+        (marker_bytes::UINT8, 0xcc),
+        (marker_bytes::UINT16, 0xcd),
+        (marker_bytes::UINT32, 0xce),
+        (marker_bytes::UINT64, 0xcf),
+        (marker_bytes::INT8, 0xd0),
+        (marker_bytes::INT16, 0xd1),
+        (marker_bytes::INT32, 0xd2),
+        (marker_bytes::INT64, 0xd3),
+        (marker_bytes::FIXEXT1, 0xd4),
+        (marker_bytes::FIXEXT2, 0xd5),
+        (marker_bytes::FIXEXT4, 0xd6),
+        (marker_bytes::FIXEXT8, 0xd7),
+        (marker_bytes::FIXEXT16, 0xd8),
+        (marker_bytes::STR8, 0xd9),
+        (marker_bytes::STR16, 0xda),
+        (marker_bytes::STR32, 0xdb),
+        (marker_bytes::ARRAY16, 0xdc),
+        (marker_bytes::ARRAY32, 0xdd),
+        (marker_bytes::MAP16, 0xde),
+        (marker_bytes::MAP32, 0xdf),
+        (marker_bytes::NEGATIVE_FIXINT, 0xe0),
+    ];
+    for &(byte, spec) in &expected {
+        assert_eq!(spec, byte);
+    }
+}
+
+#[test]
+fn marker_bytes_families_embed_lengths() {
+    assert_eq!(Marker::FixMap(3), Marker::from_u8(marker_bytes::FIXMAP | 3));
+    assert_eq!(Marker::FixArray(15), Marker::from_u8(marker_bytes::FIXARRAY | 15));
+    assert_eq!(Marker::FixStr(31), Marker::from_u8(marker_bytes::FIXSTR | 31));
+    assert_eq!(Marker::FixNeg(-32), Marker::from_u8(marker_bytes::NEGATIVE_FIXINT));
+}
@@ -22,7 +22,7 @@ quickcheck! {
         encode::write_f32(&mut buf, xs).unwrap();
 
         let res = decode::read_f32(&mut Bytes::new(buf.as_slice())).unwrap();
-        xs == res || (xs.is_nan() && res.is_nan())
+        xs.to_bits() == res.to_bits()
     }
 
     fn mirror_f64(xs: f64) -> bool {
@@ -30,6 +30,6 @@ quickcheck! {
         encode::write_f64(&mut buf, xs).expect("write");
 
         let res = decode::read_f64(&mut Bytes::new(buf.as_slice())).expect("read");
-        true || xs == res || (xs.is_nan() && res.is_nan())
+        xs.to_bits() == res.to_bits()
     }
 }
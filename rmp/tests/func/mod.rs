@@ -1,3 +1,7 @@
+#[cfg(feature = "async-tokio")]
+mod async_encode;
 mod decode;
 mod encode;
+mod frame;
+mod marker;
 mod mirror;
@@ -52,11 +52,13 @@ fn pass_sint() {
     test_decode(&[0xcf, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], ValueRef::from(i64::max_value()));
 }
 
+#[cfg(feature = "float")]
 #[test]
 fn pass_f32() {
     test_decode(&[0xca, 0x7f, 0x7f, 0xff, 0xff], ValueRef::from(3.4028234e38f32));
 }
 
+#[cfg(feature = "float")]
 #[test]
 fn pass_f64() {
     test_decode(&[0xcb, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], ValueRef::from(0.00));
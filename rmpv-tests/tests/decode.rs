@@ -7,7 +7,7 @@ use std::collections::BTreeMap;
 use serde_bytes::ByteBuf;
 
 use rmpv::decode;
-use rmpv::ext::from_value;
+use rmpv::ext::{deserialize_from, from_value};
 use rmpv::Value;
 
 /// Tests that a `Value` is properly decoded from bytes using two different mechanisms: direct
@@ -69,11 +69,13 @@ fn pass_sint() {
     test_decode(&[0xcf, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], Value::from(i64::max_value()));
 }
 
+#[cfg(feature = "float")]
 #[test]
 fn pass_f32() {
     test_decode(&[0xca, 0x7f, 0x7f, 0xff, 0xff], Value::from(3.4028234e38f32));
 }
 
+#[cfg(feature = "float")]
 #[test]
 fn pass_f64() {
     test_decode(&[0xcb, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], Value::from(0.00));
@@ -303,3 +305,89 @@ fn pass_tuple_struct_from_ext() {
     assert_eq!(ExtStruct(42, vec![255]),
         from_value(Value::Ext(42, vec![255])).unwrap());
 }
+
+#[test]
+fn pass_struct_from_value_ref() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Person<'a> {
+        name: &'a str,
+        #[serde(with = "serde_bytes")]
+        avatar: &'a [u8],
+        scores: BTreeMap<u32, Option<u8>>,
+    }
+
+    let val = Value::Map(vec![
+        (Value::from("name"), Value::from("John")),
+        (Value::from("avatar"), Value::Binary(vec![0xff, 0x00])),
+        (Value::from("scores"), Value::Map(vec![
+            (Value::from(1), Value::from(42)),
+            (Value::from(2), Value::Nil),
+        ])),
+    ]);
+
+    let mut scores = BTreeMap::new();
+    scores.insert(1, Some(42));
+    scores.insert(2, None);
+
+    let v: Person<'_> = serde::Deserialize::deserialize(&val).unwrap();
+    assert_eq!(Person { name: "John", avatar: &[0xff, 0x00], scores }, v);
+    // The value is still usable after extracting a typed fragment of it.
+    assert_eq!(Some("John"), val["name"].as_str());
+}
+
+#[test]
+fn pass_enum_from_value_ref() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Enum<'a> {
+        Unit,
+        Newtype(&'a str),
+        Tuple(&'a str, u32),
+        Struct { name: &'a str, age: u32 },
+    }
+
+    let vals = [
+        Value::Array(vec![Value::from(0), Value::Array(vec![])]),
+        Value::Array(vec![Value::from(1), Value::Array(vec![Value::from("John")])]),
+        Value::Array(vec![Value::from(2), Value::Array(vec![Value::from("John"), Value::from(42)])]),
+        Value::Array(vec![Value::from(3), Value::Map(vec![
+            (Value::from("name"), Value::from("John")),
+            (Value::from("age"), Value::from(42)),
+        ])]),
+    ];
+
+    assert_eq!(Enum::Unit, deserialize_from(&vals[0]).unwrap());
+    assert_eq!(Enum::Newtype("John"), deserialize_from(&vals[1]).unwrap());
+    assert_eq!(Enum::Tuple("John", 42), deserialize_from(&vals[2]).unwrap());
+    assert_eq!(Enum::Struct { name: "John", age: 42 }, deserialize_from(&vals[3]).unwrap());
+}
+
+#[test]
+fn pass_value_from_value_ref() {
+    let val = Value::Array(vec![
+        Value::Nil,
+        Value::from(-1),
+        Value::from(u64::MAX),
+        Value::from("le message"),
+        Value::Binary(vec![1, 2, 3]),
+        Value::Ext(42, vec![255]),
+        Value::Map(vec![
+            (Value::from(1), Value::from(true)),
+            (Value::Array(vec![Value::from(2)]), Value::Ext(-1, vec![])),
+        ]),
+    ]);
+
+    let v: Value = deserialize_from(&val).unwrap();
+    assert_eq!(val, v);
+}
+
+#[test]
+fn fail_struct_from_value_ref_type_mismatch() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Struct {
+        age: u8,
+    }
+
+    let val = Value::Map(vec![(Value::from("age"), Value::from("old"))]);
+    assert!(deserialize_from::<Struct, _>(&val).is_err());
+}
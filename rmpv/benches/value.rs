@@ -224,3 +224,46 @@ fn read_array_1mib(b: &mut Bencher) {
 fn read_array_20mib(b: &mut Bencher) {
     read_large_array(b, 20 * 1024 * 1024);
 }
+
+/// Encodes a stream of small maps like `{"id": 42, "name": "user42", "tags": ["a", "b"]}`.
+fn small_maps_stream(count: usize) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for i in 0..count {
+        let val = rmpv::Value::Map(vec![
+            (rmpv::Value::from("id"), rmpv::Value::from(i)),
+            (rmpv::Value::from("name"), rmpv::Value::from(format!("user{}", i))),
+            (rmpv::Value::from("tags"), rmpv::Value::Array(vec![rmpv::Value::from("a"), rmpv::Value::from("b")])),
+        ]);
+        rmpv::encode::write_value(&mut buf, &val).unwrap();
+    }
+    buf
+}
+
+#[bench]
+fn read_small_maps_stream_owned(b: &mut Bencher) {
+    let buf = small_maps_stream(1000);
+
+    b.iter(|| {
+        let mut rd = &buf[..];
+        while !rd.is_empty() {
+            test::black_box(read_value(&mut rd).unwrap());
+        }
+    });
+    b.bytes = buf.len() as u64;
+}
+
+#[cfg(feature = "bumpalo")]
+#[bench]
+fn read_small_maps_stream_arena(b: &mut Bencher) {
+    let buf = small_maps_stream(1000);
+    let mut arena = bumpalo::Bump::new();
+
+    b.iter(|| {
+        let mut rd = &buf[..];
+        while !rd.is_empty() {
+            test::black_box(arena::read_value_in(&mut rd, &arena).unwrap());
+        }
+        arena.reset();
+    });
+    b.bytes = buf.len() as u64;
+}
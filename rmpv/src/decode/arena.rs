@@ -0,0 +1,244 @@
+//! Decoding into a [bumpalo] arena, for many small values without per-value allocations.
+
+use std::cmp::min;
+use std::io::Read;
+use std::str;
+
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+use rmp::decode::{read_marker, RmpRead};
+use rmp::Marker;
+
+use super::Error;
+use crate::{Integer, Utf8StringRef, Value};
+
+// See https://github.com/3Hren/msgpack-rust/issues/151
+const PREALLOC_MAX: usize = 64 * 1024; // 64 KiB
+
+/// A value whose strings, binaries, arrays and maps are allocated in an arena.
+///
+/// Unlike [`ValueRef`](crate::ValueRef), arrays and maps are arena slices instead of `Vec`s, so
+/// decoding a value with [`read_value_in`] doesn't touch the global allocator once the arena has
+/// grown to fit a typical message. Resetting the arena with [`Bump::reset`] between messages
+/// reuses its memory; the borrow checker ensures that no `ArenaValue` outlives the reset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArenaValue<'a> {
+    /// Nil represents nil.
+    Nil,
+    /// Boolean represents true or false.
+    Boolean(bool),
+    /// Integer represents an integer.
+    Integer(Integer),
+    /// A 32-bit floating point number.
+    F32(f32),
+    /// A 64-bit floating point number.
+    F64(f64),
+    /// String extending Raw type represents a UTF-8 string.
+    String(Utf8StringRef<'a>),
+    /// Binary extending Raw type represents a byte array.
+    Binary(&'a [u8]),
+    /// Array represents a sequence of objects.
+    Array(&'a [ArenaValue<'a>]),
+    /// Map represents key-value pairs of objects.
+    Map(&'a [(ArenaValue<'a>, ArenaValue<'a>)]),
+    /// Extended implements Extension interface: represents a tuple of type information and a byte
+    /// array where type information is an integer whose meaning is defined by applications.
+    Ext(i8, &'a [u8]),
+}
+
+impl<'a> ArenaValue<'a> {
+    /// Converts the arena value to an owned [`Value`], deep copying all data out of the arena.
+    pub fn to_owned(&self) -> Value {
+        match *self {
+            ArenaValue::Nil => Value::Nil,
+            ArenaValue::Boolean(val) => Value::Boolean(val),
+            ArenaValue::Integer(val) => Value::Integer(val),
+            ArenaValue::F32(val) => Value::F32(val),
+            ArenaValue::F64(val) => Value::F64(val),
+            ArenaValue::String(val) => Value::String(val.into()),
+            ArenaValue::Binary(val) => Value::Binary(val.to_vec()),
+            ArenaValue::Array(val) => Value::Array(val.iter().map(|v| v.to_owned()).collect()),
+            ArenaValue::Map(val) => {
+                Value::Map(val.iter().map(|(k, v)| (k.to_owned(), v.to_owned())).collect())
+            }
+            ArenaValue::Ext(ty, buf) => Value::Ext(ty, buf.to_vec()),
+        }
+    }
+}
+
+fn read_array_data<'a, R: Read>(rd: &mut R, len: usize, depth: usize, arena: &'a Bump) -> Result<&'a [ArenaValue<'a>], Error> {
+    let depth = super::decrement_depth(depth)?;
+
+    // Note: Do not preallocate a Vec of size `len`.
+    // See https://github.com/3Hren/msgpack-rust/issues/151
+    let mut vec = BumpVec::with_capacity_in(min(len, PREALLOC_MAX / 32), arena);
+    for _ in 0..len {
+        vec.push(read_value_inner(rd, depth, arena)?);
+    }
+
+    Ok(vec.into_bump_slice())
+}
+
+fn read_map_data<'a, R: Read>(rd: &mut R, len: usize, depth: usize, arena: &'a Bump) -> Result<&'a [(ArenaValue<'a>, ArenaValue<'a>)], Error> {
+    let depth = super::decrement_depth(depth)?;
+
+    let mut vec = BumpVec::with_capacity_in(min(len, PREALLOC_MAX / 64), arena);
+    for _ in 0..len {
+        vec.push((read_value_inner(rd, depth, arena)?, read_value_inner(rd, depth, arena)?));
+    }
+
+    Ok(vec.into_bump_slice())
+}
+
+fn read_str_data<'a, R: Read>(rd: &mut R, len: usize, arena: &'a Bump) -> Result<Utf8StringRef<'a>, Error> {
+    let buf = read_bin_data(rd, len, arena)?;
+    let s = match str::from_utf8(buf) {
+        Ok(s) => Ok(s),
+        Err(err) => Err((buf, err)),
+    };
+
+    Ok(Utf8StringRef { s })
+}
+
+fn read_bin_data<'a, R: Read>(rd: &mut R, len: usize, arena: &'a Bump) -> Result<&'a [u8], Error> {
+    // The data is read in chunks, so that a bogus length can't make the arena grow past the
+    // actual input size.
+    let mut buf = BumpVec::with_capacity_in(min(len, PREALLOC_MAX), arena);
+    while buf.len() < len {
+        let start = buf.len();
+        buf.resize(start + min(len - start, PREALLOC_MAX), 0);
+        rd.read_exact(&mut buf[start..]).map_err(Error::InvalidDataRead)?;
+    }
+
+    Ok(buf.into_bump_slice())
+}
+
+fn read_ext_body<'a, R: Read>(rd: &mut R, len: usize, arena: &'a Bump) -> Result<ArenaValue<'a>, Error> {
+    let ty = rd.read_data_i8()?;
+    Ok(ArenaValue::Ext(ty, read_bin_data(rd, len, arena)?))
+}
+
+fn read_value_inner<'a, R: Read>(rd: &mut R, depth: usize, arena: &'a Bump) -> Result<ArenaValue<'a>, Error> {
+    let depth = super::decrement_depth(depth)?;
+    let val = match read_marker(rd)? {
+        Marker::Null => ArenaValue::Nil,
+        Marker::True => ArenaValue::Boolean(true),
+        Marker::False => ArenaValue::Boolean(false),
+        marker @ Marker::FixPos(..) |
+        marker @ Marker::FixNeg(..) |
+        marker @ Marker::U8 |
+        marker @ Marker::U16 |
+        marker @ Marker::U32 |
+        marker @ Marker::U64 |
+        marker @ Marker::I8 |
+        marker @ Marker::I16 |
+        marker @ Marker::I32 |
+        marker @ Marker::I64 => ArenaValue::Integer(super::read_integer(rd, marker)?),
+        #[cfg(feature = "float")]
+        Marker::F32 => ArenaValue::F32(rd.read_data_f32()?),
+        #[cfg(feature = "float")]
+        Marker::F64 => ArenaValue::F64(rd.read_data_f64()?),
+        #[cfg(not(feature = "float"))]
+        marker @ Marker::F32 |
+        marker @ Marker::F64 => return Err(Error::UnsupportedMarker(marker)),
+        Marker::FixStr(len) => ArenaValue::String(read_str_data(rd, len as usize, arena)?),
+        Marker::Str8 => {
+            let len = rd.read_data_u8()?;
+            ArenaValue::String(read_str_data(rd, len as usize, arena)?)
+        }
+        Marker::Str16 => {
+            let len = rd.read_data_u16()?;
+            ArenaValue::String(read_str_data(rd, len as usize, arena)?)
+        }
+        Marker::Str32 => {
+            let len = rd.read_data_u32()?;
+            ArenaValue::String(read_str_data(rd, len as usize, arena)?)
+        }
+        Marker::FixArray(len) => ArenaValue::Array(read_array_data(rd, len as usize, depth, arena)?),
+        Marker::Array16 => {
+            let len = rd.read_data_u16()?;
+            ArenaValue::Array(read_array_data(rd, len as usize, depth, arena)?)
+        }
+        Marker::Array32 => {
+            let len = rd.read_data_u32()?;
+            ArenaValue::Array(read_array_data(rd, len as usize, depth, arena)?)
+        }
+        Marker::FixMap(len) => ArenaValue::Map(read_map_data(rd, len as usize, depth, arena)?),
+        Marker::Map16 => {
+            let len = rd.read_data_u16()?;
+            ArenaValue::Map(read_map_data(rd, len as usize, depth, arena)?)
+        }
+        Marker::Map32 => {
+            let len = rd.read_data_u32()?;
+            ArenaValue::Map(read_map_data(rd, len as usize, depth, arena)?)
+        }
+        Marker::Bin8 => {
+            let len = rd.read_data_u8()?;
+            ArenaValue::Binary(read_bin_data(rd, len as usize, arena)?)
+        }
+        Marker::Bin16 => {
+            let len = rd.read_data_u16()?;
+            ArenaValue::Binary(read_bin_data(rd, len as usize, arena)?)
+        }
+        Marker::Bin32 => {
+            let len = rd.read_data_u32()?;
+            ArenaValue::Binary(read_bin_data(rd, len as usize, arena)?)
+        }
+        Marker::FixExt1 => read_ext_body(rd, 1, arena)?,
+        Marker::FixExt2 => read_ext_body(rd, 2, arena)?,
+        Marker::FixExt4 => read_ext_body(rd, 4, arena)?,
+        Marker::FixExt8 => read_ext_body(rd, 8, arena)?,
+        Marker::FixExt16 => read_ext_body(rd, 16, arena)?,
+        Marker::Ext8 => {
+            let len = rd.read_data_u8()? as usize;
+            read_ext_body(rd, len, arena)?
+        }
+        Marker::Ext16 => {
+            let len = rd.read_data_u16()? as usize;
+            read_ext_body(rd, len, arena)?
+        }
+        Marker::Ext32 => {
+            let len = rd.read_data_u32()? as usize;
+            read_ext_body(rd, len, arena)?
+        }
+        Marker::Reserved => return Err(Error::ReservedMarker),
+    };
+
+    Ok(val)
+}
+
+/// Attempts to read bytes from the given reader and interpret them as an [`ArenaValue`] allocated
+/// in `arena`.
+///
+/// # Errors
+///
+/// This function will return [`Error`] on any I/O error while either reading or decoding a value.
+/// Whatever was allocated in the arena before the error stays there until the arena is reset.
+///
+/// [`Error::DepthLimitExceeded`] is returned if this function recurses
+/// [`MAX_DEPTH`](super::MAX_DEPTH) times.
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::Bump;
+/// use rmpv::decode::arena::{read_value_in, ArenaValue};
+///
+/// let mut arena = Bump::new();
+/// for _ in 0..3 {
+///     // {"id": 42}
+///     let buf = [0x81, 0xa2, 0x69, 0x64, 0x2a];
+///     let val = read_value_in(&mut &buf[..], &arena).unwrap();
+///     match val {
+///         ArenaValue::Map(entries) => assert_eq!(1, entries.len()),
+///         other => panic!("unexpected value: {:?}", other),
+///     }
+///     arena.reset();
+/// }
+/// ```
+#[inline(never)]
+pub fn read_value_in<'a, R>(rd: &mut R, arena: &'a Bump) -> Result<ArenaValue<'a>, Error>
+    where R: Read
+{
+    read_value_inner(rd, super::MAX_DEPTH, arena)
+}
@@ -2,19 +2,30 @@ use std::error;
 use std::fmt::{self, Display, Formatter};
 use std::io::{self, ErrorKind};
 
-use rmp::decode::{MarkerReadError, ValueReadError};
+use rmp::decode::{MarkerReadError, RmpRead, ValueReadError};
+use rmp::Marker;
 
+use crate::Integer;
+
+#[cfg(feature = "bumpalo")]
+pub mod arena;
 pub mod value;
 pub mod value_ref;
 
-pub use self::value::{read_value, read_value_with_max_depth};
+pub use self::value::{
+    read_value, read_value_collect_errors, read_value_eof_aware, read_value_with_max_depth, read_value_with_registry,
+    CollectedErrors, ExtRegistry,
+};
 pub use self::value_ref::{read_value_ref, read_value_ref_with_max_depth};
 
 /// The maximum recursion depth before [`Error::DepthLimitExceeded`] is returned.
 pub const MAX_DEPTH: usize = 1024;
 
 /// This type represents all possible errors that can occur when deserializing a value.
+///
+/// New variants may be added, so matching on it requires a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Error while reading marker byte.
     InvalidMarkerRead(io::Error),
@@ -22,6 +33,36 @@ pub enum Error {
     InvalidDataRead(io::Error),
     /// The depth limit [`MAX_DEPTH`] was exceeded.
     DepthLimitExceeded,
+    /// The reserved `0xc1` marker, which the specification says is never used, was read.
+    ReservedMarker,
+    /// The input ended in the middle of a value.
+    ///
+    /// Returned by [`read_value_eof_aware`] instead of an `UnexpectedEof` I/O error, which it
+    /// reserves for the clean end of the input.
+    Truncated,
+    /// A value with the enclosed marker can't be decoded in this build.
+    ///
+    /// Returned for `F32` and `F64` when the `float` feature is disabled, without reading the float data.
+    UnsupportedMarker(Marker),
+}
+
+/// Reads the data of an integer after its marker, remembering the marker for re-encoding.
+fn read_integer<R: RmpRead>(rd: &mut R, marker: Marker) -> Result<Integer, ValueReadError<R::Error>> {
+    let n = match marker {
+        Marker::FixPos(val) => Integer::from(val),
+        Marker::FixNeg(val) => Integer::from(val),
+        Marker::U8 => Integer::from(rd.read_data_u8()?),
+        Marker::U16 => Integer::from(rd.read_data_u16()?),
+        Marker::U32 => Integer::from(rd.read_data_u32()?),
+        Marker::U64 => Integer::from(rd.read_data_u64()?),
+        Marker::I8 => Integer::from(rd.read_data_i8()?),
+        Marker::I16 => Integer::from(rd.read_data_i16()?),
+        Marker::I32 => Integer::from(rd.read_data_i32()?),
+        Marker::I64 => Integer::from(rd.read_data_i64()?),
+        marker => return Err(ValueReadError::TypeMismatch(marker)),
+    };
+
+    Ok(n.with_marker_unchecked(marker))
 }
 
 fn decrement_depth(depth: usize) -> Result<usize, Error> {
@@ -39,6 +80,9 @@ impl Error {
             Error::InvalidMarkerRead(ref err) => err.kind(),
             Error::InvalidDataRead(ref err) => err.kind(),
             Error::DepthLimitExceeded => ErrorKind::Unsupported,
+            Error::ReservedMarker => ErrorKind::InvalidData,
+            Error::Truncated => ErrorKind::UnexpectedEof,
+            Error::UnsupportedMarker(..) => ErrorKind::Unsupported,
         }
     }
 }
@@ -49,7 +93,10 @@ impl error::Error for Error {
         match *self {
             Error::InvalidMarkerRead(ref err) => Some(err),
             Error::InvalidDataRead(ref err) => Some(err),
-            Error::DepthLimitExceeded => None,
+            Error::DepthLimitExceeded |
+            Error::ReservedMarker |
+            Error::Truncated |
+            Error::UnsupportedMarker(..) => None,
         }
     }
 }
@@ -67,6 +114,15 @@ impl Display for Error {
             Error::DepthLimitExceeded => {
                 write!(fmt, "depth limit exceeded")
             }
+            Error::ReservedMarker => {
+                write!(fmt, "reserved marker 0xc1 is never used")
+            }
+            Error::Truncated => {
+                write!(fmt, "input ended in the middle of a value")
+            }
+            Error::UnsupportedMarker(marker) => {
+                write!(fmt, "unsupported marker {:?}", marker)
+            }
         }
     }
 }
@@ -97,7 +153,10 @@ impl Into<io::Error> for Error {
         match self {
             Error::InvalidMarkerRead(err) |
             Error::InvalidDataRead(err) => err,
-            Error::DepthLimitExceeded => io::Error::new(self.kind(), self),
+            Error::DepthLimitExceeded |
+            Error::ReservedMarker |
+            Error::Truncated |
+            Error::UnsupportedMarker(..) => io::Error::new(self.kind(), self),
         }
     }
 }
@@ -1,16 +1,89 @@
 use std::cmp::min;
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
 use std::io::{self, Read};
 
-use rmp::decode::{RmpRead, read_marker};
+use rmp::decode::{DecodeError, RmpRead, read_marker};
 use rmp::Marker;
 
 use super::Error;
+use crate::patch::PathSegment;
 use crate::{Utf8String, Value};
 
 // See https://github.com/3Hren/msgpack-rust/issues/151
 const PREALLOC_MAX: usize = 64 * 1024; // 64 KiB
 
-fn read_array_data<R: Read>(rd: &mut R, mut len: usize, depth: usize) -> Result<Vec<Value>, Error> {
+type ExtHandler = dyn Fn(&[u8]) -> Result<Value, io::Error> + Send + Sync;
+
+/// A set of per-type decoders for MessagePack extensions.
+///
+/// When used with [`read_value_with_registry`], every extension whose type has a registered
+/// handler is decoded by calling that handler with the extension payload, and the returned
+/// [`Value`] is stored in place of the raw [`Value::Ext`]. Extensions of other types are decoded
+/// as [`Value::Ext`] as usual.
+///
+/// # Examples
+///
+/// ```
+/// use rmpv::Value;
+/// use rmpv::decode::{read_value_with_registry, ExtRegistry};
+///
+/// let mut registry = ExtRegistry::new();
+/// registry.register(10, |data| Ok(Value::from(data.len())));
+///
+/// // [ext(10, [1, 2]), ext(11, [3])]
+/// let buf = [0x92, 0xd5, 0x0a, 0x01, 0x02, 0xd4, 0x0b, 0x03];
+///
+/// assert_eq!(
+///     Value::Array(vec![Value::from(2), Value::Ext(11, vec![3])]),
+///     read_value_with_registry(&mut &buf[..], &registry).unwrap()
+/// );
+/// ```
+#[derive(Default)]
+pub struct ExtRegistry {
+    handlers: HashMap<i8, Box<ExtHandler>>,
+}
+
+impl ExtRegistry {
+    /// Constructs an empty registry.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the handler for the given extension type, replacing the previous one if any.
+    ///
+    /// The handler receives the extension payload. An error returned from the handler is reported
+    /// as [`Error::InvalidDataRead`].
+    pub fn register<F>(&mut self, ty: i8, handler: F)
+        where F: Fn(&[u8]) -> Result<Value, io::Error> + Send + Sync + 'static
+    {
+        self.handlers.insert(ty, Box::new(handler));
+    }
+
+    /// Returns `true` if a handler is registered for the given extension type.
+    #[inline]
+    pub fn contains(&self, ty: i8) -> bool {
+        self.handlers.contains_key(&ty)
+    }
+
+    fn decode(&self, ty: i8, data: Vec<u8>) -> Result<Value, Error> {
+        match self.handlers.get(&ty) {
+            Some(handler) => handler(&data).map_err(Error::InvalidDataRead),
+            None => Ok(Value::Ext(ty, data)),
+        }
+    }
+}
+
+impl Debug for ExtRegistry {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        let mut types: Vec<_> = self.handlers.keys().collect();
+        types.sort();
+        fmt.debug_struct("ExtRegistry").field("types", &types).finish()
+    }
+}
+
+fn read_array_data<R: Read>(rd: &mut R, mut len: usize, depth: usize, registry: Option<&ExtRegistry>) -> Result<Vec<Value>, Error> {
     let depth = super::decrement_depth(depth)?;
 
     // Note: Do not preallocate a Vec of size `len`.
@@ -18,14 +91,14 @@ fn read_array_data<R: Read>(rd: &mut R, mut len: usize, depth: usize) -> Result<
     let mut vec = Vec::new();
 
     while len > 0 {
-        vec.push(read_value_inner(rd, depth)?);
+        vec.push(read_value_inner(rd, depth, registry)?);
         len -= 1;
     }
 
     Ok(vec)
 }
 
-fn read_map_data<R: Read>(rd: &mut R, mut len: usize, depth: usize) -> Result<Vec<(Value, Value)>, Error> {
+fn read_map_data<R: Read>(rd: &mut R, mut len: usize, depth: usize, registry: Option<&ExtRegistry>) -> Result<Vec<(Value, Value)>, Error> {
     let depth = super::decrement_depth(depth)?;
 
     // Note: Do not preallocate a Vec of size `len`.
@@ -33,7 +106,7 @@ fn read_map_data<R: Read>(rd: &mut R, mut len: usize, depth: usize) -> Result<Ve
     let mut vec = Vec::new();
 
     while len > 0 {
-        vec.push((read_value_inner(rd, depth)?, read_value_inner(rd, depth)?));
+        vec.push((read_value_inner(rd, depth, registry)?, read_value_inner(rd, depth, registry)?));
         len -= 1;
     }
 
@@ -70,33 +143,47 @@ fn read_bin_data<R: Read>(rd: &mut R, len: usize, depth: usize) -> Result<Vec<u8
     Ok(buf)
 }
 
-fn read_ext_body<R: Read>(rd: &mut R, len: usize, depth: usize) -> Result<(i8, Vec<u8>), Error> {
+fn read_ext_body<R: Read>(rd: &mut R, len: usize, depth: usize, registry: Option<&ExtRegistry>) -> Result<Value, Error> {
     let depth = super::decrement_depth(depth)?;
 
     let ty = rd.read_data_i8()?;
     let vec = read_bin_data(rd, len, depth)?;
 
-    Ok((ty, vec))
+    match registry {
+        Some(registry) => registry.decode(ty, vec),
+        None => Ok(Value::Ext(ty, vec)),
+    }
 }
 
-fn read_value_inner<R>(rd: &mut R, depth: usize) -> Result<Value, Error> where R: Read {
+fn read_value_inner<R>(rd: &mut R, depth: usize, registry: Option<&ExtRegistry>) -> Result<Value, Error> where R: Read {
     let depth = super::decrement_depth(depth)?;
-    let val = match read_marker(rd)? {
+    let marker = read_marker(rd)?;
+    read_value_data(rd, marker, depth, registry)
+}
+
+/// Reads the rest of a value after its marker.
+fn read_value_data<R>(rd: &mut R, marker: Marker, depth: usize, registry: Option<&ExtRegistry>) -> Result<Value, Error> where R: Read {
+    let val = match marker {
         Marker::Null => Value::Nil,
         Marker::True => Value::Boolean(true),
         Marker::False => Value::Boolean(false),
-        Marker::FixPos(val) => Value::from(val),
-        Marker::FixNeg(val) => Value::from(val),
-        Marker::U8 => Value::from(rd.read_data_u8()?),
-        Marker::U16 => Value::from(rd.read_data_u16()?),
-        Marker::U32 => Value::from(rd.read_data_u32()?),
-        Marker::U64 => Value::from(rd.read_data_u64()?),
-        Marker::I8 => Value::from(rd.read_data_i8()?),
-        Marker::I16 => Value::from(rd.read_data_i16()?),
-        Marker::I32 => Value::from(rd.read_data_i32()?),
-        Marker::I64 => Value::from(rd.read_data_i64()?),
+        marker @ Marker::FixPos(..) |
+        marker @ Marker::FixNeg(..) |
+        marker @ Marker::U8 |
+        marker @ Marker::U16 |
+        marker @ Marker::U32 |
+        marker @ Marker::U64 |
+        marker @ Marker::I8 |
+        marker @ Marker::I16 |
+        marker @ Marker::I32 |
+        marker @ Marker::I64 => Value::Integer(super::read_integer(rd, marker)?),
+        #[cfg(feature = "float")]
         Marker::F32 => Value::F32(rd.read_data_f32()?),
+        #[cfg(feature = "float")]
         Marker::F64 => Value::F64(rd.read_data_f64()?),
+        #[cfg(not(feature = "float"))]
+        marker @ Marker::F32 |
+        marker @ Marker::F64 => return Err(Error::UnsupportedMarker(marker)),
         Marker::FixStr(len) => {
             let res = read_str_data(rd, len as usize, depth)?;
             Value::String(res)
@@ -117,31 +204,31 @@ fn read_value_inner<R>(rd: &mut R, depth: usize) -> Result<Value, Error> where R
             Value::String(res)
         }
         Marker::FixArray(len) => {
-            let vec = read_array_data(rd, len as usize, depth)?;
+            let vec = read_array_data(rd, len as usize, depth, registry)?;
             Value::Array(vec)
         }
         Marker::Array16 => {
             let len = rd.read_data_u16()?;
-            let vec = read_array_data(rd, len as usize, depth)?;
+            let vec = read_array_data(rd, len as usize, depth, registry)?;
             Value::Array(vec)
         }
         Marker::Array32 => {
             let len = rd.read_data_u32()?;
-            let vec = read_array_data(rd, len as usize, depth)?;
+            let vec = read_array_data(rd, len as usize, depth, registry)?;
             Value::Array(vec)
         }
         Marker::FixMap(len) => {
-            let map = read_map_data(rd, len as usize, depth)?;
+            let map = read_map_data(rd, len as usize, depth, registry)?;
             Value::Map(map)
         }
         Marker::Map16 => {
             let len = rd.read_data_u16()?;
-            let map = read_map_data(rd, len as usize, depth)?;
+            let map = read_map_data(rd, len as usize, depth, registry)?;
             Value::Map(map)
         }
         Marker::Map32 => {
             let len = rd.read_data_u32()?;
-            let map = read_map_data(rd, len as usize, depth)?;
+            let map = read_map_data(rd, len as usize, depth, registry)?;
             Value::Map(map)
         }
         Marker::Bin8 => {
@@ -161,45 +248,37 @@ fn read_value_inner<R>(rd: &mut R, depth: usize) -> Result<Value, Error> where R
         }
         Marker::FixExt1 => {
             let len = 1_usize;
-            let (ty, vec) = read_ext_body(rd, len, depth)?;
-            Value::Ext(ty, vec)
+            read_ext_body(rd, len, depth, registry)?
         }
         Marker::FixExt2 => {
             let len = 2_usize;
-            let (ty, vec) = read_ext_body(rd, len, depth)?;
-            Value::Ext(ty, vec)
+            read_ext_body(rd, len, depth, registry)?
         }
         Marker::FixExt4 => {
             let len = 4_usize;
-            let (ty, vec) = read_ext_body(rd, len, depth)?;
-            Value::Ext(ty, vec)
+            read_ext_body(rd, len, depth, registry)?
         }
         Marker::FixExt8 => {
             let len = 8_usize;
-            let (ty, vec) = read_ext_body(rd, len, depth)?;
-            Value::Ext(ty, vec)
+            read_ext_body(rd, len, depth, registry)?
         }
         Marker::FixExt16 => {
             let len = 16_usize;
-            let (ty, vec) = read_ext_body(rd, len, depth)?;
-            Value::Ext(ty, vec)
+            read_ext_body(rd, len, depth, registry)?
         }
         Marker::Ext8 => {
             let len = rd.read_data_u8()? as usize;
-            let (ty, vec) = read_ext_body(rd, len, depth)?;
-            Value::Ext(ty, vec)
+            read_ext_body(rd, len, depth, registry)?
         }
         Marker::Ext16 => {
             let len = rd.read_data_u16()? as usize;
-            let (ty, vec) = read_ext_body(rd, len, depth)?;
-            Value::Ext(ty, vec)
+            read_ext_body(rd, len, depth, registry)?
         }
         Marker::Ext32 => {
             let len = rd.read_data_u32()? as usize;
-            let (ty, vec) = read_ext_body(rd, len, depth)?;
-            Value::Ext(ty, vec)
+            read_ext_body(rd, len, depth, registry)?
         }
-        Marker::Reserved => Value::Nil,
+        Marker::Reserved => return Err(Error::ReservedMarker),
     };
 
     Ok(val)
@@ -220,7 +299,58 @@ fn read_value_inner<R>(rd: &mut R, depth: usize) -> Result<Value, Error> where R
 pub fn read_value<R>(rd: &mut R) -> Result<Value, Error>
     where R: Read
 {
-    read_value_inner(rd, super::MAX_DEPTH)
+    read_value_inner(rd, super::MAX_DEPTH, None)
+}
+
+/// Attempts to read a [`Value`], like [`read_value`], returning `None` if the input ends before
+/// its first byte.
+///
+/// This distinguishes the clean end of a stream of values from a value that was cut off, without
+/// peeking at the reader: `Ok(None)` means that the reader was exhausted at a value boundary.
+///
+/// # Errors
+///
+/// Returns [`Error::Truncated`] if the input ends after the marker of the value but before its
+/// end, in addition to the errors returned by [`read_value`]. Other I/O errors are returned as is.
+///
+/// # Examples
+///
+/// ```
+/// use rmpv::Value;
+/// use rmpv::decode::{read_value_eof_aware, Error};
+///
+/// let mut rd = &[0x2a][..];
+/// assert_eq!(Some(Value::from(42)), read_value_eof_aware(&mut rd).unwrap());
+/// assert_eq!(None, read_value_eof_aware(&mut rd).unwrap());
+///
+/// // An array of two elements with only one present.
+/// let mut rd = &[0x92, 0x2a][..];
+/// match read_value_eof_aware(&mut rd) {
+///     Err(Error::Truncated) => {}
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+/// ```
+pub fn read_value_eof_aware<R>(rd: &mut R) -> Result<Option<Value>, Error>
+    where R: Read
+{
+    let mut marker = [0u8; 1];
+    loop {
+        match rd.read(&mut marker) {
+            Ok(0) => return Ok(None),
+            Ok(..) => break,
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(Error::InvalidMarkerRead(err)),
+        }
+    }
+
+    match read_value_inner(&mut (&marker[..]).chain(rd), super::MAX_DEPTH, None) {
+        Ok(val) => Ok(Some(val)),
+        Err(Error::InvalidMarkerRead(ref err)) |
+        Err(Error::InvalidDataRead(ref err)) if err.kind() == io::ErrorKind::UnexpectedEof => {
+            Err(Error::Truncated)
+        }
+        Err(err) => Err(err),
+    }
 }
 
 /// Attempts to read bytes from the given reader and interpret them as a [`Value`].
@@ -238,5 +368,167 @@ pub fn read_value<R>(rd: &mut R) -> Result<Value, Error>
 pub fn read_value_with_max_depth<R>(rd: &mut R, max_depth: usize) -> Result<Value, Error>
     where R: Read
 {
-    read_value_inner(rd, max_depth)
+    read_value_inner(rd, max_depth, None)
+}
+
+/// Attempts to read bytes from the given reader and interpret them as a [`Value`], decoding
+/// extensions with the handlers from the given [`ExtRegistry`].
+///
+/// # Errors
+///
+/// This function will return [`Error`] on any I/O error while either reading or decoding a [`Value`],
+/// including errors returned from the extension handlers.
+///
+/// [`Error::DepthLimitExceeded`] is returned if this function recurses
+/// [`MAX_DEPTH`](super::MAX_DEPTH) times.
+#[inline(never)]
+pub fn read_value_with_registry<R>(rd: &mut R, registry: &ExtRegistry) -> Result<Value, Error>
+    where R: Read
+{
+    read_value_inner(rd, super::MAX_DEPTH, Some(registry))
+}
+
+/// Skips `count` consecutive values without decoding them.
+fn skip_values<R: Read>(rd: &mut R, count: u64) -> Result<(), Error> {
+    for _ in 0..count {
+        rmp::decode::skip_value(rd, usize::MAX).map_err(|err| match err {
+            DecodeError::InvalidMarkerRead(err) => Error::InvalidMarkerRead(err),
+            DecodeError::TypeMismatch(Marker::Reserved) => Error::ReservedMarker,
+            err => Error::InvalidDataRead(err.into()),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// The errors recorded by [`read_value_collect_errors`], each with the path to the element that
+/// failed to decode.
+pub type CollectedErrors = Vec<(Vec<PathSegment>, Error)>;
+
+/// The state of [`read_value_collect_errors`]: the path to the value being read and the errors
+/// recorded so far.
+struct Collector {
+    path: Vec<PathSegment>,
+    errors: CollectedErrors,
+}
+
+impl Collector {
+    fn record(&mut self, err: Error) -> Value {
+        self.errors.push((self.path.clone(), err));
+        Value::Nil
+    }
+
+    fn read_value<R: Read>(&mut self, rd: &mut R, depth: usize) -> Result<Value, Error> {
+        let marker = read_marker(rd)?;
+
+        let (len, is_map) = match marker {
+            Marker::FixArray(len) => (u32::from(len), false),
+            Marker::Array16 => (u32::from(rd.read_data_u16()?), false),
+            Marker::Array32 => (rd.read_data_u32()?, false),
+            Marker::FixMap(len) => (u32::from(len), true),
+            Marker::Map16 => (u32::from(rd.read_data_u16()?), true),
+            Marker::Map32 => (rd.read_data_u32()?, true),
+            Marker::Reserved => return Ok(self.record(Error::ReservedMarker)),
+            #[cfg(not(feature = "float"))]
+            Marker::F32 => {
+                rd.skip_exact(4).map_err(Error::InvalidDataRead)?;
+                return Ok(self.record(Error::UnsupportedMarker(marker)));
+            }
+            #[cfg(not(feature = "float"))]
+            Marker::F64 => {
+                rd.skip_exact(8).map_err(Error::InvalidDataRead)?;
+                return Ok(self.record(Error::UnsupportedMarker(marker)));
+            }
+            // Scalars don't recurse, so the depth is only checked for containers.
+            marker => return read_value_data(rd, marker, super::MAX_DEPTH, None),
+        };
+
+        // Reading a container costs two levels of depth in `read_value`, one for the value and
+        // one for its elements, so the same documents are too deep here.
+        if depth < 2 {
+            let count = if is_map { u64::from(len) * 2 } else { u64::from(len) };
+            skip_values(rd, count)?;
+            return Ok(self.record(Error::DepthLimitExceeded));
+        }
+
+        // Note: Do not preallocate a Vec of size `len`.
+        // See https://github.com/3Hren/msgpack-rust/issues/151
+        if is_map {
+            let mut map = Vec::new();
+            for _ in 0..len {
+                self.path.push(PathSegment::Key(Value::Nil));
+                let key = self.read_value(rd, depth - 2)?;
+                self.path.pop();
+
+                self.path.push(PathSegment::Key(key));
+                let val = self.read_value(rd, depth - 2)?;
+                match self.path.pop() {
+                    Some(PathSegment::Key(key)) => map.push((key, val)),
+                    _ => unreachable!(),
+                }
+            }
+            Ok(Value::Map(map))
+        } else {
+            let mut vec = Vec::new();
+            for idx in 0..len as usize {
+                self.path.push(PathSegment::Index(idx));
+                vec.push(self.read_value(rd, depth - 2)?);
+                self.path.pop();
+            }
+            Ok(Value::Array(vec))
+        }
+    }
+}
+
+/// Attempts to read a [`Value`], recording the errors in its elements instead of failing on the
+/// first one.
+///
+/// This is a lenient mode for tools that report every malformed part of a document. An element
+/// that can't be decoded is skipped and replaced with [`Value::Nil`], and the error is returned
+/// along with the path to the element, in the format of the [`patch`](crate::patch) module. An
+/// error in a map key is reported at the path of its entry, with the key replaced by nil.
+///
+/// The recorded errors are [`Error::ReservedMarker`], [`Error::UnsupportedMarker`] with the
+/// `float` feature disabled, and [`Error::DepthLimitExceeded`] for a container nested too deep for
+/// [`read_value`], which is skipped as a whole without checking its contents. A reserved marker
+/// inside such a container can't be skipped, so it fails the whole read with
+/// [`Error::ReservedMarker`].
+///
+/// # Errors
+///
+/// I/O errors, including the end of the input in the middle of the value, can't be recovered from
+/// and are returned instead of the value.
+///
+/// # Examples
+///
+/// ```
+/// use rmpv::Value;
+/// use rmpv::decode::{read_value_collect_errors, Error};
+/// use rmpv::patch::PathSegment;
+///
+/// // {"a": [1, <reserved>], "b": 2}
+/// let buf = [0x82, 0xa1, 0x61, 0x92, 0x01, 0xc1, 0xa1, 0x62, 0x02];
+///
+/// let (val, errors) = read_value_collect_errors(&mut &buf[..]).unwrap();
+/// assert_eq!(
+///     Value::Map(vec![
+///         (Value::from("a"), Value::Array(vec![Value::from(1), Value::Nil])),
+///         (Value::from("b"), Value::from(2)),
+///     ]),
+///     val
+/// );
+///
+/// assert_eq!(1, errors.len());
+/// assert_eq!(vec![PathSegment::Key(Value::from("a")), PathSegment::Index(1)], errors[0].0);
+/// match errors[0].1 {
+///     Error::ReservedMarker => {}
+///     ref other => panic!("unexpected error: {:?}", other),
+/// }
+/// ```
+pub fn read_value_collect_errors<R>(rd: &mut R) -> Result<(Value, CollectedErrors), Error>
+    where R: Read
+{
+    let mut collector = Collector { path: Vec::new(), errors: Vec::new() };
+    let val = collector.read_value(rd, super::MAX_DEPTH)?;
+    Ok((val, collector.errors))
 }
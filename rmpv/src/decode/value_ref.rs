@@ -138,18 +138,23 @@ fn read_value_ref_inner<'a, R>(rd: &mut R, depth: usize) -> Result<ValueRef<'a>,
         Marker::Null => ValueRef::Nil,
         Marker::True => ValueRef::Boolean(true),
         Marker::False => ValueRef::Boolean(false),
-        Marker::FixPos(val) => ValueRef::from(val),
-        Marker::FixNeg(val) => ValueRef::from(val),
-        Marker::U8 => ValueRef::from(rd.read_data_u8()?),
-        Marker::U16 => ValueRef::from(rd.read_data_u16()?),
-        Marker::U32 => ValueRef::from(rd.read_data_u32()?),
-        Marker::U64 => ValueRef::from(rd.read_data_u64()?),
-        Marker::I8 => ValueRef::from(rd.read_data_i8()?),
-        Marker::I16 => ValueRef::from(rd.read_data_i16()?),
-        Marker::I32 => ValueRef::from(rd.read_data_i32()?),
-        Marker::I64 => ValueRef::from(rd.read_data_i64()?),
+        marker @ Marker::FixPos(..) |
+        marker @ Marker::FixNeg(..) |
+        marker @ Marker::U8 |
+        marker @ Marker::U16 |
+        marker @ Marker::U32 |
+        marker @ Marker::U64 |
+        marker @ Marker::I8 |
+        marker @ Marker::I16 |
+        marker @ Marker::I32 |
+        marker @ Marker::I64 => ValueRef::Integer(super::read_integer(rd, marker)?),
+        #[cfg(feature = "float")]
         Marker::F32 => ValueRef::F32(rd.read_data_f32()?),
+        #[cfg(feature = "float")]
         Marker::F64 => ValueRef::F64(rd.read_data_f64()?),
+        #[cfg(not(feature = "float"))]
+        marker @ Marker::F32 |
+        marker @ Marker::F64 => return Err(Error::UnsupportedMarker(marker)),
         Marker::FixStr(len) => {
             let res = read_str_data(rd, len as usize, depth)?;
             ValueRef::String(res)
@@ -252,7 +257,7 @@ fn read_value_ref_inner<'a, R>(rd: &mut R, depth: usize) -> Result<ValueRef<'a>,
             let (ty, vec) = read_ext_body(rd, len as usize, depth)?;
             ValueRef::Ext(ty, vec)
         }
-        Marker::Reserved => ValueRef::Nil,
+        Marker::Reserved => return Err(Error::ReservedMarker),
     };
 
     Ok(val)
@@ -1,21 +1,186 @@
 use std::io::Write;
+use std::slice;
 
 use rmp::encode::{
-    write_array_len, write_bin, write_bool, write_ext_meta, write_f32, write_f64, write_map_len,
-    write_nil, write_sint, write_str, write_uint,
+    write_array_len, write_bin, write_bool, write_ext_meta, write_f32, write_f64, write_i16,
+    write_i32, write_i64, write_i8, write_map_len, write_nfix, write_nil, write_pfix, write_sint,
+    write_str, write_u16, write_u32, write_u64, write_u8, write_uint,
 };
+use rmp::Marker;
 
 use super::Error;
 use crate::{IntPriv, Integer, Utf8String, Value};
 
+/// Selects how [`write_value_with`] chooses the marker of each integer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntPolicy {
+    /// Always use the smallest marker that fits the value, which is what [`write_value`] does.
+    ///
+    /// The output depends only on the values, so it's identical across runs and platforms. This
+    /// is the default.
+    Minimal,
+    /// Use the marker recorded on the integer by [`Integer::with_marker`] or by the decoder, see
+    /// [`Integer::marker`], falling back to the smallest one for integers without a marker.
+    ///
+    /// Decoding and re-encoding with this policy reproduces the original integer encoding.
+    Preserve,
+}
+
+impl Default for IntPolicy {
+    #[inline]
+    fn default() -> Self {
+        IntPolicy::Minimal
+    }
+}
+
+/// Options of [`write_value_with`].
+///
+/// The default options are those of [`write_value`]: [`IntPolicy::Minimal`] and no float
+/// narrowing. An [`IntPolicy`] converts into options with that policy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WriteOptions {
+    int_policy: IntPolicy,
+    allow_float_narrowing: bool,
+}
+
+impl WriteOptions {
+    /// Returns the default options.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Chooses integer markers with the given policy.
+    #[inline]
+    pub fn with_int_policy(mut self, policy: IntPolicy) -> Self {
+        self.int_policy = policy;
+        self
+    }
+
+    /// Allows `Value::F64` to be written with the 5-byte `f32` marker when that loses nothing.
+    ///
+    /// A value is narrowed only if converting it to `f32` and back gives the same bits, like
+    /// `0.5` or infinity, but not `0.1`, which stays `f64`. When disallowed, which is the default,
+    /// `Value::F64` is always written as `f64`. `Value::F32` is written as `f32` either way.
+    #[inline]
+    pub fn with_float_narrowing(mut self, allow: bool) -> Self {
+        self.allow_float_narrowing = allow;
+        self
+    }
+
+    /// Returns the policy for integer markers.
+    #[inline]
+    pub fn int_policy(&self) -> IntPolicy {
+        self.int_policy
+    }
+
+    /// Returns whether `Value::F64` may be narrowed to `f32`.
+    #[inline]
+    pub fn allow_float_narrowing(&self) -> bool {
+        self.allow_float_narrowing
+    }
+}
+
+impl From<IntPolicy> for WriteOptions {
+    #[inline]
+    fn from(policy: IntPolicy) -> Self {
+        WriteOptions::new().with_int_policy(policy)
+    }
+}
+
 /// Encodes and attempts to write the most efficient representation of the given Value.
 ///
+/// This is the same as [`write_value_with`] with the default [`WriteOptions`].
+///
 /// # Note
 ///
 /// All instances of `ErrorKind::Interrupted` are handled by this function and the underlying
 /// operation is retried.
+#[inline]
 pub fn write_value<W>(wr: &mut W, val: &Value) -> Result<(), Error>
     where W: Write
+{
+    write_value_with(wr, val, WriteOptions::new())
+}
+
+/// Encodes and attempts to write the given Value with the given options, or just an
+/// [`IntPolicy`] for choosing integer markers.
+///
+/// # Examples
+///
+/// ```
+/// use rmpv::encode::{write_value, write_value_with, IntPolicy, WriteOptions};
+/// use rmpv::Value;
+///
+/// // [1u64, 2u8]
+/// let buf = [0x92, 0xcf, 0, 0, 0, 0, 0, 0, 0, 0x01, 0xcc, 0x02];
+/// let val = rmpv::decode::read_value(&mut &buf[..]).unwrap();
+///
+/// let mut out = Vec::new();
+/// write_value_with(&mut out, &val, IntPolicy::Preserve).unwrap();
+/// assert_eq!(&buf[..], &out[..]);
+///
+/// let mut out = Vec::new();
+/// write_value(&mut out, &val).unwrap();
+/// assert_eq!([0x92, 0x01, 0x02], &out[..]);
+///
+/// let mut out = Vec::new();
+/// write_value_with(&mut out, &Value::F64(0.5), WriteOptions::new().with_float_narrowing(true)).unwrap();
+/// assert_eq!([0xca, 0x3f, 0x00, 0x00, 0x00], &out[..]);
+/// ```
+///
+/// # Note
+///
+/// All instances of `ErrorKind::Interrupted` are handled by this function and the underlying
+/// operation is retried.
+#[inline]
+pub fn write_value_with<W, O>(wr: &mut W, val: &Value, options: O) -> Result<(), Error>
+    where W: Write,
+          O: Into<WriteOptions>
+{
+    write_value_opts(wr, val, options.into())
+}
+
+/// The elements of a container that are still to be written.
+enum Pending<'a> {
+    Values(slice::Iter<'a, Value>),
+    Entries(slice::Iter<'a, (Value, Value)>),
+}
+
+/// Writes the value without recursion, keeping the containers being written on a heap stack, so
+/// the nesting depth is limited only by memory.
+fn write_value_opts<W>(wr: &mut W, val: &Value, options: WriteOptions) -> Result<(), Error>
+    where W: Write
+{
+    let mut stack = Vec::new();
+    write_head(wr, val, options, &mut stack)?;
+
+    loop {
+        let next = match stack.last_mut() {
+            Some(Pending::Values(iter)) => iter.next().map(|val| (val, None)),
+            Some(Pending::Entries(iter)) => iter.next().map(|(key, val)| (key, Some(val))),
+            None => return Ok(()),
+        };
+
+        match next {
+            Some((val, then)) => {
+                // The value of a map entry is written once the key, with anything nested in it,
+                // is done.
+                if let Some(then) = then {
+                    stack.push(Pending::Values(slice::from_ref(then).iter()));
+                }
+                write_head(wr, val, options, &mut stack)?;
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+}
+
+/// Writes a scalar value entirely, or the header of a container, pushing its elements on the stack.
+fn write_head<'a, W>(wr: &mut W, val: &'a Value, options: WriteOptions, stack: &mut Vec<Pending<'a>>) -> Result<(), Error>
+    where W: Write
 {
     match *val {
         Value::Nil => {
@@ -24,7 +189,10 @@ pub fn write_value<W>(wr: &mut W, val: &Value) -> Result<(), Error>
         Value::Boolean(val) => {
             write_bool(wr, val).map_err(Error::InvalidMarkerWrite)?;
         }
-        Value::Integer(Integer { n }) => {
+        Value::Integer(Integer { marker: Some(marker), n }) if options.int_policy == IntPolicy::Preserve => {
+            write_integer_as(wr, marker, n)?;
+        }
+        Value::Integer(Integer { n, .. }) => {
             match n {
                 IntPriv::PosInt(n) => {
                     write_uint(wr, n)?;
@@ -37,6 +205,9 @@ pub fn write_value<W>(wr: &mut W, val: &Value) -> Result<(), Error>
         Value::F32(val) => {
             write_f32(wr, val)?;
         }
+        Value::F64(val) if options.allow_float_narrowing && f64::from(val as f32).to_bits() == val.to_bits() => {
+            write_f32(wr, val as f32)?;
+        }
         Value::F64(val) => {
             write_f64(wr, val)?;
         }
@@ -51,16 +222,11 @@ pub fn write_value<W>(wr: &mut W, val: &Value) -> Result<(), Error>
         }
         Value::Array(ref vec) => {
             write_array_len(wr, vec.len() as u32)?;
-            for v in vec {
-                write_value(wr, v)?;
-            }
+            stack.push(Pending::Values(vec.iter()));
         }
         Value::Map(ref map) => {
             write_map_len(wr, map.len() as u32)?;
-            for &(ref key, ref val) in map {
-                write_value(wr, key)?;
-                write_value(wr, val)?;
-            }
+            stack.push(Pending::Entries(map.iter()));
         }
         Value::Ext(ty, ref data) => {
             write_ext_meta(wr, data.len() as u32, ty)?;
@@ -70,3 +236,27 @@ pub fn write_value<W>(wr: &mut W, val: &Value) -> Result<(), Error>
 
     Ok(())
 }
+
+/// Writes the integer with the given marker, which is known to fit it.
+fn write_integer_as<W: Write>(wr: &mut W, marker: Marker, n: IntPriv) -> Result<(), Error> {
+    let (u, i) = match n {
+        IntPriv::PosInt(n) => (n, n as i64),
+        IntPriv::NegInt(n) => (n as u64, n),
+    };
+
+    match marker {
+        Marker::FixPos(..) => write_pfix(wr, u as u8).map_err(Error::InvalidMarkerWrite)?,
+        Marker::FixNeg(..) => write_nfix(wr, i as i8).map_err(Error::InvalidMarkerWrite)?,
+        Marker::U8 => write_u8(wr, u as u8)?,
+        Marker::U16 => write_u16(wr, u as u16)?,
+        Marker::U32 => write_u32(wr, u as u32)?,
+        Marker::U64 => write_u64(wr, u)?,
+        Marker::I8 => write_i8(wr, i as i8)?,
+        Marker::I16 => write_i16(wr, i as i16)?,
+        Marker::I32 => write_i32(wr, i as i32)?,
+        Marker::I64 => write_i64(wr, i)?,
+        _ => unreachable!("non-integer marker {:?} on an integer", marker),
+    }
+
+    Ok(())
+}
@@ -36,7 +36,7 @@ pub fn write_value_ref<W>(wr: &mut W, val: &ValueRef<'_>) -> Result<(), Error>
         ValueRef::Boolean(val) => {
             write_bool(wr, val).map_err(Error::InvalidMarkerWrite)?;
         }
-        ValueRef::Integer(Integer { n }) => {
+        ValueRef::Integer(Integer { n, .. }) => {
             match n {
                 IntPriv::PosInt(n) => {
                     write_uint(wr, n)?;
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 use std::fmt::{self, Display, Formatter};
-use std::iter::ExactSizeIterator;
+use std::iter::{ExactSizeIterator, Map};
 use std::slice::Iter;
 use std::vec::IntoIter;
 
@@ -314,7 +314,7 @@ impl<'de> Deserializer<'de> for Value {
         match self {
             Value::Nil => visitor.visit_unit(),
             Value::Boolean(v) => visitor.visit_bool(v),
-            Value::Integer(Integer { n }) => {
+            Value::Integer(Integer { n, .. }) => {
                 match n {
                     IntPriv::PosInt(v) => visitor.visit_u64(v),
                     IntPriv::NegInt(v) => visitor.visit_i64(v)
@@ -403,6 +403,108 @@ impl<'de> Deserializer<'de> for Value {
     }
 }
 
+/// Deserializer for a borrowed `Value`, without cloning it.
+///
+/// Strings and binaries are borrowed from the value, so `T::deserialize(&val)` can produce `&str`
+/// and `&[u8]` fields, and the value can still be inspected afterwards.
+impl<'de> Deserializer<'de> for &'de Value {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        match *self {
+            Value::Nil => visitor.visit_unit(),
+            Value::Boolean(v) => visitor.visit_bool(v),
+            Value::Integer(Integer { n, .. }) => {
+                match n {
+                    IntPriv::PosInt(v) => visitor.visit_u64(v),
+                    IntPriv::NegInt(v) => visitor.visit_i64(v)
+                }
+            }
+            Value::F32(v) => visitor.visit_f32(v),
+            Value::F64(v) => visitor.visit_f64(v),
+            Value::String(ref v) => {
+                match v.s {
+                    Ok(ref v) => visitor.visit_borrowed_str(v),
+                    Err(ref v) => visitor.visit_borrowed_bytes(&v.0),
+                }
+            }
+            Value::Binary(ref v) => visitor.visit_borrowed_bytes(v),
+            Value::Array(ref v) => {
+                let len = v.len();
+                let mut de = SeqDeserializer::new(v.iter());
+                let seq = visitor.visit_seq(&mut de)?;
+                if de.iter.len() == 0 {
+                    Ok(seq)
+                } else {
+                    Err(de::Error::invalid_length(len, &"fewer elements in array"))
+                }
+            }
+            Value::Map(ref v) => {
+                let len = v.len();
+                let mut de = MapDeserializer::new(v.iter().map(split_entry as SplitEntry<'de>));
+                let map = visitor.visit_map(&mut de)?;
+                if de.iter.len() == 0 {
+                    Ok(map)
+                } else {
+                    Err(de::Error::invalid_length(len, &"fewer elements in map"))
+                }
+            }
+            Value::Ext(tag, ref data) => {
+                let de = ExtDeserializer::new_ref(tag, data);
+                visitor.visit_newtype_struct(de)
+            }
+        }
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        ValueBase::deserialize_option(self, visitor)
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(self, _name: &str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        ValueBase::deserialize_enum(self, visitor)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        if name == MSGPACK_EXT_STRUCT_NAME {
+            match *self {
+                Value::Ext(tag, ref data) => {
+                    let ext_de = ExtDeserializer::new_ref(tag, data);
+                    return visitor.visit_newtype_struct(ext_de);
+                }
+                ref other => {
+                    return Err(de::Error::invalid_type(other.unexpected(), &"expected Ext"))
+                }
+            }
+        }
+
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        ValueBase::deserialize_unit_struct(self, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string unit seq
+        bytes byte_buf map tuple_struct struct
+        identifier tuple ignored_any
+    }
+}
+
 impl<'de> Deserializer<'de> for ValueRef<'de> {
     type Error = Error;
 
@@ -413,7 +515,7 @@ impl<'de> Deserializer<'de> for ValueRef<'de> {
         match self {
             ValueRef::Nil => visitor.visit_unit(),
             ValueRef::Boolean(v) => visitor.visit_bool(v),
-            ValueRef::Integer(Integer { n }) => {
+            ValueRef::Integer(Integer { n, .. }) => {
                 match n {
                     IntPriv::PosInt(v) => visitor.visit_u64(v),
                     IntPriv::NegInt(v) => visitor.visit_i64(v)
@@ -512,7 +614,7 @@ impl<'de> Deserializer<'de> for &'de ValueRef<'de> {
         match *self {
             ValueRef::Nil => visitor.visit_unit(),
             ValueRef::Boolean(v) => visitor.visit_bool(v),
-            ValueRef::Integer(Integer { n }) => {
+            ValueRef::Integer(Integer { n, .. }) => {
                 match n {
                     IntPriv::PosInt(v) => visitor.visit_u64(v),
                     IntPriv::NegInt(v) => visitor.visit_i64(v)
@@ -1244,3 +1346,40 @@ impl<'de> ValueBase<'de> for ValueRef<'de> {
         }
     }
 }
+
+/// Map entries of a borrowed `Value`, split into a key and a value that are deserialized one by one.
+type SplitEntry<'de> = fn(&'de (Value, Value)) -> (&'de Value, &'de Value);
+type SplitEntries<'de> = Map<Iter<'de, (Value, Value)>, SplitEntry<'de>>;
+
+#[inline]
+fn split_entry(entry: &(Value, Value)) -> (&Value, &Value) {
+    (&entry.0, &entry.1)
+}
+
+impl<'de> ValueBase<'de> for &'de Value {
+    type Item = &'de Value;
+    type Iter = Iter<'de, Value>;
+    type MapIter = SplitEntries<'de>;
+    type MapDeserializer = MapDeserializer<Self::MapIter, Self::Item>;
+
+    #[inline]
+    fn is_nil(&self) -> bool {
+        matches!(**self, Value::Nil)
+    }
+
+    #[inline]
+    fn into_iter(self) -> Result<Self::Iter, Self::Item> {
+        match *self {
+            Value::Array(ref v) => Ok(v.iter()),
+            ref other => Err(other)
+        }
+    }
+
+    #[inline]
+    fn into_map_iter(self) -> Result<Self::MapIter, Self::Item> {
+        match *self {
+            Value::Map(ref v) => Ok(v.iter().map(split_entry as SplitEntry<'de>)),
+            ref other => Err(other)
+        }
+    }
+}
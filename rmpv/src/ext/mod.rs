@@ -37,7 +37,7 @@ impl ValueExt for Value {
         match *self {
             Value::Nil => Unexpected::Unit,
             Value::Boolean(v) => Unexpected::Bool(v),
-            Value::Integer(Integer { n }) => {
+            Value::Integer(Integer { n, .. }) => {
                 match n {
                     IntPriv::PosInt(v) => Unexpected::Unsigned(v),
                     IntPriv::NegInt(v) => Unexpected::Signed(v),
@@ -59,13 +59,20 @@ impl ValueExt for Value {
     }
 }
 
+impl ValueExt for &Value {
+    #[cold]
+    fn unexpected(&self) -> Unexpected<'_> {
+        (**self).unexpected()
+    }
+}
+
 impl<'a> ValueExt for ValueRef<'a> {
     #[cold]
     fn unexpected(&self) -> Unexpected<'_> {
         match *self {
             ValueRef::Nil => Unexpected::Unit,
             ValueRef::Boolean(v) => Unexpected::Bool(v),
-            ValueRef::Integer(Integer { n }) => {
+            ValueRef::Integer(Integer { n, .. }) => {
                 match n {
                     IntPriv::PosInt(v) => Unexpected::Unsigned(v),
                     IntPriv::NegInt(v) => Unexpected::Signed(v),
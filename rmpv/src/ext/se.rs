@@ -18,7 +18,7 @@ impl Serialize for Value {
         match *self {
             Value::Nil => s.serialize_unit(),
             Value::Boolean(v) => s.serialize_bool(v),
-            Value::Integer(Integer { n }) => {
+            Value::Integer(Integer { n, .. }) => {
                 match n {
                     IntPriv::PosInt(n) => s.serialize_u64(n),
                     IntPriv::NegInt(n) => s.serialize_i64(n),
@@ -12,19 +12,25 @@ extern crate serde;
 use std::borrow::Cow;
 use std::convert::TryFrom;
 use std::fmt::{self, Debug, Display};
+use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
+use std::mem;
 use std::ops::Index;
 use std::str::Utf8Error;
 
 use num_traits::NumCast;
+use rmp::Marker;
 
 pub mod decode;
 pub mod encode;
+pub mod patch;
+
+pub use crate::patch::{apply_patch, diff, Patch};
 
 #[cfg(feature = "with-serde")]
 pub mod ext;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 enum IntPriv {
     /// Always non-less than zero.
     PosInt(u64),
@@ -50,12 +56,133 @@ pub const MSGPACK_EXT_STRUCT_NAME: &str = "_ExtStruct";
 /// Represents a MessagePack integer, whether signed or unsigned.
 ///
 /// A `Value` or `ValueRef` that contains integer can be constructed using `From` trait.
-#[derive(Copy, Clone, PartialEq)]
+///
+/// An integer may also carry the marker it was encoded with, see [`Integer::marker`]. It doesn't
+/// take part in comparisons or hashing, `1` read from a `U8` equals `1` read from an `I64`.
+#[derive(Copy, Clone)]
 pub struct Integer {
     n: IntPriv,
+    marker: Option<Marker>,
+}
+
+impl PartialEq for Integer {
+    #[inline]
+    fn eq(&self, other: &Integer) -> bool {
+        self.n == other.n
+    }
+}
+
+impl Eq for Integer {}
+
+impl Hash for Integer {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.n.hash(state);
+    }
 }
 
 impl Integer {
+    /// Returns the marker this integer was decoded from, or `None` if it was constructed directly.
+    ///
+    /// The encoder uses it with [`IntPolicy::Preserve`](encode::IntPolicy::Preserve) to reproduce
+    /// the original width.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmp::Marker;
+    /// use rmpv::{Integer, Value};
+    ///
+    /// match rmpv::decode::read_value(&mut &[0xcd, 0x00, 0x2a][..]).unwrap() {
+    ///     Value::Integer(n) => assert_eq!(Some(Marker::U16), n.marker()),
+    ///     other => panic!("unexpected value: {:?}", other),
+    /// }
+    ///
+    /// assert_eq!(None, Integer::from(42).marker());
+    /// ```
+    #[inline]
+    pub fn marker(&self) -> Option<Marker> {
+        self.marker
+    }
+
+    /// Returns `true` if this integer was decoded from the signed family of markers, `I8` to
+    /// `I64`, or has one of them attached with [`Integer::with_marker`].
+    ///
+    /// Fixints belong to neither family, so both this and [`Integer::was_unsigned`] return `false`
+    /// for them, as well as for integers without a marker. Unlike [`Integer::is_i64`] this
+    /// tells how the value was written, not what it fits into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// // u64(1), i64(1)
+    /// let mut rd = &[0xcf, 0, 0, 0, 0, 0, 0, 0, 0x01, 0xd3, 0, 0, 0, 0, 0, 0, 0, 0x01][..];
+    /// let unsigned = rmpv::decode::read_value(&mut rd).unwrap();
+    /// let signed = rmpv::decode::read_value(&mut rd).unwrap();
+    ///
+    /// assert_eq!(unsigned, signed);
+    /// assert!(unsigned.as_integer().unwrap().was_unsigned());
+    /// assert!(signed.as_integer().unwrap().was_signed());
+    /// ```
+    #[inline]
+    pub fn was_signed(&self) -> bool {
+        matches!(self.marker, Some(Marker::I8) | Some(Marker::I16) | Some(Marker::I32) | Some(Marker::I64))
+    }
+
+    /// Returns `true` if this integer was decoded from the unsigned family of markers, `U8` to
+    /// `U64`, or has one of them attached with [`Integer::with_marker`].
+    ///
+    /// See [`Integer::was_signed`].
+    #[inline]
+    pub fn was_unsigned(&self) -> bool {
+        matches!(self.marker, Some(Marker::U8) | Some(Marker::U16) | Some(Marker::U32) | Some(Marker::U64))
+    }
+
+    /// Attaches the marker to encode this integer with under
+    /// [`IntPolicy::Preserve`](encode::IntPolicy::Preserve).
+    ///
+    /// Returns `None` if the value doesn't fit the marker, or if it isn't an integer marker.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmp::Marker;
+    /// use rmpv::Integer;
+    ///
+    /// assert_eq!(Some(Marker::U64), Integer::from(1).with_marker(Marker::U64).unwrap().marker());
+    /// assert!(Integer::from(300).with_marker(Marker::U8).is_none());
+    /// assert!(Integer::from(-1).with_marker(Marker::U32).is_none());
+    /// ```
+    pub fn with_marker(self, marker: Marker) -> Option<Integer> {
+        let fits = match (marker, self.n) {
+            (Marker::FixPos(..), IntPriv::PosInt(n)) => n <= 0x7f,
+            (Marker::U8, IntPriv::PosInt(n)) => n <= u8::MAX as u64,
+            (Marker::U16, IntPriv::PosInt(n)) => n <= u16::MAX as u64,
+            (Marker::U32, IntPriv::PosInt(n)) => n <= u32::MAX as u64,
+            (Marker::U64, IntPriv::PosInt(..)) => true,
+            (Marker::FixNeg(..), IntPriv::NegInt(n)) => n >= -32,
+            (Marker::I8, _) => self.as_i64().and_then(|n| i8::try_from(n).ok()).is_some(),
+            (Marker::I16, _) => self.as_i64().and_then(|n| i16::try_from(n).ok()).is_some(),
+            (Marker::I32, _) => self.as_i64().and_then(|n| i32::try_from(n).ok()).is_some(),
+            (Marker::I64, _) => self.is_i64(),
+            _ => false,
+        };
+
+        if fits {
+            Some(self.with_marker_unchecked(marker))
+        } else {
+            None
+        }
+    }
+
+    /// Records the marker an integer was decoded from, which always fits its value.
+    #[inline]
+    pub(crate) fn with_marker_unchecked(self, marker: Marker) -> Integer {
+        Integer { marker: Some(marker), ..self }
+    }
+
     /// Returns `true` if the integer can be represented as `i64`.
     #[inline]
     pub fn is_i64(&self) -> bool {
@@ -120,35 +247,35 @@ impl Display for Integer {
 impl From<u8> for Integer {
     #[inline]
     fn from(n: u8) -> Self {
-        Integer { n: IntPriv::PosInt(n as u64) }
+        Integer { marker: None, n: IntPriv::PosInt(n as u64) }
     }
 }
 
 impl From<u16> for Integer {
     #[inline]
     fn from(n: u16) -> Self {
-        Integer { n: IntPriv::PosInt(n as u64) }
+        Integer { marker: None, n: IntPriv::PosInt(n as u64) }
     }
 }
 
 impl From<u32> for Integer {
     #[inline]
     fn from(n: u32) -> Self {
-        Integer { n: IntPriv::PosInt(n as u64) }
+        Integer { marker: None, n: IntPriv::PosInt(n as u64) }
     }
 }
 
 impl From<u64> for Integer {
     #[inline]
     fn from(n: u64) -> Self {
-        Integer { n: IntPriv::PosInt(n as u64) }
+        Integer { marker: None, n: IntPriv::PosInt(n as u64) }
     }
 }
 
 impl From<usize> for Integer {
     #[inline]
     fn from(n: usize) -> Self {
-        Integer { n: IntPriv::PosInt(n as u64) }
+        Integer { marker: None, n: IntPriv::PosInt(n as u64) }
     }
 }
 
@@ -156,9 +283,9 @@ impl From<i8> for Integer {
     #[inline]
     fn from(n: i8) -> Self {
         if n < 0 {
-            Integer { n: IntPriv::NegInt(n as i64) }
+            Integer { marker: None, n: IntPriv::NegInt(n as i64) }
         } else {
-            Integer { n: IntPriv::PosInt(n as u64) }
+            Integer { marker: None, n: IntPriv::PosInt(n as u64) }
         }
     }
 }
@@ -167,9 +294,9 @@ impl From<i16> for Integer {
     #[inline]
     fn from(n: i16) -> Self {
         if n < 0 {
-            Integer { n: IntPriv::NegInt(n as i64) }
+            Integer { marker: None, n: IntPriv::NegInt(n as i64) }
         } else {
-            Integer { n: IntPriv::PosInt(n as u64) }
+            Integer { marker: None, n: IntPriv::PosInt(n as u64) }
         }
     }
 }
@@ -178,9 +305,9 @@ impl From<i32> for Integer {
     #[inline]
     fn from(n: i32) -> Self {
         if n < 0 {
-            Integer { n: IntPriv::NegInt(n as i64) }
+            Integer { marker: None, n: IntPriv::NegInt(n as i64) }
         } else {
-            Integer { n: IntPriv::PosInt(n as u64) }
+            Integer { marker: None, n: IntPriv::PosInt(n as u64) }
         }
     }
 }
@@ -189,9 +316,9 @@ impl From<i64> for Integer {
     #[inline]
     fn from(n: i64) -> Self {
         if n < 0 {
-            Integer { n: IntPriv::NegInt(n as i64) }
+            Integer { marker: None, n: IntPriv::NegInt(n as i64) }
         } else {
-            Integer { n: IntPriv::PosInt(n as u64) }
+            Integer { marker: None, n: IntPriv::PosInt(n as u64) }
         }
     }
 }
@@ -200,9 +327,9 @@ impl From<isize> for Integer {
     #[inline]
     fn from(n: isize) -> Self {
         if n < 0 {
-            Integer { n: IntPriv::NegInt(n as i64) }
+            Integer { marker: None, n: IntPriv::NegInt(n as i64) }
         } else {
-            Integer { n: IntPriv::PosInt(n as u64) }
+            Integer { marker: None, n: IntPriv::PosInt(n as u64) }
         }
     }
 }
@@ -219,11 +346,18 @@ impl From<isize> for Integer {
 /// it is possible to obtain an underlying bytes that were attempted to convert to a `String`. This
 /// may happen when trying to unpack strings that were decoded using older MessagePack spec with
 /// raw types instead of string/binary.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Utf8String {
     s: Result<String, (Vec<u8>, Utf8Error)>,
 }
 
+impl Hash for Utf8String {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.is_str().hash(state);
+        self.as_bytes().hash(state);
+    }
+}
+
 impl Utf8String {
     /// Returns `true` if the string is valid UTF-8.
     #[inline]
@@ -548,6 +682,24 @@ impl Value {
         self.as_bool().is_some()
     }
 
+    /// Returns true if the `Value` is an Integer, no matter its sign or width. Returns false
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// assert!(Value::from(42).is_integer());
+    /// assert!(Value::from(u64::max_value()).is_integer());
+    ///
+    /// assert!(!Value::F64(42.0).is_integer());
+    /// ```
+    #[inline]
+    pub fn is_integer(&self) -> bool {
+        self.as_integer().is_some()
+    }
+
     /// Returns true if the `Value` is convertible to an i64. Returns false otherwise.
     ///
     /// # Examples
@@ -668,9 +820,19 @@ impl Value {
     }
 
     /// Returns true if the `Value` is a Binary. Returns false otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// assert!(Value::Binary(vec![1, 2]).is_bin());
+    ///
+    /// assert!(!Value::String("le message".into()).is_bin());
+    /// ```
     #[inline]
     pub fn is_bin(&self) -> bool {
-        self.as_slice().is_some()
+        self.as_bin().is_some()
     }
 
     /// Returns true if the `Value` is an Array. Returns false otherwise.
@@ -691,6 +853,29 @@ impl Value {
         self.as_ext().is_some()
     }
 
+    /// If the `Value` is a Null, returns `()`. Returns None otherwise.
+    ///
+    /// This is the counterpart of [`is_nil`](Value::is_nil), for use with `?` and `Option`
+    /// combinators.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// assert_eq!(Some(()), Value::Nil.as_nil());
+    ///
+    /// assert_eq!(None, Value::Boolean(false).as_nil());
+    /// ```
+    #[inline]
+    pub fn as_nil(&self) -> Option<()> {
+        if let Value::Nil = *self {
+            Some(())
+        } else {
+            None
+        }
+    }
+
     /// If the `Value` is a Boolean, returns the associated bool.
     /// Returns None otherwise.
     ///
@@ -712,6 +897,29 @@ impl Value {
         }
     }
 
+    /// If the `Value` is an Integer, returns it. Returns None otherwise.
+    ///
+    /// Unlike [`as_i64`](Value::as_i64) and [`as_u64`](Value::as_u64) this succeeds for every
+    /// integer, and the marker it was decoded with is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::{Integer, Value};
+    ///
+    /// assert_eq!(Some(Integer::from(-42)), Value::from(-42).as_integer());
+    ///
+    /// assert_eq!(None, Value::F32(42.0).as_integer());
+    /// ```
+    #[inline]
+    pub fn as_integer(&self) -> Option<Integer> {
+        if let Value::Integer(n) = *self {
+            Some(n)
+        } else {
+            None
+        }
+    }
+
     /// If the `Value` is an integer, return or cast it to a i64.
     /// Returns None otherwise.
     ///
@@ -753,6 +961,30 @@ impl Value {
         }
     }
 
+    /// If (and only if) the `Value` is a f32, returns it. Returns None otherwise.
+    ///
+    /// Integers and f64 values are not converted, because that could lose precision. Use
+    /// [`as_f64`](Value::as_f64) to accept any number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// assert_eq!(Some(42.0), Value::F32(42.0).as_f32());
+    ///
+    /// assert_eq!(None, Value::F64(42.0).as_f32());
+    /// assert_eq!(None, Value::from(42).as_f32());
+    /// ```
+    #[inline]
+    pub fn as_f32(&self) -> Option<f32> {
+        if let Value::F32(n) = *self {
+            Some(n)
+        } else {
+            None
+        }
+    }
+
     /// If the `Value` is a number, return or cast it to a f64.
     /// Returns None otherwise.
     ///
@@ -799,6 +1031,27 @@ impl Value {
         }
     }
 
+    /// If (and only if) the `Value` is a Binary, returns the associated slice.
+    /// Returns None otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// assert_eq!(Some(&[1, 2, 3][..]), Value::Binary(vec![1, 2, 3]).as_bin());
+    ///
+    /// assert_eq!(None, Value::String("le message".into()).as_bin());
+    /// ```
+    #[inline]
+    pub fn as_bin(&self) -> Option<&[u8]> {
+        if let Value::Binary(ref val) = *self {
+            Some(val)
+        } else {
+            None
+        }
+    }
+
     /// If the `Value` is a Binary or a String, returns the associated slice.
     /// Returns None otherwise.
     ///
@@ -891,37 +1144,484 @@ impl Value {
             None
         }
     }
+
+    /// If the `Value` is an Array, returns the element at the given position.
+    /// Returns None if the index is out of bounds or the `Value` is not an Array.
+    ///
+    /// This is the non-defaulting counterpart of indexing with `value[index]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// let val = Value::Array(vec![Value::from(42)]);
+    ///
+    /// assert_eq!(Some(&Value::from(42)), val.get_index(0));
+    /// assert_eq!(None, val.get_index(1));
+    /// assert_eq!(None, Value::Nil.get_index(0));
+    /// ```
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<&Value> {
+        self.as_array().and_then(|array| array.get(index))
+    }
+
+    /// If the `Value` is a Map, returns the value of the first entry with the given string key.
+    /// Returns None if there is no such key or the `Value` is not a Map.
+    ///
+    /// This is the non-defaulting counterpart of indexing with `value["key"]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// let val = Value::Map(vec![(Value::from("key"), Value::from(42))]);
+    ///
+    /// assert_eq!(Some(&Value::from(42)), val.get("key"));
+    /// assert_eq!(None, val.get("nope"));
+    /// assert_eq!(None, Value::Nil.get("key"));
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_map()?
+            .iter()
+            .find(|(k, _)| k.as_str() == Some(key))
+            .map(|(_, v)| v)
+    }
+
+    /// Returns an iterator over the key-value pairs if the `Value` is a Map.
+    /// Yields nothing otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// let val = Value::Map(vec![(Value::from("a"), Value::from(1)), (Value::from("b"), Value::from(2))]);
+    ///
+    /// let keys: Vec<&str> = val.entries().filter_map(|(k, _)| k.as_str()).collect();
+    /// assert_eq!(vec!["a", "b"], keys);
+    ///
+    /// assert_eq!(0, Value::Nil.entries().count());
+    /// ```
+    #[inline]
+    pub fn entries(&self) -> std::slice::Iter<'_, (Value, Value)> {
+        match *self {
+            Value::Map(ref map) => map.iter(),
+            _ => [].iter(),
+        }
+    }
+
+    /// If the `Value` is a String with valid UTF-8, returns a mutable reference to its contents.
+    /// Returns None otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// let mut val = Value::from("le message");
+    /// val.as_str_mut().unwrap().make_ascii_uppercase();
+    ///
+    /// assert_eq!(Some("LE MESSAGE"), val.as_str());
+    /// assert_eq!(None, Value::Nil.as_str_mut());
+    /// ```
+    #[inline]
+    pub fn as_str_mut(&mut self) -> Option<&mut str> {
+        if let Value::String(ref mut val) = *self {
+            val.s.as_mut().ok().map(|s| s.as_mut_str())
+        } else {
+            None
+        }
+    }
+
+    /// If the `Value` is a Binary or a String, returns a mutable reference to its byte contents.
+    /// Returns None otherwise.
+    ///
+    /// # Note
+    ///
+    /// Only binaries and strings with invalid UTF-8 can be modified this way. Use
+    /// [`Value::as_str_mut`] for valid strings, because writing arbitrary bytes into them could
+    /// break the UTF-8 invariant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// let mut val = Value::Binary(vec![1, 2, 3]);
+    /// val.as_slice_mut().unwrap()[0] = 42;
+    ///
+    /// assert_eq!(Some(&[42, 2, 3][..]), val.as_slice());
+    /// assert_eq!(None, Value::from("valid").as_slice_mut());
+    /// ```
+    #[inline]
+    pub fn as_slice_mut(&mut self) -> Option<&mut [u8]> {
+        match *self {
+            Value::Binary(ref mut val) => Some(val),
+            Value::String(Utf8String { s: Err((ref mut val, _)) }) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// If the `Value` is an Array, returns a mutable reference to the associated vector.
+    /// Returns None otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// let mut val = Value::Array(vec![Value::Nil]);
+    /// val.as_array_mut().unwrap().push(Value::Boolean(true));
+    ///
+    /// assert_eq!(Some(&vec![Value::Nil, Value::Boolean(true)]), val.as_array());
+    /// assert_eq!(None, Value::Nil.as_array_mut());
+    /// ```
+    #[inline]
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<Value>> {
+        if let Value::Array(ref mut array) = *self {
+            Some(array)
+        } else {
+            None
+        }
+    }
+
+    /// If the `Value` is a Map, returns a mutable reference to the associated vector of key-value
+    /// tuples.
+    /// Returns None otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// let mut val = Value::Map(vec![]);
+    /// val.as_map_mut().unwrap().push((Value::Nil, Value::Boolean(true)));
+    ///
+    /// assert_eq!(Some(&vec![(Value::Nil, Value::Boolean(true))]), val.as_map());
+    /// assert_eq!(None, Value::Nil.as_map_mut());
+    /// ```
+    #[inline]
+    pub fn as_map_mut(&mut self) -> Option<&mut Vec<(Value, Value)>> {
+        if let Value::Map(ref mut map) = *self {
+            Some(map)
+        } else {
+            None
+        }
+    }
+
+    /// If the `Value` is an Ext, returns the associated tuple with a ty and a mutable slice.
+    /// Returns None otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// let mut val = Value::Ext(42, vec![1, 2]);
+    /// val.as_ext_mut().unwrap().1[1] = 3;
+    ///
+    /// assert_eq!(Some((42, &[1, 3][..])), val.as_ext());
+    /// assert_eq!(None, Value::Nil.as_ext_mut());
+    /// ```
+    #[inline]
+    pub fn as_ext_mut(&mut self) -> Option<(i8, &mut [u8])> {
+        if let Value::Ext(ty, ref mut buf) = *self {
+            Some((ty, buf))
+        } else {
+            None
+        }
+    }
+
+    /// Mutable counterpart of [`Value::get_index`].
+    #[inline]
+    pub fn get_index_mut(&mut self, index: usize) -> Option<&mut Value> {
+        self.as_array_mut().and_then(|array| array.get_mut(index))
+    }
+
+    /// Mutable counterpart of [`Value::get`].
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.as_map_mut()?
+            .iter_mut()
+            .find(|(k, _)| k.as_str() == Some(key))
+            .map(|(_, v)| v)
+    }
+
+    /// If the `Value` is a Map, returns a mutable reference to the value of the first entry with
+    /// the given string key, appending a new entry with a `Nil` value if there is no such key.
+    /// Returns None if the `Value` is not a Map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// let mut val = Value::Map(vec![(Value::from("a"), Value::from(1))]);
+    ///
+    /// *val.entry("a").unwrap() = Value::from(2);
+    /// *val.entry("b").unwrap() = Value::from(3);
+    ///
+    /// assert_eq!(Value::Map(vec![
+    ///     (Value::from("a"), Value::from(2)),
+    ///     (Value::from("b"), Value::from(3)),
+    /// ]), val);
+    ///
+    /// assert_eq!(None, Value::Nil.entry("a"));
+    /// ```
+    pub fn entry(&mut self, key: &str) -> Option<&mut Value> {
+        let map = self.as_map_mut()?;
+        let idx = match map.iter().position(|(k, _)| k.as_str() == Some(key)) {
+            Some(idx) => idx,
+            None => {
+                map.push((Value::from(key), Value::Nil));
+                map.len() - 1
+            }
+        };
+
+        Some(&mut map[idx].1)
+    }
+
+    /// If the `Value` is a Map, removes the first entry with the given string key, returning its
+    /// value.
+    /// Returns None if there is no such key or the `Value` is not a Map.
+    ///
+    /// The order of the remaining entries is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// let mut val = Value::Map(vec![(Value::from("password"), Value::from("hunter2"))]);
+    ///
+    /// assert_eq!(Some(Value::from("hunter2")), val.remove("password"));
+    /// assert_eq!(None, val.remove("password"));
+    /// assert_eq!(Value::Map(vec![]), val);
+    /// ```
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        let map = self.as_map_mut()?;
+        let idx = map.iter().position(|(k, _)| k.as_str() == Some(key))?;
+
+        Some(map.remove(idx).1)
+    }
+
+    /// Takes the value out, leaving `Value::Nil` in its place.
+    ///
+    /// This allows moving a nested value out of a tree without cloning it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::Value;
+    ///
+    /// let mut val = Value::Array(vec![Value::from("le message"), Value::from(42)]);
+    ///
+    /// let msg = val.get_index_mut(0).unwrap().take();
+    /// assert_eq!(Value::from("le message"), msg);
+    /// assert_eq!(Value::Array(vec![Value::Nil, Value::from(42)]), val);
+    /// ```
+    #[inline]
+    pub fn take(&mut self) -> Value {
+        mem::replace(self, Value::Nil)
+    }
+}
+
+/// Consistent with `PartialEq`: negative and positive zero hash the same.
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match *self {
+            Value::Nil => {}
+            Value::Boolean(val) => val.hash(state),
+            Value::Integer(val) => val.hash(state),
+            Value::F32(val) => (if val == 0.0 { 0.0 } else { val }).to_bits().hash(state),
+            Value::F64(val) => (if val == 0.0 { 0.0 } else { val }).to_bits().hash(state),
+            Value::String(ref val) => val.hash(state),
+            Value::Binary(ref val) => val.hash(state),
+            Value::Array(ref val) => val.hash(state),
+            Value::Map(ref val) => val.hash(state),
+            Value::Ext(ty, ref data) => {
+                ty.hash(state);
+                data.hash(state);
+            }
+        }
+    }
+}
+
+/// A `Value` usable as a key of hash-based maps and sets.
+///
+/// `Value` itself is not `Eq`, because its floats compare by value and NaN is not equal to
+/// itself. `ValueKey` compares and hashes floats by their bits instead, recursively, so every key
+/// equals itself and can be found again. As a consequence `-0.0` and `0.0` are different keys,
+/// and so are NaNs with different payloads. Everything else compares like `Value`.
+///
+/// It wraps either an owned `Value` or a reference to one.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+///
+/// use rmpv::{Value, ValueKey};
+///
+/// let mut set = HashSet::new();
+/// set.insert(ValueKey(Value::from(f64::NAN)));
+///
+/// assert!(set.contains(&ValueKey(Value::from(f64::NAN))));
+/// assert!(!set.contains(&ValueKey(Value::from(-0.0))));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ValueKey<V = Value>(pub V);
+
+impl<V: AsRef<Value>> PartialEq for ValueKey<V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        key_eq(self.0.as_ref(), other.0.as_ref())
+    }
+}
+
+impl<V: AsRef<Value>> Eq for ValueKey<V> {}
+
+impl<V: AsRef<Value>> Hash for ValueKey<V> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        key_hash(self.0.as_ref(), state);
+    }
+}
+
+impl AsRef<Value> for Value {
+    #[inline]
+    fn as_ref(&self) -> &Value {
+        self
+    }
+}
+
+fn key_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::F32(a), Value::F32(b)) => a.to_bits() == b.to_bits(),
+        (Value::F64(a), Value::F64(b)) => a.to_bits() == b.to_bits(),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| key_eq(a, b))
+        }
+        (Value::Map(a), Value::Map(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|((ak, av), (bk, bv))| key_eq(ak, bk) && key_eq(av, bv))
+        }
+        (Value::F32(..), _) | (Value::F64(..), _) | (Value::Array(..), _) | (Value::Map(..), _) => false,
+        (a, b) => a == b,
+    }
+}
+
+fn key_hash<H: Hasher>(val: &Value, state: &mut H) {
+    match *val {
+        Value::F32(v) => {
+            mem::discriminant(val).hash(state);
+            v.to_bits().hash(state);
+        }
+        Value::F64(v) => {
+            mem::discriminant(val).hash(state);
+            v.to_bits().hash(state);
+        }
+        Value::Array(ref vec) => {
+            mem::discriminant(val).hash(state);
+            vec.len().hash(state);
+            for v in vec {
+                key_hash(v, state);
+            }
+        }
+        Value::Map(ref map) => {
+            mem::discriminant(val).hash(state);
+            map.len().hash(state);
+            for (k, v) in map {
+                key_hash(k, state);
+                key_hash(v, state);
+            }
+        }
+        _ => val.hash(state),
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl Value {
+    /// If the `Value` is a Map, returns an ordered map view of it with constant-time keyed lookup.
+    ///
+    /// The keys are wrapped in [`ValueKey`], so float keys, including NaN, are matched by their
+    /// bits. The entry order is preserved. If a key occurs more than once, the last value wins
+    /// while the key keeps the position of its first occurrence. Returns `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rmpv::{Value, ValueKey};
+    ///
+    /// let val = Value::Map(vec![
+    ///     (Value::from("b"), Value::from(1)),
+    ///     (Value::from("a"), Value::from(2)),
+    /// ]);
+    /// let map = val.as_index_map().unwrap();
+    ///
+    /// assert_eq!(Some(&&Value::from(2)), map.get(&ValueKey(&Value::from("a"))));
+    /// assert_eq!(vec!["b", "a"], map.keys().map(|k| k.0.as_str().unwrap()).collect::<Vec<_>>());
+    ///
+    /// assert_eq!(None, Value::Nil.as_index_map());
+    /// ```
+    pub fn as_index_map(&self) -> Option<indexmap::IndexMap<ValueKey<&Value>, &Value>> {
+        self.as_map().map(|map| map.iter().map(|(k, v)| (ValueKey(k), v)).collect())
+    }
+
+    /// If the `Value` is a Map, converts it into an ordered map with constant-time keyed lookup.
+    ///
+    /// Keys and duplicates are handled like in [`Value::as_index_map`]. Returns `None` otherwise.
+    pub fn into_index_map(self) -> Option<indexmap::IndexMap<ValueKey, Value>> {
+        match self {
+            Value::Map(map) => Some(map.into_iter().map(|(k, v)| (ValueKey(k), v)).collect()),
+            _ => None,
+        }
+    }
 }
 
 static NIL: Value = Value::Nil;
 static NIL_REF: ValueRef<'static> = ValueRef::Nil;
 
+/// Indexes into an Array.
+///
+/// Unlike slices, this never panics: `Value::Nil` is returned if the index is out of bounds or
+/// the `Value` is not an Array, which allows chaining like `value[0][1]`. Use
+/// [`Value::get_index`] to tell a missing element apart from a present `Nil`.
 impl Index<usize> for Value {
     type Output = Value;
 
     fn index(&self, index: usize) -> &Value {
-        self.as_array().and_then(|v| v.get(index)).unwrap_or(&NIL)
+        self.get_index(index).unwrap_or(&NIL)
     }
 }
 
+/// Indexes into a Map with string keys.
+///
+/// This never panics: `Value::Nil` is returned if the key is not present or the `Value` is not a
+/// Map, which allows chaining like `value["a"]["b"]`. Use [`Value::get`] to tell a missing key
+/// apart from a present `Nil`.
 impl Index<&str> for Value {
     type Output = Value;
+
     fn index(&self, index: &str) -> &Value {
-        if let Value::Map(ref map) = *self {
-            if let Some(found) = map.iter().find(
-                |(key, _val)|  {
-                if let Value::String(ref strval) = *key {
-                    if let Some(s) = strval.as_str() {
-                        if s == index { return true; }
-                    }
-                }
-                false
-                })
-            {
-                return &found.1;
-            }
+        self.get(index).unwrap_or(&NIL)
+    }
+}
+
+/// Iterates over the elements of an Array. Yields nothing for other kinds of `Value`.
+///
+/// Use [`Value::entries`] to iterate over the entries of a Map.
+impl<'a> IntoIterator for &'a Value {
+    type Item = &'a Value;
+    type IntoIter = std::slice::Iter<'a, Value>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        match *self {
+            Value::Array(ref array) => array.iter(),
+            _ => [].iter(),
         }
-        &NIL
     }
 }
 
@@ -988,6 +1688,8 @@ impl From<i32> for Value {
     }
 }
 
+/// Non-negative values are stored like unsigned ones, so `Value::from(5i64)` equals
+/// `Value::from(5u8)` and both are encoded as a positive fixint.
 impl From<i64> for Value {
     #[inline]
     fn from(v: i64) -> Self {
@@ -1072,6 +1774,14 @@ impl From<Vec<(Value, Value)>> for Value {
     }
 }
 
+#[cfg(feature = "indexmap")]
+impl From<indexmap::IndexMap<ValueKey, Value>> for Value {
+    #[inline]
+    fn from(v: indexmap::IndexMap<ValueKey, Value>) -> Self {
+        Value::Map(v.into_iter().map(|(k, v)| (k.0, v)).collect())
+    }
+}
+
 /// Note that an `Iterator<Item = u8>` will be collected into an
 /// [`Array`](crate::Value::Array), rather than a
 /// [`Binary`](crate::Value::Binary)
@@ -1083,6 +1793,17 @@ where V: Into<Value> {
   }
 }
 
+/// Collects key-value pairs into a [`Map`](crate::Value::Map), keeping their order and any
+/// duplicate keys.
+impl<K, V> FromIterator<(K, V)> for Value
+    where K: Into<Value>,
+          V: Into<Value>
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Value::Map(iter.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
 impl TryFrom<Value> for u64 {
   type Error = Value;
 
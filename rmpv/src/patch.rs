@@ -0,0 +1,363 @@
+//! Structural differences between two values, for sending only what changed.
+//!
+//! [`diff`] compares two values and returns a [`Patch`], a list of operations at paths into the
+//! old value. [`apply_patch`] performs these operations, turning the old value into the new one.
+//!
+//! # Examples
+//!
+//! ```
+//! use rmpv::Value;
+//! use rmpv::patch::{apply_patch, diff, PatchOp, PathSegment};
+//!
+//! let old = Value::Map(vec![
+//!     (Value::from("id"), Value::from(42)),
+//!     (Value::from("tags"), Value::Array(vec![Value::from("a"), Value::from("c")])),
+//! ]);
+//! let new = Value::Map(vec![
+//!     (Value::from("id"), Value::from(42)),
+//!     (Value::from("tags"), Value::Array(vec![Value::from("a"), Value::from("b"), Value::from("c")])),
+//! ]);
+//!
+//! let patch = diff(&old, &new);
+//! assert_eq!(
+//!     &[PatchOp::Add {
+//!         path: vec![PathSegment::Key(Value::from("tags")), PathSegment::Index(1)],
+//!         value: Value::from("b"),
+//!     }],
+//!     patch.ops()
+//! );
+//!
+//! let mut val = old.clone();
+//! apply_patch(&mut val, &patch).unwrap();
+//! assert_eq!(new, val);
+//! ```
+
+use std::error;
+use std::fmt::{self, Display, Formatter};
+
+use crate::Value;
+
+/// Arrays with more elements than this in the changed range are not aligned element by element,
+/// because the alignment takes quadratic time and memory.
+const MAX_ALIGN_LEN: usize = 1024;
+
+/// A step of a path into a value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathSegment {
+    /// The value of the first map entry with this key, of any type.
+    Key(Value),
+    /// The array element at this position.
+    Index(usize),
+}
+
+/// An operation of a [`Patch`].
+///
+/// Paths are resolved against the value as it is when the operation is applied, so array indices
+/// account for elements added or removed by the operations before.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PatchOp {
+    /// Inserts an array element at the index ending the path, or appends a map entry with the key
+    /// ending the path.
+    Add {
+        /// Path of the new element or entry.
+        path: Vec<PathSegment>,
+        /// The added value.
+        value: Value,
+    },
+    /// Removes the array element or the map entry at the path.
+    Remove {
+        /// Path of the removed element or entry.
+        path: Vec<PathSegment>,
+    },
+    /// Replaces the value at the path, which is the whole value if the path is empty.
+    Replace {
+        /// Path of the replaced value.
+        path: Vec<PathSegment>,
+        /// The new value.
+        value: Value,
+    },
+}
+
+impl PatchOp {
+    /// Returns the path of the operation.
+    pub fn path(&self) -> &[PathSegment] {
+        match *self {
+            PatchOp::Add { ref path, .. } |
+            PatchOp::Remove { ref path } |
+            PatchOp::Replace { ref path, .. } => path,
+        }
+    }
+}
+
+/// A list of operations turning a value into another, as returned by [`diff`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Patch {
+    ops: Vec<PatchOp>,
+}
+
+impl Patch {
+    /// Constructs a patch performing the given operations in order.
+    pub fn new(ops: Vec<PatchOp>) -> Self {
+        Patch { ops }
+    }
+
+    /// Returns the operations in the order they are applied.
+    pub fn ops(&self) -> &[PatchOp] {
+        &self.ops
+    }
+
+    /// Returns `true` if the patch doesn't change anything.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Consumes the patch, returning its operations.
+    pub fn into_ops(self) -> Vec<PatchOp> {
+        self.ops
+    }
+}
+
+impl From<Vec<PatchOp>> for Patch {
+    fn from(ops: Vec<PatchOp>) -> Self {
+        Patch::new(ops)
+    }
+}
+
+/// This type represents errors that can occur when applying a patch.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PatchError {
+    /// The operation at the enclosed position in the patch has a path that doesn't exist in the
+    /// value, or that goes through a value which is neither an array nor a map.
+    PathNotFound(usize),
+}
+
+impl error::Error for PatchError {}
+
+impl Display for PatchError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match *self {
+            PatchError::PathNotFound(idx) => write!(fmt, "path of patch operation {} not found", idx),
+        }
+    }
+}
+
+/// Computes the operations turning `old` into `new`.
+///
+/// Maps are compared by key, with keys of any type. Changed values are diffed recursively, and
+/// removed and added entries become `Remove` and `Add` operations. If the entries that are kept
+/// would end up in a different order than in `new`, or either map has duplicate keys, the whole
+/// map is replaced instead, so that applying the patch always reproduces `new` exactly.
+///
+/// Arrays are aligned by their longest common subsequence of equal elements, so an inserted or
+/// removed element doesn't shift all elements after it. Elements in between the aligned ones are
+/// diffed pairwise. Very long changed ranges are diffed pairwise without alignment.
+///
+/// Values of different types and differing scalars are replaced whole. Note that a NaN is never
+/// equal to itself, so it always produces a `Replace`.
+pub fn diff(old: &Value, new: &Value) -> Patch {
+    let mut ops = Vec::new();
+    diff_into(&mut Vec::new(), old, new, &mut ops);
+    Patch::new(ops)
+}
+
+fn diff_into(path: &mut Vec<PathSegment>, old: &Value, new: &Value, ops: &mut Vec<PatchOp>) {
+    if old == new {
+        return;
+    }
+
+    match (old, new) {
+        (Value::Array(old), Value::Array(new)) => diff_array(path, old, new, ops),
+        (Value::Map(old), Value::Map(new)) if can_diff_map(old, new) => diff_map(path, old, new, ops),
+        (_, new) => ops.push(PatchOp::Replace { path: path.clone(), value: new.clone() }),
+    }
+}
+
+fn diff_array(path: &mut Vec<PathSegment>, old: &[Value], new: &[Value], ops: &mut Vec<PatchOp>) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // Index in the array being patched. Everything before it is already equal to `new`, and
+    // everything from it onwards are the remaining elements of `old`.
+    let mut idx = prefix;
+    let (mut i, mut j) = (0, 0);
+    for (a, b) in align(old_mid, new_mid) {
+        diff_gap(path, &old_mid[i..a], &new_mid[j..b], &mut idx, ops);
+        idx += 1;
+        i = a + 1;
+        j = b + 1;
+    }
+    diff_gap(path, &old_mid[i..], &new_mid[j..], &mut idx, ops);
+}
+
+/// Returns the positions of equal elements in the longest common subsequence of both slices.
+fn align(old: &[Value], new: &[Value]) -> Vec<(usize, usize)> {
+    if old.is_empty() || new.is_empty() || old.len() > MAX_ALIGN_LEN || new.len() > MAX_ALIGN_LEN {
+        return Vec::new();
+    }
+
+    // lens[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+    let width = new.len() + 1;
+    let mut lens = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lens[i * width + j] = if old[i] == new[j] {
+                lens[(i + 1) * width + j + 1] + 1
+            } else {
+                lens[(i + 1) * width + j].max(lens[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lens[(i + 1) * width + j] >= lens[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// Diffs a range of unaligned elements pairwise, removing or adding the excess ones.
+fn diff_gap(path: &mut Vec<PathSegment>, old: &[Value], new: &[Value], idx: &mut usize, ops: &mut Vec<PatchOp>) {
+    for (a, b) in old.iter().zip(new) {
+        path.push(PathSegment::Index(*idx));
+        diff_into(path, a, b, ops);
+        path.pop();
+        *idx += 1;
+    }
+    for _ in new.len()..old.len() {
+        let mut path = path.clone();
+        path.push(PathSegment::Index(*idx));
+        ops.push(PatchOp::Remove { path });
+    }
+    for val in new.iter().skip(old.len()) {
+        let mut path = path.clone();
+        path.push(PathSegment::Index(*idx));
+        ops.push(PatchOp::Add { path, value: val.clone() });
+        *idx += 1;
+    }
+}
+
+fn find<'a>(entries: &'a [(Value, Value)], key: &Value) -> Option<&'a Value> {
+    entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+fn has_duplicate_keys(entries: &[(Value, Value)]) -> bool {
+    entries.iter().enumerate().any(|(i, (key, _))| entries[..i].iter().any(|(k, _)| k == key))
+}
+
+/// Checks whether the key is equal to itself, which fails for NaN anywhere inside it.
+#[allow(clippy::eq_op)]
+fn is_self_equal(key: &Value) -> bool {
+    key == key
+}
+
+/// Checks whether removing and appending entries of `old` yields the keys of `new` in order.
+///
+/// A key that isn't equal to itself, like NaN, can't be found by a path, so its map is replaced
+/// as a whole.
+fn can_diff_map(old: &[(Value, Value)], new: &[(Value, Value)]) -> bool {
+    if old.iter().chain(new).any(|(k, _)| !is_self_equal(k)) {
+        return false;
+    }
+    if has_duplicate_keys(old) || has_duplicate_keys(new) {
+        return false;
+    }
+
+    let kept = old.iter().filter(|(k, _)| find(new, k).is_some()).map(|(k, _)| k);
+    let added = new.iter().filter(|(k, _)| find(old, k).is_none()).map(|(k, _)| k);
+    kept.chain(added).eq(new.iter().map(|(k, _)| k))
+}
+
+fn diff_map(path: &mut Vec<PathSegment>, old: &[(Value, Value)], new: &[(Value, Value)], ops: &mut Vec<PatchOp>) {
+    for (key, val) in old {
+        path.push(PathSegment::Key(key.clone()));
+        match find(new, key) {
+            Some(new_val) => diff_into(path, val, new_val, ops),
+            None => ops.push(PatchOp::Remove { path: path.clone() }),
+        }
+        path.pop();
+    }
+
+    for (key, val) in new {
+        if find(old, key).is_none() {
+            let mut path = path.clone();
+            path.push(PathSegment::Key(key.clone()));
+            ops.push(PatchOp::Add { path, value: val.clone() });
+        }
+    }
+}
+
+/// Applies the operations of the patch to the value in order.
+///
+/// Applying the patch returned by `diff(old, new)` to `old` makes it equal to `new`.
+///
+/// # Errors
+///
+/// Returns [`PatchError::PathNotFound`] if an operation refers to something that doesn't exist,
+/// for example because the patch was computed against a different value. The operations before
+/// the failed one stay applied.
+pub fn apply_patch(val: &mut Value, patch: &Patch) -> Result<(), PatchError> {
+    for (idx, op) in patch.ops().iter().enumerate() {
+        apply_op(val, op).ok_or(PatchError::PathNotFound(idx))?;
+    }
+    Ok(())
+}
+
+fn apply_op(val: &mut Value, op: &PatchOp) -> Option<()> {
+    let (last, parent) = match op.path().split_last() {
+        Some((last, parent)) => (last, parent),
+        None => {
+            match *op {
+                PatchOp::Replace { ref value, .. } => *val = value.clone(),
+                PatchOp::Add { .. } | PatchOp::Remove { .. } => return None,
+            }
+            return Some(());
+        }
+    };
+
+    let mut parent_val = val;
+    for segment in parent {
+        parent_val = child_mut(parent_val, segment)?;
+    }
+
+    match (op, parent_val, last) {
+        (PatchOp::Add { value, .. }, Value::Array(vec), PathSegment::Index(idx)) if *idx <= vec.len() => {
+            vec.insert(*idx, value.clone());
+        }
+        (PatchOp::Add { value, .. }, Value::Map(entries), PathSegment::Key(key)) => {
+            entries.push((key.clone(), value.clone()));
+        }
+        (PatchOp::Remove { .. }, Value::Array(vec), PathSegment::Index(idx)) if *idx < vec.len() => {
+            vec.remove(*idx);
+        }
+        (PatchOp::Remove { .. }, Value::Map(entries), PathSegment::Key(key)) => {
+            let pos = entries.iter().position(|(k, _)| k == key)?;
+            entries.remove(pos);
+        }
+        (PatchOp::Replace { value, .. }, parent_val, last) => {
+            *child_mut(parent_val, last)? = value.clone();
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+fn child_mut<'a>(val: &'a mut Value, segment: &PathSegment) -> Option<&'a mut Value> {
+    match (val, segment) {
+        (Value::Array(vec), PathSegment::Index(idx)) => vec.get_mut(*idx),
+        (Value::Map(entries), PathSegment::Key(key)) => {
+            entries.iter_mut().find(|(k, _)| k == key).map(|(_, v)| v)
+        }
+        _ => None,
+    }
+}
@@ -1,4 +1,9 @@
-use rmpv::decode::{read_value, Error};
+use std::io;
+
+use rmpv::decode::{
+    read_value, read_value_collect_errors, read_value_eof_aware, read_value_with_registry, Error, ExtRegistry,
+};
+use rmpv::patch::PathSegment;
 use rmpv::Value;
 
 #[test]
@@ -62,12 +67,40 @@ fn from_i32_decode_value() {
     assert_eq!(Value::from(-1), read_value(&mut &buf[..]).unwrap());
 }
 
+#[cfg(feature = "float")]
 #[test]
 fn from_f64_decode_value() {
     let buf = [0xcb, 0xff, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
     assert_eq!(Value::F64(::std::f64::NEG_INFINITY), read_value(&mut &buf[..]).unwrap());
 }
 
+#[cfg(not(feature = "float"))]
+#[test]
+fn fail_f64_decode_value_without_float_support() {
+    use rmp::Marker;
+
+    let buf = [0xcb, 0xff, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+    match read_value(&mut &buf[..]) {
+        Err(Error::UnsupportedMarker(Marker::F64)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(not(feature = "float"))]
+#[test]
+fn fail_nested_f32_decode_value_without_float_support() {
+    use rmp::Marker;
+
+    // [1, 3.0]
+    let buf = [0x92, 0x01, 0xca, 0x40, 0x40, 0x00, 0x00];
+
+    match read_value(&mut &buf[..]) {
+        Err(Error::UnsupportedMarker(Marker::F32)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(io::ErrorKind::Unsupported, read_value(&mut &buf[..]).unwrap_err().kind());
+}
 
 #[test]
 fn from_strfix_decode_value() {
@@ -397,3 +430,284 @@ fn invalid_buf_size_arr() {
         Err(e) => panic!("Unexpected error: {}", e),
     }
 }
+
+#[test]
+fn from_ext_with_registry_decode_value() {
+    let mut registry = ExtRegistry::new();
+    registry.register(10, |data| Ok(Value::from(u16::from_be_bytes([data[0], data[1]]))));
+
+    // {"a": ext(10, [1, 2]), "b": ext(11, [3])}
+    let buf = [0x82, 0xa1, 0x61, 0xd5, 0x0a, 0x01, 0x02, 0xa1, 0x62, 0xd4, 0x0b, 0x03];
+
+    let expected = Value::Map(vec![
+        (Value::from("a"), Value::from(258)),
+        (Value::from("b"), Value::Ext(11, vec![3])),
+    ]);
+    assert_eq!(expected, read_value_with_registry(&mut &buf[..], &registry).unwrap());
+    assert!(registry.contains(10));
+    assert!(!registry.contains(11));
+}
+
+#[test]
+fn from_ext_with_empty_registry_decode_value() {
+    let buf = [0xd4, 0x0a, 0x2a];
+    assert_eq!(Value::Ext(10, vec![42]), read_value_with_registry(&mut &buf[..], &ExtRegistry::new()).unwrap());
+}
+
+#[test]
+fn from_ext_with_registry_handler_error() {
+    let mut registry = ExtRegistry::new();
+    registry.register(10, |_| Err(io::Error::new(io::ErrorKind::InvalidData, "bad decimal")));
+
+    let buf = [0xd4, 0x0a, 0x2a];
+    match read_value_with_registry(&mut &buf[..], &registry) {
+        Err(Error::InvalidDataRead(err)) => assert_eq!(io::ErrorKind::InvalidData, err.kind()),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_reserved_decode_value() {
+    let buf = [0xc1];
+
+    match read_value(&mut &buf[..]) {
+        Err(Error::ReservedMarker) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_reserved_in_map_decode_value() {
+    let buf = [0x81, 0xa1, 0x6b, 0xc1];
+
+    match read_value(&mut &buf[..]) {
+        Err(Error::ReservedMarker) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(std::io::ErrorKind::InvalidData, read_value(&mut &buf[..]).unwrap_err().kind());
+}
+
+#[cfg(feature = "float")]
+#[test]
+fn from_stream_decode_value_eof_aware() {
+    // 42, ["a", nil], 3.0
+    let buf = [0x2a, 0x92, 0xa1, 0x61, 0xc0, 0xca, 0x40, 0x40, 0x00, 0x00];
+    let mut rd = &buf[..];
+
+    assert_eq!(Some(Value::from(42)), read_value_eof_aware(&mut rd).unwrap());
+    assert_eq!(Some(Value::Array(vec![Value::from("a"), Value::Nil])), read_value_eof_aware(&mut rd).unwrap());
+    assert_eq!(Some(Value::F32(3.0)), read_value_eof_aware(&mut rd).unwrap());
+    assert_eq!(None, read_value_eof_aware(&mut rd).unwrap());
+    assert_eq!(None, read_value_eof_aware(&mut rd).unwrap());
+}
+
+#[test]
+fn from_empty_decode_value_eof_aware() {
+    assert_eq!(None, read_value_eof_aware(&mut &[][..]).unwrap());
+}
+
+#[test]
+fn from_truncated_decode_value_eof_aware() {
+    // ["a", 300]
+    let buf = [0x92, 0xa1, 0x61, 0xcd, 0x01, 0x2c];
+
+    for len in 1..buf.len() {
+        match read_value_eof_aware(&mut &buf[..len]) {
+            Err(Error::Truncated) => (),
+            other => panic!("unexpected result for {} bytes: {:?}", len, other),
+        }
+    }
+    assert!(read_value_eof_aware(&mut &buf[..]).unwrap().is_some());
+    assert_eq!(io::ErrorKind::UnexpectedEof, Error::Truncated.kind());
+}
+
+#[test]
+fn from_failing_reader_decode_value_eof_aware() {
+    struct Failing(bool);
+
+    impl io::Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            // Interrupted first, which must be retried.
+            if self.0 {
+                Err(io::ErrorKind::BrokenPipe.into())
+            } else {
+                self.0 = true;
+                Err(io::ErrorKind::Interrupted.into())
+            }
+        }
+    }
+
+    match read_value_eof_aware(&mut Failing(false)) {
+        Err(Error::InvalidMarkerRead(ref err)) if err.kind() == io::ErrorKind::BrokenPipe => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(all(feature = "bumpalo", feature = "float"))]
+#[test]
+fn from_complex_decode_value_in_arena() {
+    use rmpv::decode::arena::{read_value_in, ArenaValue};
+
+    let val = Value::Array(vec![
+        Value::from(42),
+        Value::from(-1.5),
+        Value::from("le message"),
+        Value::Binary(vec![1, 2, 3]),
+        Value::Map(vec![(Value::from("k"), Value::Array(vec![Value::Nil, Value::Boolean(true)]))]),
+        Value::Ext(7, vec![0xaa, 0xbb]),
+    ]);
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &val).unwrap();
+
+    let mut arena = bumpalo::Bump::new();
+    for _ in 0..2 {
+        let decoded = read_value_in(&mut &buf[..], &arena).unwrap();
+        match decoded {
+            ArenaValue::Array(items) => assert_eq!(6, items.len()),
+            other => panic!("unexpected value: {:?}", other),
+        }
+        assert_eq!(val, decoded.to_owned());
+        arena.reset();
+    }
+}
+
+#[cfg(feature = "bumpalo")]
+#[test]
+fn from_truncated_bin_decode_value_in_arena() {
+    use rmpv::decode::arena::read_value_in;
+
+    // A bin32 declaring 4 GiB of data followed by two bytes.
+    let buf = [0xc6, 0xff, 0xff, 0xff, 0xff, 0x01, 0x02];
+    let arena = bumpalo::Bump::new();
+
+    match read_value_in(&mut &buf[..], &arena) {
+        Err(Error::InvalidDataRead(err)) => assert_eq!(io::ErrorKind::UnexpectedEof, err.kind()),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(arena.allocated_bytes() < 1024 * 1024);
+}
+
+#[cfg(all(feature = "bumpalo", not(feature = "float")))]
+#[test]
+fn fail_f64_decode_value_in_arena_without_float_support() {
+    use rmp::Marker;
+    use rmpv::decode::arena::read_value_in;
+
+    let buf = [0xcb, 0x7f, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+    let arena = bumpalo::Bump::new();
+
+    match read_value_in(&mut &buf[..], &arena) {
+        Err(Error::UnsupportedMarker(Marker::F64)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_valid_decode_value_collect_errors() {
+    let val = Value::Map(vec![
+        (Value::from("k"), Value::Array(vec![Value::from(1), Value::from("v"), Value::Binary(vec![2])])),
+        (Value::Array(vec![Value::Nil]), Value::Ext(1, vec![3, 4])),
+    ]);
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &val).unwrap();
+
+    let mut rd = &buf[..];
+    let (decoded, errors) = read_value_collect_errors(&mut rd).unwrap();
+    assert_eq!(val, decoded);
+    assert!(errors.is_empty());
+    assert!(rd.is_empty());
+}
+
+#[test]
+fn from_reserved_decode_value_collect_errors() {
+    // [<reserved>, {<reserved>: 1, "k": {"n": <reserved>}}, 2]
+    let buf = [0x93, 0xc1, 0x82, 0xc1, 0x01, 0xa1, 0x6b, 0x81, 0xa1, 0x6e, 0xc1, 0x02];
+
+    let (val, errors) = read_value_collect_errors(&mut &buf[..]).unwrap();
+    assert_eq!(
+        Value::Array(vec![
+            Value::Nil,
+            Value::Map(vec![
+                (Value::Nil, Value::from(1)),
+                (Value::from("k"), Value::Map(vec![(Value::from("n"), Value::Nil)])),
+            ]),
+            Value::from(2),
+        ]),
+        val
+    );
+
+    let paths: Vec<_> = errors.iter().map(|(path, _)| path.clone()).collect();
+    assert_eq!(
+        vec![
+            vec![PathSegment::Index(0)],
+            vec![PathSegment::Index(1), PathSegment::Key(Value::Nil)],
+            vec![PathSegment::Index(1), PathSegment::Key(Value::from("k")), PathSegment::Key(Value::from("n"))],
+        ],
+        paths
+    );
+    for (_, err) in &errors {
+        match *err {
+            Error::ReservedMarker => (),
+            ref other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn from_too_deep_decode_value_collect_errors() {
+    // [[[...[1, "x"]...]], 3] with the inner arrays nested past the depth limit.
+    let mut buf = vec![0x91; rmpv::decode::MAX_DEPTH / 2 + 11];
+    buf[0] = 0x92;
+    buf.extend(&[0x92, 0x01, 0xa1, 0x78, 0x03]);
+
+    let mut rd = &buf[..];
+    let (val, errors) = read_value_collect_errors(&mut rd).unwrap();
+    assert!(rd.is_empty());
+    assert_eq!(Some(&Value::from(3)), val.get_index(1));
+
+    assert_eq!(1, errors.len());
+    let (ref path, ref err) = errors[0];
+    assert_eq!(rmpv::decode::MAX_DEPTH / 2, path.len());
+    match *err {
+        Error::DepthLimitExceeded => (),
+        ref other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn from_reserved_in_too_deep_decode_value_collect_errors() {
+    let mut buf = vec![0x91; rmpv::decode::MAX_DEPTH / 2 + 11];
+    buf.push(0xc1);
+
+    match read_value_collect_errors(&mut &buf[..]) {
+        Err(Error::ReservedMarker) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_truncated_decode_value_collect_errors() {
+    // ["a", <reserved>, 300] cut off in the last element.
+    let buf = [0x93, 0xa1, 0x61, 0xc1, 0xcd, 0x01];
+
+    match read_value_collect_errors(&mut &buf[..]) {
+        Err(Error::InvalidDataRead(ref err)) if err.kind() == io::ErrorKind::UnexpectedEof => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(not(feature = "float"))]
+#[test]
+fn from_float_decode_value_collect_errors_without_float_support() {
+    use rmp::Marker;
+
+    // [1.5, 2]
+    let buf = [0x92, 0xcb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02];
+
+    let (val, errors) = read_value_collect_errors(&mut &buf[..]).unwrap();
+    assert_eq!(Value::Array(vec![Value::Nil, Value::from(2)]), val);
+    match errors[..] {
+        [(ref path, Error::UnsupportedMarker(Marker::F64))] => assert_eq!(vec![PathSegment::Index(0)], *path),
+        ref other => panic!("unexpected errors: {:?}", other),
+    }
+}
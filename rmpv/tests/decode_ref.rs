@@ -134,6 +134,7 @@ fn from_i64() {
     assert_eq!(ValueRef::from(9223372036854775807i64), read_value_ref(&mut rd).unwrap());
 }
 
+#[cfg(feature = "float")]
 #[test]
 fn from_f32() {
     let buf = [0xca, 0x7f, 0x7f, 0xff, 0xff];
@@ -143,6 +144,7 @@ fn from_f32() {
     assert_eq!(ValueRef::F32(3.4028234e38_f32), read_value_ref(&mut rd).unwrap());
 }
 
+#[cfg(feature = "float")]
 #[test]
 fn from_f64() {
     use std::f64;
@@ -154,6 +156,21 @@ fn from_f64() {
     assert_eq!(ValueRef::F64(f64::INFINITY), read_value_ref(&mut rd).unwrap());
 }
 
+#[cfg(not(feature = "float"))]
+#[test]
+fn fail_f32_without_float_support() {
+    use rmp::Marker;
+
+    let buf = [0xca, 0x7f, 0x7f, 0xff, 0xff];
+
+    let mut rd = &buf[..];
+
+    match read_value_ref(&mut rd) {
+        Err(Error::UnsupportedMarker(Marker::F32)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
 #[test]
 fn from_strfix() {
     let buf = [0xaa, 0x6c, 0x65, 0x20, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65];
@@ -633,7 +650,22 @@ fn from_reserved() {
 
     let mut rd = &buf[..];
 
-    assert_eq!(ValueRef::Nil, read_value_ref(&mut rd).unwrap());
+    match read_value_ref(&mut rd) {
+        Err(Error::ReservedMarker) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_reserved_nested() {
+    let buf = [0x92, 0xc0, 0xc1];
+
+    let mut rd = &buf[..];
+
+    match read_value_ref(&mut rd) {
+        Err(Error::ReservedMarker) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
 }
 
 #[test]
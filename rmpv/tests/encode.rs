@@ -0,0 +1,204 @@
+use rmp::Marker;
+use rmpv::decode::read_value;
+use rmpv::encode::{write_value, write_value_with, IntPolicy, WriteOptions};
+use rmpv::{Integer, Value};
+
+fn encode_with(val: &Value, policy: IntPolicy) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_value_with(&mut buf, val, policy).unwrap();
+    buf
+}
+
+#[test]
+fn pack_with_preserve_policy_reproduces_integer_encoding() {
+    let bufs: &[&[u8]] = &[
+        &[0x2a],
+        &[0xe0],
+        &[0xcc, 0x01],
+        &[0xcd, 0x00, 0x01],
+        &[0xce, 0x00, 0x00, 0x00, 0x01],
+        &[0xcf, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01],
+        &[0xd0, 0x01],
+        &[0xd1, 0xff, 0xff],
+        &[0xd2, 0x00, 0x00, 0x00, 0x01],
+        &[0xd3, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+    ];
+
+    for buf in bufs {
+        let val = read_value(&mut &buf[..]).unwrap();
+        assert_eq!(*buf, &encode_with(&val, IntPolicy::Preserve)[..]);
+    }
+}
+
+#[test]
+fn pack_with_preserve_policy_nested() {
+    // {"id": u32(7), "tags": [i16(-1), 2]}
+    let buf = [
+        0x82, 0xa2, 0x69, 0x64, 0xce, 0x00, 0x00, 0x00, 0x07,
+        0xa4, 0x74, 0x61, 0x67, 0x73, 0x92, 0xd1, 0xff, 0xff, 0x02,
+    ];
+    let val = read_value(&mut &buf[..]).unwrap();
+
+    assert_eq!(&buf[..], &encode_with(&val, IntPolicy::Preserve)[..]);
+}
+
+#[test]
+fn pack_with_minimal_policy_ignores_marker() {
+    let buf = [0xd3, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05];
+    let val = read_value(&mut &buf[..]).unwrap();
+
+    let mut out = Vec::new();
+    write_value(&mut out, &val).unwrap();
+    assert_eq!([0x05], &out[..]);
+    assert_eq!(out, encode_with(&val, IntPolicy::Minimal));
+    assert_eq!(IntPolicy::Minimal, IntPolicy::default());
+}
+
+#[test]
+fn pack_with_preserve_policy_uses_explicit_marker() {
+    let val = Value::Array(vec![
+        Value::Integer(Integer::from(5).with_marker(Marker::U16).unwrap()),
+        Value::Integer(Integer::from(-2).with_marker(Marker::I64).unwrap()),
+        Value::from(300),
+    ]);
+
+    assert_eq!(
+        vec![
+            0x93, 0xcd, 0x00, 0x05, 0xd3, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+            0xcd, 0x01, 0x2c,
+        ],
+        encode_with(&val, IntPolicy::Preserve)
+    );
+}
+
+#[test]
+fn integer_marker_does_not_affect_equality() {
+    let wide = read_value(&mut &[0xcf, 0, 0, 0, 0, 0, 0, 0, 0x01][..]).unwrap();
+    let narrow = read_value(&mut &[0x01][..]).unwrap();
+
+    assert_eq!(wide, narrow);
+    assert_eq!(Value::from(1), wide);
+}
+
+#[test]
+fn integer_with_marker_rejects_misfits() {
+    assert!(Integer::from(128).with_marker(Marker::FixPos(0)).is_none());
+    assert!(Integer::from(-33).with_marker(Marker::FixNeg(-1)).is_none());
+    assert!(Integer::from(1).with_marker(Marker::FixNeg(-1)).is_none());
+    assert!(Integer::from(u64::MAX).with_marker(Marker::I64).is_none());
+    assert!(Integer::from(200).with_marker(Marker::I8).is_none());
+    assert!(Integer::from(1).with_marker(Marker::Null).is_none());
+    assert!(Integer::from(127).with_marker(Marker::I8).is_some());
+}
+
+#[test]
+fn integer_remembers_sign_family() {
+    let cases: &[(&[u8], bool, bool)] = &[
+        (&[0x2a], false, false),
+        (&[0xe0], false, false),
+        (&[0xcc, 0x01], false, true),
+        (&[0xcf, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01], false, true),
+        (&[0xd0, 0x01], true, false),
+        (&[0xd3, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01], true, false),
+    ];
+
+    for &(buf, signed, unsigned) in cases {
+        let val = read_value(&mut &buf[..]).unwrap();
+        let n = val.as_integer().unwrap();
+        assert_eq!((signed, unsigned), (n.was_signed(), n.was_unsigned()), "{:?}", buf);
+        assert_eq!(buf, &encode_with(&val, IntPolicy::Preserve)[..]);
+    }
+
+    let n = Integer::from(1);
+    assert!(!n.was_signed() && !n.was_unsigned());
+    assert!(n.with_marker(Marker::I32).unwrap().was_signed());
+}
+
+fn encode_narrowing(val: &Value) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_value_with(&mut buf, val, WriteOptions::new().with_float_narrowing(true)).unwrap();
+    buf
+}
+
+#[test]
+fn pack_f64_without_narrowing_by_default() {
+    let mut buf = Vec::new();
+    write_value(&mut buf, &Value::F64(0.5)).unwrap();
+    assert_eq!([0xcb, 0x3f, 0xe0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &buf[..]);
+
+    assert!(!WriteOptions::default().allow_float_narrowing());
+    assert_eq!(buf, encode_with(&Value::F64(0.5), IntPolicy::Preserve));
+}
+
+#[test]
+fn pack_f64_narrowed_when_exact() {
+    let cases = [0.5, -0.0, 1.0e10, f64::INFINITY, f64::NEG_INFINITY, f64::from(std::f32::consts::PI)];
+
+    for &v in &cases {
+        let buf = encode_narrowing(&Value::F64(v));
+        assert_eq!(0xca, buf[0]);
+        assert_eq!((v as f32).to_bits().to_be_bytes(), buf[1..]);
+    }
+}
+
+#[test]
+fn pack_f64_not_narrowed_when_lossy() {
+    let cases = [0.1, std::f64::consts::PI, 1.0e300, 1.0e-300, f64::MAX];
+
+    for &v in &cases {
+        let buf = encode_narrowing(&Value::F64(v));
+        assert_eq!(0xcb, buf[0]);
+        assert_eq!(v.to_bits().to_be_bytes(), buf[1..]);
+    }
+}
+
+#[test]
+fn pack_with_options_combines_int_policy_and_narrowing() {
+    // [u16(1), 2.0f64]
+    let val = Value::Array(vec![
+        Value::Integer(Integer::from(1).with_marker(Marker::U16).unwrap()),
+        Value::F64(2.0),
+    ]);
+
+    let mut buf = Vec::new();
+    let options = WriteOptions::new().with_int_policy(IntPolicy::Preserve).with_float_narrowing(true);
+    write_value_with(&mut buf, &val, options).unwrap();
+    assert_eq!([0x92, 0xcd, 0x00, 0x01, 0xca, 0x40, 0x00, 0x00, 0x00], &buf[..]);
+}
+
+#[test]
+fn pack_thousand_deep_array() {
+    let mut val = Value::Nil;
+    for _ in 0..1000 {
+        val = Value::Array(vec![val]);
+    }
+
+    let mut buf = Vec::new();
+    write_value(&mut buf, &val).unwrap();
+
+    let mut expected = vec![0x91; 1000];
+    expected.push(0xc0);
+    assert_eq!(expected, buf);
+}
+
+#[test]
+fn pack_nested_containers_in_order() {
+    // [{[1, 2]: {"a": [3]}, nil: 4}, 5]
+    let val = Value::Array(vec![
+        Value::Map(vec![
+            (
+                Value::Array(vec![Value::from(1), Value::from(2)]),
+                Value::Map(vec![(Value::from("a"), Value::Array(vec![Value::from(3)]))]),
+            ),
+            (Value::Nil, Value::from(4)),
+        ]),
+        Value::from(5),
+    ]);
+
+    let mut buf = Vec::new();
+    write_value(&mut buf, &val).unwrap();
+    assert_eq!(
+        [0x92, 0x82, 0x92, 0x01, 0x02, 0x81, 0xa1, 0x61, 0x91, 0x03, 0xc0, 0x04, 0x05],
+        &buf[..]
+    );
+}
@@ -23,6 +23,13 @@ quickcheck! {
         mirror_test(xs)
     }
 
+    fn mirror_str(xs: String) -> bool {
+        mirror_test(xs)
+    }
+}
+
+#[cfg(feature = "float")]
+quickcheck! {
     fn mirror_f32_value(xs: f32) -> bool {
         let mut buf = Vec::new();
         write_value(&mut buf, &Value::from(xs)).unwrap();
@@ -38,8 +45,4 @@ quickcheck! {
 
         eq || (!eq && xs.is_nan())
     }
-
-    fn mirror_str(xs: String) -> bool {
-        mirror_test(xs)
-    }
 }
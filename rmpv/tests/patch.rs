@@ -0,0 +1,168 @@
+use rmpv::patch::{PatchError, PatchOp, PathSegment};
+use rmpv::{apply_patch, diff, Patch, Value};
+
+fn array(vals: &[i64]) -> Value {
+    Value::Array(vals.iter().map(|&v| Value::from(v)).collect())
+}
+
+fn check_round_trip(old: &Value, new: &Value) -> Patch {
+    let patch = diff(old, new);
+    let mut val = old.clone();
+    apply_patch(&mut val, &patch).unwrap();
+    assert_eq!(*new, val, "patch: {:?}", patch);
+    patch
+}
+
+#[test]
+fn pass_diff_equal_is_empty() {
+    let val = Value::Map(vec![(Value::from("a"), array(&[1, 2, 3]))]);
+    assert!(diff(&val, &val.clone()).is_empty());
+}
+
+#[test]
+fn pass_diff_scalar_replaces_root() {
+    let patch = check_round_trip(&Value::from(1), &Value::from("one"));
+    assert_eq!(&[PatchOp::Replace { path: vec![], value: Value::from("one") }], patch.ops());
+}
+
+#[test]
+fn pass_diff_map_entries() {
+    let old = Value::Map(vec![
+        (Value::from("kept"), Value::from(1)),
+        (Value::from("changed"), Value::from(2)),
+        (Value::from("removed"), Value::from(3)),
+    ]);
+    let new = Value::Map(vec![
+        (Value::from("kept"), Value::from(1)),
+        (Value::from("changed"), Value::from(20)),
+        (Value::from("added"), Value::from(4)),
+    ]);
+
+    let patch = check_round_trip(&old, &new);
+    assert_eq!(
+        &[
+            PatchOp::Replace { path: vec![PathSegment::Key(Value::from("changed"))], value: Value::from(20) },
+            PatchOp::Remove { path: vec![PathSegment::Key(Value::from("removed"))] },
+            PatchOp::Add { path: vec![PathSegment::Key(Value::from("added"))], value: Value::from(4) },
+        ],
+        patch.ops()
+    );
+}
+
+#[test]
+fn pass_diff_map_with_non_string_keys() {
+    let old = Value::Map(vec![
+        (Value::from(1), Value::from("a")),
+        (array(&[1, 2]), Value::Nil),
+        (Value::Nil, Value::from(true)),
+    ]);
+    let new = Value::Map(vec![
+        (Value::from(1), Value::from("b")),
+        (Value::Nil, Value::from(true)),
+        (Value::Binary(vec![0xff]), Value::from(false)),
+    ]);
+
+    let patch = check_round_trip(&old, &new);
+    assert_eq!(3, patch.ops().len());
+}
+
+#[test]
+fn pass_diff_map_reordered_replaces_map() {
+    let old = Value::Map(vec![(Value::from("a"), Value::from(1)), (Value::from("b"), Value::from(2))]);
+    let new = Value::Map(vec![(Value::from("b"), Value::from(2)), (Value::from("a"), Value::from(1))]);
+
+    let patch = check_round_trip(&old, &new);
+    assert_eq!(&[PatchOp::Replace { path: vec![], value: new }], patch.ops());
+}
+
+#[test]
+fn pass_diff_array_insert_and_remove() {
+    let patch = check_round_trip(&array(&[1, 2, 3, 4, 5]), &array(&[1, 3, 4, 9, 5]));
+    assert_eq!(
+        &[
+            PatchOp::Remove { path: vec![PathSegment::Index(1)] },
+            PatchOp::Add { path: vec![PathSegment::Index(3)], value: Value::from(9) },
+        ],
+        patch.ops()
+    );
+}
+
+#[test]
+fn pass_diff_array_nested() {
+    let old = Value::Array(vec![
+        Value::from("header"),
+        Value::Map(vec![(Value::from("n"), array(&[1, 2]))]),
+    ]);
+    let new = Value::Array(vec![
+        Value::from("header"),
+        Value::Map(vec![(Value::from("n"), array(&[1, 2, 3]))]),
+        Value::from("footer"),
+    ]);
+
+    let patch = check_round_trip(&old, &new);
+    assert_eq!(
+        &[
+            PatchOp::Add {
+                path: vec![PathSegment::Index(1), PathSegment::Key(Value::from("n")), PathSegment::Index(2)],
+                value: Value::from(3),
+            },
+            PatchOp::Add { path: vec![PathSegment::Index(2)], value: Value::from("footer") },
+        ],
+        patch.ops()
+    );
+}
+
+#[test]
+fn pass_diff_round_trip_arrays() {
+    let cases: &[(&[i64], &[i64])] = &[
+        (&[], &[1, 2, 3]),
+        (&[1, 2, 3], &[]),
+        (&[1, 2, 3], &[3, 2, 1]),
+        (&[1, 1, 1, 2], &[2, 1, 1, 1]),
+        (&[1, 2, 3, 4, 5, 6], &[0, 2, 4, 6, 8]),
+        (&[5, 6, 7], &[1, 2, 5, 3, 7, 4]),
+    ];
+    for &(old, new) in cases {
+        check_round_trip(&array(old), &array(new));
+    }
+}
+
+#[test]
+fn pass_diff_round_trip_long_arrays() {
+    let old: Vec<i64> = (0..3000).collect();
+    let new: Vec<i64> = (0..3000).filter(|v| v % 7 != 0).map(|v| if v % 11 == 0 { -v } else { v }).collect();
+    check_round_trip(&array(&old), &array(&new));
+}
+
+#[test]
+fn pass_diff_map_with_nan_key() {
+    // {NaN: 1, "a": 1} to {"a": 2}
+    let old = Value::Map(vec![(Value::F64(f64::NAN), Value::from(1)), (Value::from("a"), Value::from(1))]);
+    let new = Value::Map(vec![(Value::from("a"), Value::from(2))]);
+
+    let patch = check_round_trip(&old, &new);
+    assert_eq!(&[PatchOp::Replace { path: vec![], value: new.clone() }], patch.ops());
+}
+
+#[test]
+fn fail_apply_patch_path_not_found() {
+    let patch = Patch::new(vec![
+        PatchOp::Replace { path: vec![PathSegment::Index(0)], value: Value::from(10) },
+        PatchOp::Remove { path: vec![PathSegment::Index(5)] },
+    ]);
+
+    let mut val = array(&[1, 2]);
+    assert_eq!(Err(PatchError::PathNotFound(1)), apply_patch(&mut val, &patch));
+    assert_eq!(array(&[10, 2]), val);
+}
+
+#[test]
+fn fail_apply_patch_through_scalar() {
+    let patch = Patch::new(vec![PatchOp::Add {
+        path: vec![PathSegment::Key(Value::from("a")), PathSegment::Index(0)],
+        value: Value::Nil,
+    }]);
+
+    let mut val = Value::Map(vec![(Value::from("a"), Value::from(1))]);
+    assert_eq!(Err(PatchError::PathNotFound(0)), apply_patch(&mut val, &patch));
+}
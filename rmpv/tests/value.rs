@@ -1,4 +1,4 @@
-use rmpv::Value;
+use rmpv::{Value, ValueKey};
 
 #[test]
 fn display_nil() {
@@ -140,12 +140,96 @@ fn from_iterator() {
     assert!(w != w3);
 }
 
+#[test]
+fn from_iterator_of_pairs() {
+    let w: Value = vec![("a", 1), ("b", 2), ("a", 3)].into_iter().collect();
+
+    assert_eq!(Value::Map(vec![
+        (Value::from("a"), Value::from(1)),
+        (Value::from("b"), Value::from(2)),
+        (Value::from("a"), Value::from(3)),
+    ]), w);
+}
+
+#[test]
+fn from_strings_and_bytes() {
+    assert_eq!(Value::from("le message"), Value::from(String::from("le message")));
+    assert_eq!(Value::Binary(vec![1, 2]), Value::from(&[1u8, 2][..]));
+    assert_eq!(Value::Binary(vec![1, 2]), Value::from(vec![1u8, 2]));
+    assert_eq!(Value::Array(vec![Value::Nil]), Value::from(vec![Value::Nil]));
+    assert_eq!(Value::Map(vec![(Value::Nil, Value::Nil)]), Value::from(vec![(Value::Nil, Value::Nil)]));
+}
+
+#[test]
+fn from_integers_encode_independently_of_source_type() {
+    fn encode(val: Value) -> Vec<u8> {
+        let mut buf = Vec::new();
+        rmpv::encode::write_value(&mut buf, &val).unwrap();
+        buf
+    }
+
+    assert_eq!(vec![0x05], encode(Value::from(5u8)));
+    assert_eq!(vec![0x05], encode(Value::from(5i64)));
+    assert_eq!(vec![0xcc, 0xc8], encode(Value::from(200i32)));
+    assert_eq!(vec![0xcc, 0xc8], encode(Value::from(200u64)));
+    assert_eq!(vec![0xff], encode(Value::from(-1i64)));
+    assert_eq!(vec![0xd0, 0x80], encode(Value::from(-128i16)));
+}
+
 #[test]
 fn is_nil() {
     assert!(Value::Nil.is_nil());
     assert!(!Value::Boolean(true).is_nil());
 }
 
+#[test]
+fn is_accessors_match_exactly_one_variant() {
+    type Check = (&'static str, fn(&Value) -> bool);
+
+    fn kinds(val: &Value) -> Vec<&'static str> {
+        let checks: [Check; 10] = [
+            ("nil", Value::is_nil),
+            ("bool", Value::is_bool),
+            ("integer", Value::is_integer),
+            ("f32", Value::is_f32),
+            ("f64", Value::is_f64),
+            ("str", Value::is_str),
+            ("bin", Value::is_bin),
+            ("array", Value::is_array),
+            ("map", Value::is_map),
+            ("ext", Value::is_ext),
+        ];
+        checks.iter().filter(|(_, f)| f(val)).map(|&(name, _)| name).collect()
+    }
+
+    assert_eq!(vec!["nil"], kinds(&Value::Nil));
+    assert_eq!(vec!["bool"], kinds(&Value::Boolean(false)));
+    assert_eq!(vec!["integer"], kinds(&Value::from(-1)));
+    assert_eq!(vec!["integer"], kinds(&Value::from(u64::MAX)));
+    assert_eq!(vec!["f32"], kinds(&Value::F32(1.0)));
+    assert_eq!(vec!["f64"], kinds(&Value::F64(1.0)));
+    assert_eq!(vec!["str"], kinds(&Value::from("le message")));
+    assert_eq!(vec!["bin"], kinds(&Value::Binary(vec![1])));
+    assert_eq!(vec!["array"], kinds(&Value::Array(vec![])));
+    assert_eq!(vec!["map"], kinds(&Value::Map(vec![])));
+    assert_eq!(vec!["ext"], kinds(&Value::Ext(1, vec![])));
+}
+
+#[test]
+fn as_accessors() {
+    assert_eq!(Some(()), Value::Nil.as_nil());
+    assert_eq!(None, Value::from(0).as_nil());
+    assert_eq!(Some(rmpv::Integer::from(u64::MAX)), Value::from(u64::MAX).as_integer());
+    assert_eq!(None, Value::F64(1.0).as_integer());
+    assert_eq!(Some(1.5), Value::F32(1.5).as_f32());
+    assert_eq!(None, Value::F64(1.5).as_f32());
+    assert_eq!(Some(&[1u8][..]), Value::Binary(vec![1]).as_bin());
+    assert_eq!(None, Value::from("a").as_bin());
+    assert_eq!(Some(&b"a"[..]), Value::from("a").as_slice());
+    assert_eq!(Some((-1, &[1u8, 2][..])), Value::Ext(-1, vec![1, 2]).as_ext());
+    assert_eq!(None, Value::Binary(vec![1, 2]).as_ext());
+}
+
 #[test]
 fn monadic_index() {
     let val = Value::Array(vec![
@@ -186,6 +270,88 @@ fn index_into_map() {
     assert!(val["d"][4].is_nil());
 }
 
+#[test]
+fn get_from_array_and_map() {
+    let val = Value::Map(vec![
+        ( Value::String("a".into()), Value::Nil ),
+        ( Value::String("b".into()), Value::Array(vec![Value::from(3)]) ),
+    ]);
+
+    assert_eq!(Some(&Value::Nil), val.get("a"));
+    assert_eq!(None, val.get("c"));
+    assert_eq!(Some(&Value::from(3)), val.get("b").and_then(|b| b.get_index(0)));
+    assert_eq!(None, val.get("b").and_then(|b| b.get_index(1)));
+    assert_eq!(None, val.get_index(0));
+    assert_eq!(None, val["b"].get("a"));
+}
+
+#[test]
+fn iterate_array_and_map() {
+    let array = Value::Array(vec![Value::from(1), Value::from(2)]);
+    let mut sum = 0;
+    for v in &array {
+        sum += v.as_u64().unwrap();
+    }
+    assert_eq!(3, sum);
+    assert_eq!(0, array.entries().count());
+
+    let map = Value::Map(vec![(Value::from("a"), Value::from(1))]);
+    assert_eq!(vec![(&Value::from("a"), &Value::from(1))], map.entries().map(|(k, v)| (k, v)).collect::<Vec<_>>());
+    assert_eq!(0, (&map).into_iter().count());
+}
+
+#[test]
+fn modify_in_place() {
+    let mut val = Value::Map(vec![
+        ( Value::String("user".into()), Value::from("root") ),
+        ( Value::String("password".into()), Value::from("hunter2") ),
+        ( Value::String("tags".into()), Value::Array(vec![Value::from(1)]) ),
+    ]);
+
+    *val.get_mut("password").unwrap() = Value::from("<redacted>");
+    val.get_mut("tags").and_then(|t| t.as_array_mut()).unwrap().push(Value::from(2));
+    *val.get_mut("tags").and_then(|t| t.get_index_mut(0)).unwrap() = Value::from(0);
+    *val.entry("id").unwrap() = Value::from(42);
+    assert_eq!(Some(Value::from("root")), val.remove("user"));
+    assert!(val.get_mut("user").is_none());
+    assert!(val.get_index_mut(0).is_none());
+
+    let expected = Value::Map(vec![
+        ( Value::String("password".into()), Value::from("<redacted>") ),
+        ( Value::String("tags".into()), Value::Array(vec![Value::from(0), Value::from(2)]) ),
+        ( Value::String("id".into()), Value::from(42) ),
+    ]);
+    assert_eq!(expected, val);
+}
+
+#[test]
+fn take_nested_value() {
+    let mut val = Value::Map(vec![
+        ( Value::String("blob".into()), Value::Array(vec![Value::from("le message"), Value::from(42)]) ),
+    ]);
+
+    let blob = val.get_mut("blob").unwrap().take();
+    assert_eq!(Value::Array(vec![Value::from("le message"), Value::from(42)]), blob);
+    assert_eq!(Value::Map(vec![(Value::String("blob".into()), Value::Nil)]), val);
+
+    let mut nil = Value::Nil;
+    assert_eq!(Value::Nil, nil.take());
+    assert_eq!(Value::Nil, nil);
+}
+
+#[test]
+fn mut_accessors_on_mismatched_types() {
+    let mut val = Value::from(42);
+
+    assert!(val.as_array_mut().is_none());
+    assert!(val.as_map_mut().is_none());
+    assert!(val.as_str_mut().is_none());
+    assert!(val.as_slice_mut().is_none());
+    assert!(val.as_ext_mut().is_none());
+    assert!(val.entry("key").is_none());
+    assert!(val.remove("key").is_none());
+}
+
 #[test]
 fn try_from_val() {
   use rmpv::Utf8String;
@@ -196,3 +362,89 @@ fn try_from_val() {
   assert_eq!(String::from("spook"), TryInto::<String>::try_into(Value::from("spook")).unwrap());
   assert_eq!(vec![0], TryInto::<Vec<u8>>::try_into(Value::Binary(vec![0u8])).unwrap());
 }
+
+#[test]
+fn hash_is_consistent_with_eq() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash_of(val: &Value) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        val.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    assert_eq!(Value::from(0.0), Value::from(-0.0));
+    assert_eq!(hash_of(&Value::from(0.0)), hash_of(&Value::from(-0.0)));
+    assert_eq!(hash_of(&Value::from(42)), hash_of(&Value::from(42u8)));
+}
+
+#[test]
+fn value_key_compares_floats_by_bits() {
+    use std::collections::HashSet;
+
+    let mut set = HashSet::new();
+    set.insert(ValueKey(Value::from(0.0)));
+    set.insert(ValueKey(Value::from(f64::NAN)));
+    set.insert(ValueKey(Value::Array(vec![Value::from(f32::NAN), Value::Nil])));
+    set.insert(ValueKey(Value::from("le message")));
+    // The same NaN is de-duplicated.
+    set.insert(ValueKey(Value::from(f64::NAN)));
+    assert_eq!(4, set.len());
+
+    assert!(set.contains(&ValueKey(Value::from(f64::NAN))));
+    assert!(set.contains(&ValueKey(Value::Array(vec![Value::from(f32::NAN), Value::Nil]))));
+    assert!(set.contains(&ValueKey(Value::from("le message"))));
+    assert!(!set.contains(&ValueKey(Value::from(-0.0))));
+    assert!(!set.contains(&ValueKey(Value::from(0.0f32))));
+    assert!(!set.contains(&ValueKey(Value::Binary(b"le message".to_vec()))));
+
+    assert_eq!(ValueKey(Value::from(42)), ValueKey(Value::from(42u8)));
+    assert_eq!(ValueKey(&Value::from(f32::NAN)), ValueKey(&Value::from(f32::NAN)));
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn as_index_map_preserves_order_and_last_duplicate_wins() {
+    let val = Value::Map(vec![
+        (Value::from("z"), Value::from(1)),
+        (Value::from(7), Value::from(2)),
+        (Value::from("z"), Value::from(3)),
+    ]);
+
+    let map = val.as_index_map().unwrap();
+    assert_eq!(2, map.len());
+    assert_eq!(vec![&Value::from("z"), &Value::from(7)], map.keys().map(|k| k.0).collect::<Vec<_>>());
+    assert_eq!(Some(&&Value::from(3)), map.get(&ValueKey(&Value::from("z"))));
+    assert_eq!(Some(&&Value::from(2)), map.get(&ValueKey(&Value::from(7))));
+
+    assert_eq!(None, Value::Array(vec![]).as_index_map());
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn into_index_map_round_trip() {
+    let val = Value::Map(vec![
+        (Value::from("b"), Value::from(1)),
+        (Value::from("a"), Value::from(2)),
+    ]);
+
+    let map = val.clone().into_index_map().unwrap();
+    assert_eq!(Some(&Value::from(2)), map.get(&ValueKey(Value::from("a"))));
+    assert_eq!(val, Value::from(map));
+
+    assert_eq!(None, Value::Nil.into_index_map());
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn as_index_map_finds_nan_keys() {
+    let val = Value::Map(vec![
+        (Value::from(f64::NAN), Value::from(1)),
+        (Value::from(f64::NAN), Value::from(2)),
+    ]);
+
+    let map = val.as_index_map().unwrap();
+    assert_eq!(1, map.len());
+    assert_eq!(Some(&&Value::from(2)), map.get(&ValueKey(&Value::from(f64::NAN))));
+}